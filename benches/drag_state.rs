@@ -0,0 +1,111 @@
+// 热路径基准测试：拖拽状态机、滚轮节流，以及按事件类型分流的完整处理流程
+// 负载来自 src/load.rs，与 JS 侧 runSyntheticLoad 使用同一个生成器
+//
+// cargo bench --features bench
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rdev::EventType;
+use std::time::{Duration, Instant};
+
+// 直接引入不依赖 napi 的模块，避免基准测试链接 napi 符号
+// 基准测试不运行 #[test]，模块里测试用的导入在这里未被使用
+#[allow(dead_code, unused_imports)]
+#[path = "../src/state_machine.rs"]
+mod state_machine;
+
+#[allow(dead_code, unused_imports)]
+#[path = "../src/load.rs"]
+mod load;
+
+use state_machine::{DragStateMachine, PointerInput, WheelThrottle};
+
+// 1 kHz 为常见鼠标回报率，8 kHz 为高端游戏鼠标的上限
+const RATES: [u32; 2] = [1_000, 8_000];
+
+// 与监听线程一致：按键事件使用最近一次移动的坐标
+fn pointer_input(event_type: &EventType, last: &mut (f64, f64)) -> Option<PointerInput> {
+    match *event_type {
+        EventType::MouseMove { x, y } => {
+            *last = (x, y);
+            Some(PointerInput::Move { x, y })
+        }
+        EventType::ButtonPress(_) => Some(PointerInput::Press { button: 0, x: last.0, y: last.1 }),
+        EventType::ButtonRelease(_) => Some(PointerInput::Release { button: 0, x: last.0, y: last.1 }),
+        _ => None,
+    }
+}
+
+fn bench_state_machine(c: &mut Criterion) {
+    let mut group = c.benchmark_group("drag_state_machine");
+    for rate in RATES {
+        let mut last = (0.0, 0.0);
+        let inputs: Vec<PointerInput> = load::generate(rate, Duration::from_secs(1))
+            .iter()
+            .filter_map(|(_, event_type)| pointer_input(event_type, &mut last))
+            .collect();
+        group.throughput(Throughput::Elements(inputs.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(format!("{}hz", rate)), &inputs, |b, inputs| {
+            b.iter(|| {
+                let mut machine = DragStateMachine::new();
+                for input in inputs {
+                    black_box(machine.handle(*input));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_wheel_filter(c: &mut Criterion) {
+    let mut group = c.benchmark_group("wheel_filter");
+    for rate in RATES {
+        let deltas: Vec<(Duration, f64, f64)> = load::generate(rate, Duration::from_secs(1))
+            .into_iter()
+            .filter_map(|(offset, event_type)| match event_type {
+                EventType::Wheel { delta_x, delta_y } => Some((offset, delta_x as f64, -(delta_y as f64))),
+                _ => None,
+            })
+            .collect();
+        group.throughput(Throughput::Elements(deltas.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(format!("{}hz", rate)), &deltas, |b, deltas| {
+            b.iter(|| {
+                let start = Instant::now();
+                let mut throttle = WheelThrottle::new();
+                throttle.throttle_ms = 16;
+                for (offset, delta_x, delta_y) in deltas {
+                    black_box(throttle.push(*delta_x, *delta_y, start + *offset));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+// 按事件类型分流到状态机或滚轮节流，对应监听线程里分发之前的全部工作
+fn bench_pipeline(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pipeline");
+    for rate in RATES {
+        let events = load::generate(rate, Duration::from_secs(1));
+        group.throughput(Throughput::Elements(events.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(format!("{}hz", rate)), &events, |b, events| {
+            b.iter(|| {
+                let start = Instant::now();
+                let mut machine = DragStateMachine::new();
+                let mut throttle = WheelThrottle::new();
+                throttle.throttle_ms = 16;
+                let mut last = (0.0, 0.0);
+                for (offset, event_type) in events {
+                    if let EventType::Wheel { delta_x, delta_y } = *event_type {
+                        black_box(throttle.push(delta_x as f64, -(delta_y as f64), start + *offset));
+                    } else if let Some(input) = pointer_input(event_type, &mut last) {
+                        black_box(machine.handle(input));
+                    }
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_state_machine, bench_wheel_filter, bench_pipeline);
+criterion_main!(benches);
@@ -12,6 +12,7 @@ fn main() {
         println!("cargo:rustc-link-lib=framework=Cocoa");
         println!("cargo:rustc-link-lib=framework=AppKit");
         println!("cargo:rustc-link-lib=framework=Foundation");
+        println!("cargo:rustc-link-lib=framework=ApplicationServices");
 
         // Set deployment target for better compatibility
         println!("cargo:rustc-env=MACOSX_DEPLOYMENT_TARGET=10.13");
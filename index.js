@@ -310,7 +310,7 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`)
 }
 
-const { startMouseMonitor, stopMouseMonitor, onMouseEvent, removeMouseEventListener, onDragEvent, removeDragEventListener, isMonitoring } = nativeBinding
+const { startMouseMonitor, stopMouseMonitor, onMouseEvent, removeMouseEventListener, onDragEvent, removeDragEventListener, isMonitoring, onInputEvent, removeInputEventListener, onWheelEvent, removeWheelEventListener, setWheelThrottle, pauseMonitoring, resumeMonitoring, isPaused, setWindowChromeDragMode, getCursorPosition, getInputState, annotateDrag, setDragTimeout, startExternalDragWatch, stopExternalDragWatch, onExternalDragEvent, removeExternalDragEventListener, runSyntheticLoad, DragEndReason, setReferenceDisplay, getReferenceDisplay, setButtonNumbering, getSchema, setSeedFromButtonState, getPrimaryDisplaySize, getCapabilities, onCurrentDrag, removeCurrentDragListener, getSystemTheme, onSystemThemeChange, removeSystemThemeListener, startNativeFileDrag, serializeEvent, deserializeEvent, getDisplayName, normalizePath, setDragThreshold, startFilePromiseDrag, createDragImage, getScreenColorAt, captureScreenRegion, CaptureFormat, getDisplays, setCoordinateSpace, getCoordinateSpace, setIgnoreOwnProcessEvents, setIgnoreSyntheticEvents, setDragPathRecording, addScreenRegion, removeScreenRegion, onRegionEvent, removeRegionEventListener, grabMouse, releaseMouseGrab, onMonitorError, removeMonitorErrorListener, setAutoMonitor, removeAllMouseListeners, removeAllDragListeners, configure, initialize, MouseEventType, DragEventType, __injectEvent, getCallbackOverflowCount, startGestureWatch, stopGestureWatch, onGestureEvent, removeGestureEventListener, addIgnoreRegion, removeIgnoreRegion, setActiveAppFilter, getFrontmostApplication, startClipboardMonitor, stopClipboardMonitor, onClipboardChange, removeClipboardChangeListener, writeFilesToClipboard, readFilesFromClipboard, setScreenEdgeDetection, onScreenEdgeEvent, removeScreenEdgeEventListener, attachDropTarget, detachDropTarget, onDropTargetEvent, removeDropTargetEventListener, getWindowBounds, DragMonitor, setLogLevel, setLogFile, onLog, removeLogListener, getDiagnostics } = nativeBinding
 
 module.exports.startMouseMonitor = startMouseMonitor
 module.exports.stopMouseMonitor = stopMouseMonitor
@@ -319,3 +319,96 @@ module.exports.removeMouseEventListener = removeMouseEventListener
 module.exports.onDragEvent = onDragEvent
 module.exports.removeDragEventListener = removeDragEventListener
 module.exports.isMonitoring = isMonitoring
+module.exports.onInputEvent = onInputEvent
+module.exports.removeInputEventListener = removeInputEventListener
+module.exports.onWheelEvent = onWheelEvent
+module.exports.removeWheelEventListener = removeWheelEventListener
+module.exports.setWheelThrottle = setWheelThrottle
+module.exports.pauseMonitoring = pauseMonitoring
+module.exports.resumeMonitoring = resumeMonitoring
+module.exports.isPaused = isPaused
+module.exports.setWindowChromeDragMode = setWindowChromeDragMode
+module.exports.getCursorPosition = getCursorPosition
+module.exports.getInputState = getInputState
+module.exports.annotateDrag = annotateDrag
+module.exports.setDragTimeout = setDragTimeout
+module.exports.startExternalDragWatch = startExternalDragWatch
+module.exports.stopExternalDragWatch = stopExternalDragWatch
+module.exports.onExternalDragEvent = onExternalDragEvent
+module.exports.removeExternalDragEventListener = removeExternalDragEventListener
+module.exports.runSyntheticLoad = runSyntheticLoad
+module.exports.DragEndReason = DragEndReason
+module.exports.setReferenceDisplay = setReferenceDisplay
+module.exports.getReferenceDisplay = getReferenceDisplay
+module.exports.setButtonNumbering = setButtonNumbering
+module.exports.getSchema = getSchema
+module.exports.setSeedFromButtonState = setSeedFromButtonState
+module.exports.getPrimaryDisplaySize = getPrimaryDisplaySize
+module.exports.getCapabilities = getCapabilities
+module.exports.onCurrentDrag = onCurrentDrag
+module.exports.removeCurrentDragListener = removeCurrentDragListener
+module.exports.getSystemTheme = getSystemTheme
+module.exports.onSystemThemeChange = onSystemThemeChange
+module.exports.removeSystemThemeListener = removeSystemThemeListener
+module.exports.startNativeFileDrag = startNativeFileDrag
+module.exports.serializeEvent = serializeEvent
+module.exports.deserializeEvent = deserializeEvent
+module.exports.getDisplayName = getDisplayName
+module.exports.normalizePath = normalizePath
+module.exports.setDragThreshold = setDragThreshold
+module.exports.startFilePromiseDrag = startFilePromiseDrag
+module.exports.createDragImage = createDragImage
+module.exports.getScreenColorAt = getScreenColorAt
+module.exports.captureScreenRegion = captureScreenRegion
+module.exports.CaptureFormat = CaptureFormat
+module.exports.getDisplays = getDisplays
+module.exports.setCoordinateSpace = setCoordinateSpace
+module.exports.getCoordinateSpace = getCoordinateSpace
+module.exports.setIgnoreOwnProcessEvents = setIgnoreOwnProcessEvents
+module.exports.setIgnoreSyntheticEvents = setIgnoreSyntheticEvents
+module.exports.setDragPathRecording = setDragPathRecording
+module.exports.addScreenRegion = addScreenRegion
+module.exports.removeScreenRegion = removeScreenRegion
+module.exports.onRegionEvent = onRegionEvent
+module.exports.removeRegionEventListener = removeRegionEventListener
+module.exports.grabMouse = grabMouse
+module.exports.releaseMouseGrab = releaseMouseGrab
+module.exports.onMonitorError = onMonitorError
+module.exports.removeMonitorErrorListener = removeMonitorErrorListener
+module.exports.setAutoMonitor = setAutoMonitor
+module.exports.removeAllMouseListeners = removeAllMouseListeners
+module.exports.removeAllDragListeners = removeAllDragListeners
+module.exports.configure = configure
+module.exports.initialize = initialize
+module.exports.MouseEventType = MouseEventType
+module.exports.DragEventType = DragEventType
+module.exports.__injectEvent = __injectEvent
+module.exports.getCallbackOverflowCount = getCallbackOverflowCount
+module.exports.startGestureWatch = startGestureWatch
+module.exports.stopGestureWatch = stopGestureWatch
+module.exports.onGestureEvent = onGestureEvent
+module.exports.removeGestureEventListener = removeGestureEventListener
+module.exports.addIgnoreRegion = addIgnoreRegion
+module.exports.removeIgnoreRegion = removeIgnoreRegion
+module.exports.setActiveAppFilter = setActiveAppFilter
+module.exports.getFrontmostApplication = getFrontmostApplication
+module.exports.startClipboardMonitor = startClipboardMonitor
+module.exports.stopClipboardMonitor = stopClipboardMonitor
+module.exports.onClipboardChange = onClipboardChange
+module.exports.removeClipboardChangeListener = removeClipboardChangeListener
+module.exports.writeFilesToClipboard = writeFilesToClipboard
+module.exports.readFilesFromClipboard = readFilesFromClipboard
+module.exports.setScreenEdgeDetection = setScreenEdgeDetection
+module.exports.onScreenEdgeEvent = onScreenEdgeEvent
+module.exports.removeScreenEdgeEventListener = removeScreenEdgeEventListener
+module.exports.attachDropTarget = attachDropTarget
+module.exports.detachDropTarget = detachDropTarget
+module.exports.onDropTargetEvent = onDropTargetEvent
+module.exports.removeDropTargetEventListener = removeDropTargetEventListener
+module.exports.getWindowBounds = getWindowBounds
+module.exports.DragMonitor = DragMonitor
+module.exports.setLogLevel = setLogLevel
+module.exports.setLogFile = setLogFile
+module.exports.onLog = onLog
+module.exports.removeLogListener = removeLogListener
+module.exports.getDiagnostics = getDiagnostics
@@ -80,7 +80,7 @@
 //! what key was interpreted by the OS at that time, it will respect the layout.
 //!
 //! ```no_run
-//! # use crate::rdev::EventType;
+//! # use crate::rdev::{EventType, PointerDetail, ScrollDetail};
 //! # use std::time::SystemTime;
 //! /// When events arrive from the system we can add some information
 //! /// time is when the event was received.
@@ -89,6 +89,9 @@
 //!     pub time: SystemTime,
 //!     pub name: Option<String>,
 //!     pub event_type: EventType,
+//!     pub synthetic: bool,
+//!     pub scroll: Option<ScrollDetail>,
+//!     pub pointer: Option<PointerDetail>,
 //! }
 //! ```
 //!
@@ -219,7 +222,8 @@
 //! Serde if you install this library with the `serialize` feature.
 mod rdev;
 pub use crate::rdev::{
-    Button, DisplayError, Event, EventType, GrabCallback, GrabError, ListenError, SimulateError,
+    Button, DisplayError, Event, EventType, GrabCallback, GrabError, ListenError, PointerDetail,
+    PointerType, ScrollDetail, ScrollPhase, SimulateError,
 };
 
 #[cfg(target_os = "macos")]
@@ -326,8 +330,10 @@ pub use crate::macos::grab as _grab;
 pub use crate::windows::grab as _grab;
 #[cfg(feature = "unstable_grab")]
 /// Grabbing global events. In the callback, returning None ignores the event
-/// and returning the event let's it pass. There is no modification of the event
-/// possible here.
+/// and returning the event let's it pass. On MacOS the grab uses a default
+/// (filtering) event tap on mouse events only, and a returned event may also
+/// rewrite the position of a `MouseMove` or the deltas of a `Wheel`; elsewhere
+/// there is no modification of the event possible.
 /// Caveat: On MacOS, you require the grab
 /// loop needs to be the primary app (no fork before) and need to have accessibility
 /// settings enabled.
@@ -139,7 +139,11 @@ macro_rules! convert_buttons {
 convert_buttons!(
     BTN_LEFT, Left,
     BTN_RIGHT, Right,
-    BTN_MIDDLE, Middle
+    BTN_MIDDLE, Middle,
+    BTN_SIDE, Back,
+    BTN_EXTRA, Forward,
+    BTN_BACK, Back,
+    BTN_FORWARD, Forward
 );
 
 //TODO: IntlBackslash, kpDelete
@@ -391,6 +395,9 @@ where
             time: SystemTime::now(),
             name,
             event_type,
+            synthetic: false,
+            scroll: None,
+            pointer: None,
         };
         if callback(rdev_event).is_some() {
             (Some(event), GrabStatus::Continue)
@@ -580,7 +587,6 @@ fn setup_devices() -> io::Result<(RawFd, Vec<Device>, Vec<UInputDevice>)> {
 fn setup_inotify(epoll_fd: RawFd, devices: &[Device]) -> io::Result<Inotify> {
     //Ensure there is space for inotify at last epoll index.
     if devices.len() as u64 >= INOTIFY_DATA {
-        eprintln!("number of devices: {}", devices.len());
         return Err(io::Error::other("too many device files!"));
     }
     // Set up inotify to listen for new devices being plugged in
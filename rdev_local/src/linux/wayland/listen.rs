@@ -30,6 +30,9 @@ fn convert_type(libevent: LibEvent) -> Option<EventType> {
                 272 => Some(Button::Left),
                 273 => Some(Button::Right),
                 274 => Some(Button::Middle),
+                // BTN_SIDE / BTN_EXTRA, and the less common BTN_FORWARD / BTN_BACK
+                275 | 278 => Some(Button::Back),
+                276 | 277 => Some(Button::Forward),
                 _ => None,
             };
             if let Some(rdev_btn) = rdev_btn {
@@ -68,6 +71,9 @@ fn convert(keyboard: &mut Keyboard, libevent: LibEvent) -> Option<Event> {
         time: SystemTime::now(),
         name,
         event_type,
+        synthetic: false,
+        scroll: None,
+        pointer: None,
     })
 }
 
@@ -96,6 +96,8 @@ impl Handle {
                         Button::Left => UKey::ButtonLeft,
                         Button::Right => UKey::ButtonRight,
                         Button::Middle => UKey::ButtonMiddle,
+                        Button::Back => UKey::ButtonSide,
+                        Button::Forward => UKey::ButtonExtra,
                         Button::Unknown(_) => return Err(SimulateError),
                     };
                     let time = Self::get_current_time();
@@ -110,6 +112,8 @@ impl Handle {
                         Button::Left => UKey::ButtonLeft,
                         Button::Right => UKey::ButtonRight,
                         Button::Middle => UKey::ButtonMiddle,
+                        Button::Back => UKey::ButtonSide,
+                        Button::Forward => UKey::ButtonExtra,
                         Button::Unknown(_) => return Err(SimulateError),
                     };
                     let time = Self::get_current_time();
@@ -4,7 +4,8 @@ use crate::rdev::{Button, Event, EventType, KeyboardState};
 use std::convert::TryInto;
 use std::os::raw::{c_int, c_uchar, c_uint};
 use std::ptr::null;
-use std::time::SystemTime;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
 use x11::xlib;
 
 pub const TRUE: c_int = 1;
@@ -43,13 +44,17 @@ pub fn convert_event(code: c_uchar, type_: c_int, x: f64, y: f64) -> Option<Even
                 delta_y: 0,
                 delta_x: 1,
             }),
+            8 => Some(EventType::ButtonPress(Button::Back)),
+            9 => Some(EventType::ButtonPress(Button::Forward)),
             code => Some(EventType::ButtonPress(Button::Unknown(code))),
         },
         xlib::ButtonRelease => match code {
             1 => Some(EventType::ButtonRelease(Button::Left)),
             2 => Some(EventType::ButtonRelease(Button::Middle)),
             3 => Some(EventType::ButtonRelease(Button::Right)),
-            4 | 5 => None,
+            4..=7 => None,
+            8 => Some(EventType::ButtonRelease(Button::Back)),
+            9 => Some(EventType::ButtonRelease(Button::Forward)),
             _ => Some(EventType::ButtonRelease(Button::Unknown(code))),
         },
         xlib::MotionNotify => Some(EventType::MouseMove { x, y }),
@@ -57,6 +62,26 @@ pub fn convert_event(code: c_uchar, type_: c_int, x: f64, y: f64) -> Option<Even
     }
 }
 
+/// Events injected by `simulate` through XTest that `listen` has not seen yet.
+/// XRecord reports them exactly like device events, so they are matched by
+/// event type in the order they were sent.
+pub static SIMULATED: Mutex<Vec<(EventType, Instant)>> = Mutex::new(Vec::new());
+pub const SIMULATED_TTL: Duration = Duration::from_millis(100);
+
+fn take_simulated(event_type: &EventType) -> bool {
+    let Ok(mut pending) = SIMULATED.lock() else {
+        return false;
+    };
+    pending.retain(|(_, sent)| sent.elapsed() < SIMULATED_TTL);
+    match pending.iter().position(|(pending_type, _)| pending_type == event_type) {
+        Some(index) => {
+            pending.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
 pub fn convert(
     keyboard: &mut Option<Keyboard>,
     code: c_uint,
@@ -71,6 +96,9 @@ pub fn convert(
         event_type,
         time: SystemTime::now(),
         name,
+        synthetic: take_simulated(&event_type),
+        scroll: None,
+        pointer: None,
     })
 }
 
@@ -1,9 +1,8 @@
 use super::common::Display;
-use super::keyboard::Keyboard;
-use crate::rdev::{Button, Event, EventType, GrabError, Key, KeyboardState};
+use crate::rdev::{Button, Event, EventType, GrabError};
 use epoll::ControlOptions::{EPOLL_CTL_ADD, EPOLL_CTL_DEL};
 use evdev_rs::{
-    Device, InputEvent, UInputDevice,
+    Device, DeviceWrapper, InputEvent, UInputDevice,
     enums::{EV_KEY, EV_REL, EventCode},
 };
 use inotify::{Inotify, WatchMask};
@@ -21,31 +20,6 @@ use std::time::SystemTime;
 // TODO The x, y coordinates are currently wrong !! Is there mouse acceleration
 // to take into account ??
 
-macro_rules! convert_keys {
-    ($($ev_key:ident, $rdev_key:ident),*) => {
-        //TODO: make const when rust lang issue #49146 is fixed
-        #[allow(unreachable_patterns)]
-        fn evdev_key_to_rdev_key(key: &EV_KEY) -> Option<Key> {
-            match key {
-                $(
-                    EV_KEY::$ev_key => Some(Key::$rdev_key),
-                )*
-                _ => None,
-            }
-        }
-
-        // //TODO: make const when rust lang issue #49146 is fixed
-        // fn rdev_key_to_evdev_key(key: &Key) -> Option<EV_KEY> {
-        //     match key {
-        //         $(
-        //             Key::$rdev_key => Some(EV_KEY::$ev_key),
-        //         )*
-        //         _ => None
-        //     }
-        // }
-    };
-}
-
 macro_rules! convert_buttons {
     ($($ev_key:ident, $rdev_key:ident),*) => {
         //TODO: make const when rust lang issue #49146 is fixed
@@ -74,118 +48,11 @@ macro_rules! convert_buttons {
 convert_buttons!(
     BTN_LEFT, Left,
     BTN_RIGHT, Right,
-    BTN_MIDDLE, Middle
-);
-
-//TODO: IntlBackslash, kpDelete
-#[rustfmt::skip]
-convert_keys!(
-    KEY_ESC, Escape,
-    KEY_1, Num1,
-    KEY_2, Num2,
-    KEY_3, Num3,
-    KEY_4, Num4,
-    KEY_5, Num5,
-    KEY_6, Num6,
-    KEY_7, Num7,
-    KEY_8, Num8,
-    KEY_9, Num9,
-    KEY_0, Num0,
-    KEY_MINUS, Minus,
-    KEY_EQUAL, Equal,
-    KEY_BACKSPACE, Backspace,
-    KEY_TAB, Tab,
-    KEY_Q, KeyQ,
-    KEY_W, KeyW,
-    KEY_E, KeyE,
-    KEY_R, KeyR,
-    KEY_T, KeyT,
-    KEY_Y, KeyY,
-    KEY_U, KeyU,
-    KEY_I, KeyI,
-    KEY_O, KeyO,
-    KEY_P, KeyP,
-    KEY_LEFTBRACE, LeftBracket,
-    KEY_RIGHTBRACE, RightBracket,
-    KEY_ENTER, Return,
-    KEY_LEFTCTRL, ControlLeft,
-    KEY_A, KeyA,
-    KEY_S, KeyS,
-    KEY_D, KeyD,
-    KEY_F, KeyF,
-    KEY_G, KeyG,
-    KEY_H, KeyH,
-    KEY_J, KeyJ,
-    KEY_K, KeyK,
-    KEY_L, KeyL,
-    KEY_SEMICOLON, SemiColon,
-    KEY_APOSTROPHE, Quote,
-    KEY_GRAVE, BackQuote,
-    KEY_LEFTSHIFT, ShiftLeft,
-    KEY_BACKSLASH, BackSlash,
-    KEY_Z, KeyZ,
-    KEY_X, KeyX,
-    KEY_C, KeyC,
-    KEY_V, KeyV,
-    KEY_B, KeyB,
-    KEY_N, KeyN,
-    KEY_M, KeyM,
-    KEY_COMMA, Comma,
-    KEY_DOT, Dot,
-    KEY_SLASH, Slash,
-    KEY_RIGHTSHIFT, ShiftRight,
-    KEY_KPASTERISK , KpMultiply,
-    KEY_LEFTALT, Alt,
-    KEY_SPACE, Space,
-    KEY_CAPSLOCK, CapsLock,
-    KEY_F1, F1,
-    KEY_F2, F2,
-    KEY_F3, F3,
-    KEY_F4, F4,
-    KEY_F5, F5,
-    KEY_F6, F6,
-    KEY_F7, F7,
-    KEY_F8, F8,
-    KEY_F9, F9,
-    KEY_F10, F10,
-    KEY_NUMLOCK, NumLock,
-    KEY_SCROLLLOCK, ScrollLock,
-    KEY_KP7, Kp7,
-    KEY_KP8, Kp8,
-    KEY_KP9, Kp9,
-    KEY_KPMINUS, KpMinus,
-    KEY_KP4, Kp4,
-    KEY_KP5, Kp5,
-    KEY_KP6, Kp6,
-    KEY_KPPLUS, KpPlus,
-    KEY_KP1, Kp1,
-    KEY_KP2, Kp2,
-    KEY_KP3, Kp3,
-    KEY_KP0, Kp0,
-    KEY_F11, F11,
-    KEY_F12, F12,
-    KEY_KPENTER, KpReturn,
-    KEY_RIGHTCTRL, ControlRight,
-    KEY_KPSLASH, KpDivide,
-    KEY_RIGHTALT, AltGr,
-    KEY_HOME , Home,
-    KEY_UP, UpArrow,
-    KEY_PAGEUP, PageUp,
-    KEY_LEFT, LeftArrow,
-    KEY_RIGHT, RightArrow,
-    KEY_END, End,
-    KEY_DOWN, DownArrow,
-    KEY_PAGEDOWN, PageDown,
-    KEY_INSERT, Insert,
-    KEY_DELETE, Delete,
-    KEY_PAUSE, Pause,
-    KEY_LEFTMETA, MetaLeft,
-    KEY_RIGHTMETA, MetaRight,
-    KEY_PRINT, PrintScreen,
-    // KpDelete behaves like normal Delete most of the time
-    KEY_DELETE, KpDelete,
-    // Linux doesn't have an IntlBackslash key
-    KEY_BACKSLASH, IntlBackslash
+    BTN_MIDDLE, Middle,
+    BTN_SIDE, Back,
+    BTN_EXTRA, Forward,
+    BTN_BACK, Back,
+    BTN_FORWARD, Forward
 );
 
 fn evdev_event_to_rdev_event(
@@ -197,21 +64,11 @@ fn evdev_event_to_rdev_event(
 ) -> Option<EventType> {
     match &event.event_code {
         EventCode::EV_KEY(key) => {
-            if let Some(button) = evdev_key_to_rdev_button(key) {
-                // first check if pressed key is a mouse button
-                match event.value {
-                    0 => Some(EventType::ButtonRelease(button)),
-                    _ => Some(EventType::ButtonPress(button)),
-                }
-            } else if let Some(key) = evdev_key_to_rdev_key(key) {
-                // check if pressed key is a keyboard key
-                match event.value {
-                    0 => Some(EventType::KeyRelease(key)),
-                    _ => Some(EventType::KeyPress(key)),
-                }
-            } else {
-                // if neither mouse button nor keyboard key, return none
-                None
+            // Keyboard keys are passed through untouched, only mouse buttons are reported
+            let button = evdev_key_to_rdev_button(key)?;
+            match event.value {
+                0 => Some(EventType::ButtonRelease(button)),
+                _ => Some(EventType::ButtonPress(button)),
             }
         }
         EventCode::EV_REL(mouse) => match mouse {
@@ -305,7 +162,6 @@ pub fn grab<T>(mut callback: T) -> Result<(), GrabError>
 where
     T: FnMut(Event) -> Option<Event> + 'static,
 {
-    let mut kb = Keyboard::new().ok_or(GrabError::KeyboardError)?;
     let display = Display::new().ok_or(GrabError::MissingDisplayError)?;
     let (width, height) = display.get_size().ok_or(GrabError::MissingDisplayError)?;
     let (current_x, current_y) = display
@@ -321,11 +177,13 @@ where
             // If we can't convert event, simulate it
             None => return (Some(event), GrabStatus::Continue),
         };
-        let name = kb.add(&event_type);
         let rdev_event = Event {
             time: SystemTime::now(),
-            name,
+            name: None,
             event_type,
+            synthetic: false,
+            scroll: None,
+            pointer: None,
         };
         if callback(rdev_event).is_some() {
             (Some(event), GrabStatus::Continue)
@@ -341,7 +199,7 @@ pub fn filter_map_events<F>(mut func: F) -> io::Result<()>
 where
     F: FnMut(InputEvent) -> (Option<InputEvent>, GrabStatus),
 {
-    let (epoll_fd, mut devices, output_devices) = setup_devices()?;
+    let (epoll_fd, mut devices, mut output_devices) = setup_devices()?;
     let mut inotify = setup_inotify(epoll_fd, &devices)?;
 
     //grab devices
@@ -364,7 +222,15 @@ where
                         event.mask.contains(inotify::EventMask::CREATE),
                         "inotify is listening for events other than file creation"
                     );
-                    add_device_to_epoll_from_inotify_event(epoll_fd, event, &mut devices)?;
+                    // A device that cannot be opened yet (udev still setting permissions)
+                    // is left ungrabbed rather than stopping the whole grab
+                    add_device_to_epoll_from_inotify_event(
+                        epoll_fd,
+                        event,
+                        &mut devices,
+                        &mut output_devices,
+                    )
+                    .ok();
                 }
             } else {
                 // Input device received event
@@ -474,35 +340,58 @@ fn inotify_devices() -> io::Result<Inotify> {
     Ok(inotify)
 }
 
+/// Only pointer devices are grabbed. Keyboards and other devices keep delivering
+/// their input directly, so a grab can never lock the user out of the keyboard.
+fn is_pointer(device: &Device) -> bool {
+    device.has(EventCode::EV_KEY(EV_KEY::BTN_LEFT)) || device.has(EventCode::EV_REL(EV_REL::REL_X))
+}
+
 fn add_device_to_epoll_from_inotify_event(
     epoll_fd: RawFd,
     event: inotify::Event<&OsStr>,
     devices: &mut Vec<Device>,
+    output_devices: &mut Vec<UInputDevice>,
 ) -> io::Result<()> {
+    let Some(name) = event.name else {
+        return Ok(());
+    };
     let mut device_path = OsString::from(DEV_PATH);
     device_path.push(OsString::from("/"));
-    device_path.push(event.name.unwrap());
+    device_path.push(name);
+    // Our own uinput copies appear in /dev/input as well, never grab those
+    if output_devices
+        .iter()
+        .any(|output| output.devnode().map(OsStr::new) == Some(device_path.as_os_str()))
+    {
+        return Ok(());
+    }
     // new plug events
-    let file = File::open(device_path)?;
-    let fd = file.as_raw_fd();
-    let device = Device::new_from_file(file)?;
+    let file = File::open(&device_path)?;
+    let mut device = Device::new_from_file(file)?;
+    if !is_pointer(&device) {
+        return Ok(());
+    }
+    let output_device = UInputDevice::create_from_device(&device)?;
+    device.grab(evdev_rs::GrabMode::Grab)?;
+    let fd = device.file().as_raw_fd();
     let event = epoll::Event::new(EPOLLIN, devices.len() as u64);
-    devices.push(device);
     epoll::ctl(epoll_fd, EPOLL_CTL_ADD, fd, event)?;
+    devices.push(device);
+    output_devices.push(output_device);
     Ok(())
 }
 
-/// Returns tuple of epoll_fd, all devices, and uinput devices, where
+/// Returns tuple of epoll_fd, all pointer devices, and uinput devices, where
 /// uinputdevices is the same length as devices, and each uinput device is
 /// a libevdev copy of its corresponding device.The epoll_fd is level-triggered
 /// on any available data in the original devices.
 fn setup_devices() -> io::Result<(RawFd, Vec<Device>, Vec<UInputDevice>)> {
-    let device_files = get_device_files(DEV_PATH)?;
-    let epoll_fd = epoll_watch_all(device_files.iter())?;
-    let devices = device_files
+    let devices = get_device_files(DEV_PATH)?
         .into_iter()
-        .map(Device::new_from_file)
-        .collect::<io::Result<Vec<Device>>>()?;
+        .filter_map(|file| Device::new_from_file(file).ok())
+        .filter(is_pointer)
+        .collect::<Vec<Device>>();
+    let epoll_fd = epoll_watch_all(devices.iter().map(|device| device.file()))?;
     let output_devices = devices
         .iter()
         .map(UInputDevice::create_from_device)
@@ -515,7 +404,6 @@ fn setup_devices() -> io::Result<(RawFd, Vec<Device>, Vec<UInputDevice>)> {
 fn setup_inotify(epoll_fd: RawFd, devices: &[Device]) -> io::Result<Inotify> {
     //Ensure there is space for inotify at last epoll index.
     if devices.len() as u64 >= INOTIFY_DATA {
-        eprintln!("number of devices: {}", devices.len());
         return Err(io::Error::other("too many device files!"));
     }
     // Set up inotify to listen for new devices being plugged in
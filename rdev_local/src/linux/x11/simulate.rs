@@ -1,8 +1,9 @@
-use super::common::{FALSE, TRUE};
+use super::common::{FALSE, SIMULATED, SIMULATED_TTL, TRUE};
 use super::keycodes::code_from_key;
 use crate::rdev::{Button, EventType, SimulateError};
 use std::os::raw::c_int;
 use std::ptr::null;
+use std::time::Instant;
 use x11::xlib;
 use x11::xtest;
 
@@ -21,6 +22,8 @@ unsafe fn send_native(event_type: &EventType, display: *mut xlib::Display) -> Op
                 Button::Left => xtest::XTestFakeButtonEvent(display, 1, TRUE, 0),
                 Button::Middle => xtest::XTestFakeButtonEvent(display, 2, TRUE, 0),
                 Button::Right => xtest::XTestFakeButtonEvent(display, 3, TRUE, 0),
+                Button::Back => xtest::XTestFakeButtonEvent(display, 8, TRUE, 0),
+                Button::Forward => xtest::XTestFakeButtonEvent(display, 9, TRUE, 0),
                 Button::Unknown(code) => {
                     xtest::XTestFakeButtonEvent(display, (*code).into(), TRUE, 0)
                 }
@@ -29,6 +32,8 @@ unsafe fn send_native(event_type: &EventType, display: *mut xlib::Display) -> Op
                 Button::Left => xtest::XTestFakeButtonEvent(display, 1, FALSE, 0),
                 Button::Middle => xtest::XTestFakeButtonEvent(display, 2, FALSE, 0),
                 Button::Right => xtest::XTestFakeButtonEvent(display, 3, FALSE, 0),
+                Button::Back => xtest::XTestFakeButtonEvent(display, 8, FALSE, 0),
+                Button::Forward => xtest::XTestFakeButtonEvent(display, 9, FALSE, 0),
                 Button::Unknown(code) => {
                     xtest::XTestFakeButtonEvent(display, (*code).into(), FALSE, 0)
                 }
@@ -70,7 +75,37 @@ unsafe fn send_native(event_type: &EventType, display: *mut xlib::Display) -> Op
     }
 }
 
+/// The events `listen` will report for one simulated event
+fn recorded_events(event_type: &EventType) -> Vec<EventType> {
+    match *event_type {
+        EventType::MouseMove { x, y } => vec![EventType::MouseMove {
+            x: x.round(),
+            y: y.round(),
+        }],
+        EventType::Wheel { delta_x, delta_y } => {
+            let horizontal = EventType::Wheel {
+                delta_x: delta_x.signum(),
+                delta_y: 0,
+            };
+            let vertical = EventType::Wheel {
+                delta_x: 0,
+                delta_y: delta_y.signum(),
+            };
+            let mut events = vec![horizontal; delta_x.unsigned_abs() as usize];
+            events.extend(vec![vertical; delta_y.unsigned_abs() as usize]);
+            events
+        }
+        other => vec![other],
+    }
+}
+
 pub fn simulate(event_type: &EventType) -> Result<(), SimulateError> {
+    // Registered before sending: XRecord may deliver the events before XSync returns
+    if let Ok(mut pending) = SIMULATED.lock() {
+        pending.retain(|(_, sent)| sent.elapsed() < SIMULATED_TTL);
+        let now = Instant::now();
+        pending.extend(recorded_events(event_type).into_iter().map(|event| (event, now)));
+    }
     unsafe {
         let dpy = xlib::XOpenDisplay(null());
         if dpy.is_null() {
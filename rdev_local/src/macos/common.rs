@@ -1,9 +1,90 @@
 #![allow(clippy::upper_case_acronyms)]
-use crate::rdev::{Button, Event, EventType};
+use crate::rdev::{
+    Button, Event, EventType, PointerDetail, PointerType, ScrollDetail, ScrollPhase,
+};
 use core::ptr::NonNull;
-use objc2_core_graphics::{CGEvent, CGEventField, CGEventType};
+use objc2_core_graphics::{CGEvent, CGEventField, CGEventSourceStateID, CGEventType};
 use std::time::SystemTime;
 
+/// Written to `EventSourceUserData` by `simulate`, so our own events can be
+/// recognised when they come back through a tap.
+pub const SIMULATED_USER_DATA: i64 = 0x7264_6576; // "rdev"
+
+unsafe fn is_synthetic(cg_event: NonNull<CGEvent>) -> bool {
+    let event = unsafe { Some(cg_event.as_ref()) };
+    let user_data = CGEvent::integer_value_field(event, CGEventField::EventSourceUserData);
+    let state_id = CGEvent::integer_value_field(event, CGEventField::EventSourceStateID);
+    user_data == SIMULATED_USER_DATA || state_id != CGEventSourceStateID::HIDSystemState.0 as i64
+}
+
+// NSEventPhase values used by ScrollWheelEventScrollPhase
+const SCROLL_PHASE_BEGAN: i64 = 1;
+const SCROLL_PHASE_CHANGED: i64 = 2;
+const SCROLL_PHASE_ENDED: i64 = 4;
+const SCROLL_PHASE_CANCELLED: i64 = 8;
+// CGMomentumScrollPhase values used by ScrollWheelEventMomentumPhase
+const MOMENTUM_PHASE_BEGIN: i64 = 1;
+const MOMENTUM_PHASE_CONTINUE: i64 = 2;
+const MOMENTUM_PHASE_END: i64 = 3;
+
+unsafe fn scroll_detail(cg_event: NonNull<CGEvent>) -> ScrollDetail {
+    let event = unsafe { Some(cg_event.as_ref()) };
+    let field = |field: CGEventField| CGEvent::integer_value_field(event, field);
+    let phase = match (
+        field(CGEventField::ScrollWheelEventScrollPhase),
+        field(CGEventField::ScrollWheelEventMomentumPhase),
+    ) {
+        (_, MOMENTUM_PHASE_BEGIN | MOMENTUM_PHASE_CONTINUE) => Some(ScrollPhase::Momentum),
+        (_, MOMENTUM_PHASE_END) => Some(ScrollPhase::Ended),
+        (SCROLL_PHASE_BEGAN, _) => Some(ScrollPhase::Began),
+        (SCROLL_PHASE_CHANGED, _) => Some(ScrollPhase::Changed),
+        (SCROLL_PHASE_ENDED | SCROLL_PHASE_CANCELLED, _) => Some(ScrollPhase::Ended),
+        _ => None,
+    };
+    ScrollDetail {
+        pixel_delta_x: field(CGEventField::ScrollWheelEventPointDeltaAxis2) as f64,
+        pixel_delta_y: field(CGEventField::ScrollWheelEventPointDeltaAxis1) as f64,
+        continuous: field(CGEventField::ScrollWheelEventIsContinuous) != 0,
+        phase,
+    }
+}
+
+// kCGEventMouseSubtypeTabletPoint: a mouse event generated by a tablet pen
+const MOUSE_SUBTYPE_TABLET_POINT: i64 = 1;
+
+unsafe fn pointer_detail(cg_event: NonNull<CGEvent>) -> PointerDetail {
+    let event = unsafe { Some(cg_event.as_ref()) };
+    if CGEvent::integer_value_field(event, CGEventField::MouseEventSubtype) != MOUSE_SUBTYPE_TABLET_POINT {
+        // A mouse reports 1.0 while a button is down, which is not a real pressure
+        return PointerDetail {
+            pointer_type: PointerType::Mouse,
+            pressure: None,
+            tilt_x: None,
+            tilt_y: None,
+        };
+    }
+    // Tilt is reported in [-1, 1]
+    let double = |field: CGEventField| CGEvent::double_value_field(event, field);
+    PointerDetail {
+        pointer_type: PointerType::Pen,
+        pressure: Some(double(CGEventField::TabletEventPointPressure)),
+        tilt_x: Some(double(CGEventField::TabletEventTiltX) * 90.0),
+        tilt_y: Some(double(CGEventField::TabletEventTiltY) * 90.0),
+    }
+}
+
+/// Button number of an `OtherMouse*` event: 2 is the middle button, 3 and 4
+/// are the side buttons. Higher buttons keep their 1-based number.
+unsafe fn other_button(cg_event: NonNull<CGEvent>) -> Button {
+    let event = unsafe { Some(cg_event.as_ref()) };
+    match CGEvent::integer_value_field(event, CGEventField::MouseEventButtonNumber) {
+        2 => Button::Middle,
+        3 => Button::Back,
+        4 => Button::Forward,
+        number => Button::Unknown((number + 1).clamp(0, u8::MAX as i64) as u8),
+    }
+}
+
 pub unsafe fn convert(
     _type: CGEventType,
     cg_event: NonNull<CGEvent>,
@@ -14,6 +95,8 @@ pub unsafe fn convert(
             CGEventType::LeftMouseUp => Some(EventType::ButtonRelease(Button::Left)),
             CGEventType::RightMouseDown => Some(EventType::ButtonPress(Button::Right)),
             CGEventType::RightMouseUp => Some(EventType::ButtonRelease(Button::Right)),
+            CGEventType::OtherMouseDown => Some(EventType::ButtonPress(other_button(cg_event))),
+            CGEventType::OtherMouseUp => Some(EventType::ButtonRelease(other_button(cg_event))),
             CGEventType::MouseMoved => {
                 let point = CGEvent::location(Some(cg_event.as_ref()));
                 Some(EventType::MouseMove {
@@ -28,7 +111,7 @@ pub unsafe fn convert(
                     y: point.y,
                 })
             }
-            CGEventType::RightMouseDragged => {
+            CGEventType::RightMouseDragged | CGEventType::OtherMouseDragged => {
                 let point = CGEvent::location(Some(cg_event.as_ref()));
                 Some(EventType::MouseMove {
                     x: point.x,
@@ -64,10 +147,17 @@ pub unsafe fn convert(
         };
 
         if let Some(event_type) = option_type {
+            let (scroll, pointer) = match event_type {
+                EventType::Wheel { .. } => (Some(scroll_detail(cg_event)), None),
+                _ => (None, Some(pointer_detail(cg_event))),
+            };
             return Some(Event {
                 event_type,
                 time: SystemTime::now(),
                 name: None,
+                synthetic: is_synthetic(cg_event),
+                scroll,
+                pointer,
             });
         }
     }
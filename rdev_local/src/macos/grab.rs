@@ -1,20 +1,72 @@
 #![allow(improper_ctypes_definitions)]
 use crate::macos::common::*;
-use crate::rdev::{Event, GrabError};
-use objc2_core_foundation::{CFMachPort, CFRunLoop, kCFRunLoopCommonModes};
+use crate::rdev::{Event, EventType, GrabError};
+use objc2_core_foundation::{CFMachPort, CFRetained, CFRunLoop, CGPoint, kCFRunLoopCommonModes};
 use objc2_core_graphics::{
-    CGEvent, CGEventTapCallBack, CGEventTapLocation, CGEventTapOptions, CGEventTapPlacement,
-    CGEventTapProxy, CGEventType, kCGEventMaskForAllEvents,
+    CGEvent, CGEventField, CGEventMask, CGEventTapCallBack, CGEventTapLocation, CGEventTapOptions,
+    CGEventTapPlacement, CGEventTapProxy, CGEventType,
 };
 use objc2_foundation::NSAutoreleasePool;
 use std::os::raw::c_void;
 use std::ptr::{NonNull, null_mut};
 
 static mut GLOBAL_CALLBACK: Option<Box<dyn FnMut(Event) -> Option<Event>>> = None;
+// Kept so the tap can be re-enabled after the system disables it
+static mut GLOBAL_TAP: Option<CFRetained<CFMachPort>> = None;
 
 #[link(name = "Cocoa", kind = "framework")]
 unsafe extern "C" {}
 
+// Only mouse events go through the grab tap. A default (filtering) tap sits in the
+// delivery path of every event it receives, so keyboard input is left alone entirely.
+const GRAB_EVENT_TYPES: [CGEventType; 11] = [
+    CGEventType::LeftMouseDown,
+    CGEventType::LeftMouseUp,
+    CGEventType::RightMouseDown,
+    CGEventType::RightMouseUp,
+    CGEventType::OtherMouseDown,
+    CGEventType::OtherMouseUp,
+    CGEventType::MouseMoved,
+    CGEventType::LeftMouseDragged,
+    CGEventType::RightMouseDragged,
+    CGEventType::OtherMouseDragged,
+    CGEventType::ScrollWheel,
+];
+
+fn grab_event_mask() -> CGEventMask {
+    GRAB_EVENT_TYPES
+        .iter()
+        .fold(0, |mask, event_type| mask | (1 << event_type.0))
+}
+
+// Write the fields the callback changed back into the native event. Only the
+// position of moves and the line deltas of wheel events can be rewritten; a
+// different event kind cannot be expressed on the same CGEvent and is ignored.
+unsafe fn apply_changes(cg_event: NonNull<CGEvent>, original: &EventType, changed: &EventType) {
+    if original == changed {
+        return;
+    }
+    let event = unsafe { cg_event.as_ref() };
+    match (original, changed) {
+        (EventType::MouseMove { .. }, EventType::MouseMove { x, y }) => {
+            CGEvent::set_location(Some(event), CGPoint::new(*x, *y));
+        }
+        (EventType::Wheel { .. }, EventType::Wheel { delta_x, delta_y }) => {
+            CGEvent::set_integer_value_field(
+                Some(event),
+                CGEventField::ScrollWheelEventDeltaAxis1,
+                *delta_y,
+            );
+            CGEvent::set_integer_value_field(
+                Some(event),
+                CGEventField::ScrollWheelEventDeltaAxis2,
+                *delta_x,
+            );
+        }
+        _ => {}
+    }
+}
+
 unsafe extern "C-unwind" fn raw_callback(
     _proxy: CGEventTapProxy,
     _type: CGEventType,
@@ -22,13 +74,25 @@ unsafe extern "C-unwind" fn raw_callback(
     _user_info: *mut c_void,
 ) -> *mut CGEvent {
     unsafe {
+        // A tap whose callback is too slow (or that is interrupted by secure input) is
+        // disabled by the system; turn it back on instead of silently losing the grab.
+        if _type == CGEventType::TapDisabledByTimeout || _type == CGEventType::TapDisabledByUserInput {
+            let tap = &raw const GLOBAL_TAP;
+            if let Some(tap) = &*tap {
+                CGEvent::tap_enable(tap, true);
+            }
+            return cg_event.as_ptr();
+        }
         if let Some(event) = convert(_type, cg_event) {
             // Reborrowing the global callback pointer.
             // Totally UB. but not sure there's a great alternative.
             let ptr = &raw mut GLOBAL_CALLBACK;
             if let Some(callback) = &mut *ptr {
-                if callback(event).is_none() {
-                    CGEvent::set_type(Some(cg_event.as_ref()), CGEventType::Null)
+                let original = event.event_type;
+                match callback(event) {
+                    // Returning NULL from a default tap deletes the event
+                    None => return null_mut(),
+                    Some(event) => apply_changes(cg_event, &original, &event.event_type),
                 }
             }
         }
@@ -48,7 +112,7 @@ where
             CGEventTapLocation::HIDEventTap, // HID, Session, AnnotatedSession,
             CGEventTapPlacement::HeadInsertEventTap,
             CGEventTapOptions::Default,
-            kCGEventMaskForAllEvents.into(),
+            grab_event_mask(),
             callback,
             null_mut(),
         )
@@ -60,6 +124,7 @@ where
         current_loop.add_source(Some(&loop_), kCFRunLoopCommonModes);
 
         CGEvent::tap_enable(&tap, true);
+        GLOBAL_TAP = Some(tap);
         CFRunLoop::run();
     }
     Ok(())
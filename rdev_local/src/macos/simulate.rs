@@ -1,9 +1,10 @@
 use objc2_core_foundation::{CFRetained, CGPoint};
 use objc2_core_graphics::{
-    CGEvent, CGEventSource, CGEventSourceStateID, CGEventTapLocation,
+    CGEvent, CGEventField, CGEventSource, CGEventSourceStateID, CGEventTapLocation,
     CGEventType, CGMouseButton, CGScrollEventUnit,
 };
 
+use crate::macos::common::SIMULATED_USER_DATA;
 use crate::rdev::{Button, EventType, SimulateError};
 
 unsafe fn convert_native_with_source(
@@ -12,30 +13,34 @@ unsafe fn convert_native_with_source(
 ) -> Option<CFRetained<CGEvent>> {
       match event_type {
         EventType::ButtonPress(button) => {
-            let mouse_button = match button {
-                Button::Left => CGMouseButton::Left,
-                Button::Right => CGMouseButton::Right,
-                Button::Middle => CGMouseButton::Center,
-                Button::Unknown(_) => CGMouseButton::Left,
+            let (event_type, mouse_button) = match button {
+                Button::Back => (CGEventType::OtherMouseDown, CGMouseButton(3)),
+                Button::Forward => (CGEventType::OtherMouseDown, CGMouseButton(4)),
+                Button::Left => (CGEventType::LeftMouseDown, CGMouseButton::Left),
+                Button::Right => (CGEventType::LeftMouseDown, CGMouseButton::Right),
+                Button::Middle => (CGEventType::LeftMouseDown, CGMouseButton::Center),
+                Button::Unknown(_) => (CGEventType::LeftMouseDown, CGMouseButton::Left),
             };
             let event = CGEvent::new_mouse_event(
                 Some(&source),
-                CGEventType::LeftMouseDown,
+                event_type,
                 CGPoint::new(0.0, 0.0),
                 mouse_button,
             )?;
             Some(event)
         }
         EventType::ButtonRelease(button) => {
-            let mouse_button = match button {
-                Button::Left => CGMouseButton::Left,
-                Button::Right => CGMouseButton::Right,
-                Button::Middle => CGMouseButton::Center,
-                Button::Unknown(_) => CGMouseButton::Left,
+            let (event_type, mouse_button) = match button {
+                Button::Back => (CGEventType::OtherMouseUp, CGMouseButton(3)),
+                Button::Forward => (CGEventType::OtherMouseUp, CGMouseButton(4)),
+                Button::Left => (CGEventType::LeftMouseUp, CGMouseButton::Left),
+                Button::Right => (CGEventType::LeftMouseUp, CGMouseButton::Right),
+                Button::Middle => (CGEventType::LeftMouseUp, CGMouseButton::Center),
+                Button::Unknown(_) => (CGEventType::LeftMouseUp, CGMouseButton::Left),
             };
             let event = CGEvent::new_mouse_event(
                 Some(&source),
-                CGEventType::LeftMouseUp,
+                event_type,
                 CGPoint::new(0.0, 0.0),
                 mouse_button,
             )?;
@@ -70,6 +75,11 @@ pub fn simulate(event_type: &EventType) -> Result<(), SimulateError> {
             .ok_or(SimulateError)?;
         let event = convert_native_with_source(event_type, source)
             .ok_or(SimulateError)?;
+        CGEvent::set_integer_value_field(
+            Some(&event),
+            CGEventField::EventSourceUserData,
+            SIMULATED_USER_DATA,
+        );
         CGEvent::post(CGEventTapLocation::HIDEventTap, Some(&event));
     }
     Ok(())
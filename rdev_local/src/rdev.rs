@@ -83,14 +83,18 @@ impl std::error::Error for SimulateError {}
 
 
 /// Standard mouse buttons
-/// Some mice have more than 3 buttons. These are not defined, and different
-/// OSs will give different `Button::Unknown` values.
+/// `Back` and `Forward` are the side buttons found on most mice (XBUTTON1/2 on
+/// Windows, buttons 8/9 on X11, `BTN_SIDE`/`BTN_EXTRA` on evdev, buttons 3/4 on
+/// macOS). Any further buttons are not defined, and different OSs will give
+/// different `Button::Unknown` values.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub enum Button {
     Left,
     Right,
     Middle,
+    Back,
+    Forward,
     Unknown(u8),
 }
 
@@ -129,5 +133,66 @@ pub struct Event {
     pub time: SystemTime,
     pub name: Option<String>,
     pub event_type: EventType,
+    /// True when the event was injected by software instead of coming from an
+    /// input device: events posted by `simulate` on every platform, plus any
+    /// injected event on Windows (`LLMHF_INJECTED`) and any event not posted by
+    /// the HID system on MacOS.
+    pub synthetic: bool,
+    /// Precise scroll data for `EventType::Wheel`, where the platform reports
+    /// it (MacOS only for now). `None` for every other event.
+    pub scroll: Option<ScrollDetail>,
+    /// Pointer device data for button and move events, where the platform
+    /// reports it (MacOS and Windows). `None` for every other event.
+    pub pointer: Option<PointerDetail>,
+}
+
+/// Kind of device that produced a pointer event.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum PointerType {
+    Mouse,
+    Pen,
+    Touch,
+}
+
+/// Device data attached to button and move events.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct PointerDetail {
+    pub pointer_type: PointerType,
+    /// Normalized pressure in `[0, 1]`, `None` when the device does not report it.
+    pub pressure: Option<f64>,
+    /// Tilt of a pen in degrees, in `[-90, 90]`, `None` when not reported.
+    pub tilt_x: Option<f64>,
+    pub tilt_y: Option<f64>,
+}
+
+/// Phase of a trackpad (or Magic Mouse) scroll gesture.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum ScrollPhase {
+    /// Fingers touched down and started scrolling.
+    Began,
+    /// Fingers are still scrolling.
+    Changed,
+    /// Fingers lifted (or the gesture was cancelled), or the momentum that
+    /// followed came to rest.
+    Ended,
+    /// Inertial scrolling after the fingers lifted.
+    Momentum,
+}
+
+/// High resolution scroll data attached to a `EventType::Wheel` event.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct ScrollDetail {
+    /// Deltas in pixels, with the same sign convention as `EventType::Wheel`.
+    pub pixel_delta_x: f64,
+    pub pixel_delta_y: f64,
+    /// True for devices that scroll continuously (trackpads) rather than in
+    /// line steps (classic scroll wheels).
+    pub continuous: bool,
+    /// `None` for events that are not part of a gesture, e.g. a scroll wheel.
+    pub phase: Option<ScrollPhase>,
 }
 
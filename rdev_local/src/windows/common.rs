@@ -1,4 +1,4 @@
-use crate::rdev::{Button, EventType};
+use crate::rdev::{Button, EventType, PointerDetail, PointerType};
 use std::convert::TryInto;
 use std::os::raw::{c_int, c_short};
 use std::ptr::null_mut;
@@ -7,10 +7,10 @@ use winapi::shared::ntdef::LONG;
 use winapi::shared::windef::HHOOK;
 use winapi::um::errhandlingapi::GetLastError;
 use winapi::um::winuser::{
-    MSLLHOOKSTRUCT, SetWindowsHookExA, WH_MOUSE_LL, WHEEL_DELTA,
+    LLMHF_INJECTED, MSLLHOOKSTRUCT, SetWindowsHookExA, WH_MOUSE_LL, WHEEL_DELTA,
     WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP,
     WM_MOUSEHWHEEL, WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_RBUTTONDOWN, WM_RBUTTONUP,
-    WM_XBUTTONDOWN, WM_XBUTTONUP,
+    WM_XBUTTONDOWN, WM_XBUTTONUP, XBUTTON1, XBUTTON2,
 };
 pub const TRUE: i32 = 1;
 pub const FALSE: i32 = 0;
@@ -32,6 +32,41 @@ pub unsafe fn get_delta(lpdata: LPARAM) -> WORD {
         HIWORD(mouse.mouseData)
     }
 }
+/// Events sent with `SendInput` (by `simulate` or any other process) carry the injected flag
+pub unsafe fn is_injected(lpdata: LPARAM) -> bool {
+    unsafe {
+        let mouse = *(lpdata as *const MSLLHOOKSTRUCT);
+        mouse.flags & LLMHF_INJECTED != 0
+    }
+}
+// Mouse messages promoted from pen or touch input carry this signature in
+// `dwExtraInfo`, with 0x80 set for touch.
+// https://learn.microsoft.com/en-us/windows/win32/tablet/system-events-and-mouse-messages
+const MI_WP_SIGNATURE: usize = 0xFF51_5700;
+const SIGNATURE_MASK: usize = 0xFFFF_FF00;
+const TOUCH_FLAG: usize = 0x80;
+
+/// Pressure and tilt only reach the window under the pen through `WM_POINTER`
+/// messages and cannot be read from a global hook, so they are left unset.
+pub unsafe fn pointer_detail(event_type: &EventType, lpdata: LPARAM) -> Option<PointerDetail> {
+    if let EventType::Wheel { .. } = event_type {
+        return None;
+    }
+    let extra_info = unsafe { (*(lpdata as *const MSLLHOOKSTRUCT)).dwExtraInfo };
+    let pointer_type = if extra_info & SIGNATURE_MASK != MI_WP_SIGNATURE {
+        PointerType::Mouse
+    } else if extra_info & TOUCH_FLAG != 0 {
+        PointerType::Touch
+    } else {
+        PointerType::Pen
+    };
+    Some(PointerDetail {
+        pointer_type,
+        pressure: None,
+        tilt_x: None,
+        tilt_y: None,
+    })
+}
 pub unsafe fn get_button_code(lpdata: LPARAM) -> WORD {
     unsafe {
         let mouse = *(lpdata as *const MSLLHOOKSTRUCT);
@@ -39,6 +74,14 @@ pub unsafe fn get_button_code(lpdata: LPARAM) -> WORD {
     }
 }
 
+fn x_button(code: WORD) -> Button {
+    match code {
+        XBUTTON1 => Button::Back,
+        XBUTTON2 => Button::Forward,
+        code => Button::Unknown(code as u8),
+    }
+}
+
 pub unsafe fn convert(param: WPARAM, lpdata: LPARAM) -> Option<EventType> {
     unsafe {
         match param.try_into() {
@@ -48,14 +91,8 @@ pub unsafe fn convert(param: WPARAM, lpdata: LPARAM) -> Option<EventType> {
             Ok(WM_MBUTTONUP) => Some(EventType::ButtonRelease(Button::Middle)),
             Ok(WM_RBUTTONDOWN) => Some(EventType::ButtonPress(Button::Right)),
             Ok(WM_RBUTTONUP) => Some(EventType::ButtonRelease(Button::Right)),
-            Ok(WM_XBUTTONDOWN) => {
-                let code = get_button_code(lpdata) as u8;
-                Some(EventType::ButtonPress(Button::Unknown(code)))
-            }
-            Ok(WM_XBUTTONUP) => {
-                let code = get_button_code(lpdata) as u8;
-                Some(EventType::ButtonRelease(Button::Unknown(code)))
-            }
+            Ok(WM_XBUTTONDOWN) => Some(EventType::ButtonPress(x_button(get_button_code(lpdata)))),
+            Ok(WM_XBUTTONUP) => Some(EventType::ButtonRelease(x_button(get_button_code(lpdata)))),
             Ok(WM_MOUSEMOVE) => {
                 let (x, y) = get_point(lpdata);
                 Some(EventType::MouseMove {
@@ -1,5 +1,7 @@
 use crate::rdev::{Event, GrabError};
-use crate::windows::common::{HOOK, HookError, convert, set_mouse_hook};
+use crate::windows::common::{
+    HOOK, HookError, convert, is_injected, pointer_detail, set_mouse_hook,
+};
 use std::ptr::null_mut;
 use std::time::SystemTime;
 use winapi::um::winuser::{CallNextHookEx, GetMessageA, HC_ACTION};
@@ -16,6 +18,9 @@ unsafe extern "system" fn raw_callback(code: i32, param: usize, lpdata: isize) -
                     event_type,
                     time: SystemTime::now(),
                     name,
+                    synthetic: is_injected(lpdata),
+                    scroll: None,
+                    pointer: pointer_detail(&event_type, lpdata),
                 };
                 let ptr = &raw mut GLOBAL_CALLBACK;
                 if let Some(callback) = &mut *ptr {
@@ -48,7 +53,9 @@ where
         GLOBAL_CALLBACK = Some(Box::new(callback));
         set_mouse_hook(raw_callback)?;
 
-        GetMessageA(null_mut(), null_mut(), 0, 0);
+        // The low level hook only runs while this thread pumps messages
+        let mut msg = std::mem::zeroed();
+        while GetMessageA(&mut msg, null_mut(), 0, 0) != 0 {}
     }
     Ok(())
 }
@@ -2,7 +2,9 @@
 // This file previously contained Windows keyboard event listening logic
 
 use crate::rdev::{Event, ListenError};
-use crate::windows::common::{HOOK, HookError, convert, set_mouse_hook};
+use crate::windows::common::{
+    HOOK, HookError, convert, is_injected, pointer_detail, set_mouse_hook,
+};
 use std::os::raw::c_int;
 use std::ptr::null_mut;
 use std::time::SystemTime;
@@ -28,6 +30,9 @@ unsafe extern "system" fn raw_callback(code: c_int, param: WPARAM, lpdata: LPARA
                     event_type,
                     time: SystemTime::now(),
                     name: None,
+                    synthetic: is_injected(lpdata),
+                    scroll: None,
+                    pointer: pointer_detail(&event_type, lpdata),
                 };
                 let ptr = &raw mut GLOBAL_CALLBACK;
                 if let Some(callback) = &mut *ptr {
@@ -10,6 +10,7 @@ use winapi::um::winuser::{
     MOUSEEVENTF_MIDDLEDOWN, MOUSEEVENTF_MIDDLEUP, MOUSEEVENTF_MOVE, MOUSEEVENTF_RIGHTDOWN,
     MOUSEEVENTF_RIGHTUP, MOUSEEVENTF_VIRTUALDESK, MOUSEEVENTF_WHEEL, MOUSEEVENTF_XDOWN,
     MOUSEEVENTF_XUP, MOUSEINPUT, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SendInput, WHEEL_DELTA,
+    XBUTTON1, XBUTTON2,
 };
 /// Not defined in win32 but define here for clarity
 static KEYEVENTF_KEYDOWN: DWORD = 0;
@@ -77,12 +78,16 @@ pub fn simulate(event_type: &EventType) -> Result<(), SimulateError> {
             Button::Left => sim_mouse_event(MOUSEEVENTF_LEFTDOWN, 0, 0, 0),
             Button::Middle => sim_mouse_event(MOUSEEVENTF_MIDDLEDOWN, 0, 0, 0),
             Button::Right => sim_mouse_event(MOUSEEVENTF_RIGHTDOWN, 0, 0, 0),
+            Button::Back => sim_mouse_event(MOUSEEVENTF_XDOWN, XBUTTON1.into(), 0, 0),
+            Button::Forward => sim_mouse_event(MOUSEEVENTF_XDOWN, XBUTTON2.into(), 0, 0),
             Button::Unknown(code) => sim_mouse_event(MOUSEEVENTF_XDOWN, (*code).into(), 0, 0),
         },
         EventType::ButtonRelease(button) => match button {
             Button::Left => sim_mouse_event(MOUSEEVENTF_LEFTUP, 0, 0, 0),
             Button::Middle => sim_mouse_event(MOUSEEVENTF_MIDDLEUP, 0, 0, 0),
             Button::Right => sim_mouse_event(MOUSEEVENTF_RIGHTUP, 0, 0, 0),
+            Button::Back => sim_mouse_event(MOUSEEVENTF_XUP, XBUTTON1.into(), 0, 0),
+            Button::Forward => sim_mouse_event(MOUSEEVENTF_XUP, XBUTTON2.into(), 0, 0),
             Button::Unknown(code) => sim_mouse_event(MOUSEEVENTF_XUP, (*code).into(), 0, 0),
         },
         EventType::Wheel { delta_x, delta_y } => {
@@ -0,0 +1,152 @@
+#!/usr/bin/env node
+
+// Post-process the index.d.ts generated by `napi build`.
+// The Rust attributes already give string-literal unions for event types and
+// typed callbacks; this step adds what napi-rs cannot express:
+// - event payloads delivered to callbacks are readonly
+// - InputEvent becomes a discriminated union on `kind`
+// - string enums (event types, drag end reasons) become string-literal unions,
+//   so plain strings like 'dragstart' or 'monitor-stopped' type-check and typos do not
+// - button fields document the numbering, which is stable across platforms
+// - DragMonitor.on gets one overload per event name with the matching payload type
+
+const fs = require('fs');
+const path = require('path');
+
+const file = path.join(__dirname, '..', 'index.d.ts');
+
+// Payloads created by the addon and handed to callbacks
+const READONLY_INTERFACES = [
+    'MouseEvent',
+    'DragEvent',
+    'WheelEvent',
+    'ExternalDragEvent',
+    'RegionEvent',
+    'ScreenEdgeEvent',
+    'GestureEvent',
+    'ClipboardEvent',
+    'DropTargetEvent',
+    'SystemTheme',
+    'FilePromiseRequest',
+    'MonitorError',
+    'LogRecord',
+];
+
+// String enums emitted by napi-rs as `const enum`
+const STRING_ENUMS = ['MouseEventType', 'DragEventType', 'DragEndReason'];
+
+// Interfaces with a `button` field
+const BUTTON_INTERFACES = ['MouseEvent', 'DragEvent'];
+
+const BUTTON_DOC = `  /**
+   * Mouse button. With the default 'native' numbering: 1=left, 2=middle, 3=right,
+   * 4=back, 5=forward (Windows XBUTTON1/2, Linux buttons 8/9, macOS buttons 3/4),
+   * higher values are other buttons as reported by the OS. With 'dom' numbering
+   * (setButtonNumbering) every value is one lower, matching MouseEvent.button in the browser.
+   */`;
+
+const INPUT_EVENT = `export type InputEvent =
+  | { readonly kind: 'mouse'; readonly mouse: MouseEvent; readonly drag?: undefined; readonly wheel?: undefined }
+  | { readonly kind: 'drag'; readonly mouse?: undefined; readonly drag: DragEvent; readonly wheel?: undefined }
+  | { readonly kind: 'wheel'; readonly mouse?: undefined; readonly drag?: undefined; readonly wheel: WheelEvent }`;
+
+// Event names accepted by DragMonitor.on and the payload each one delivers
+const DRAG_MONITOR_EVENTS = [
+    ['mouse', 'MouseEvent'],
+    ['drag', 'DragEvent'],
+    ['wheel', 'WheelEvent'],
+    ['input', 'InputEvent'],
+    ['region', 'RegionEvent'],
+    ['screen-edge', 'ScreenEdgeEvent'],
+    ['error', 'MonitorError'],
+];
+
+function interfacePattern(name) {
+    return new RegExp(`export interface ${name} \\{\\n([\\s\\S]*?)\\n\\}`);
+}
+
+function makeReadonly(source, name) {
+    const pattern = interfacePattern(name);
+    if (!pattern.test(source)) {
+        throw new Error(`interface ${name} not found in index.d.ts`);
+    }
+    return source.replace(pattern, (_, body) => {
+        const fields = body
+            .split('\n')
+            .map((line) => line.replace(/^(\s+)(?!readonly |\/\/|\*|\/\*)(\w+\??:)/, '$1readonly $2'))
+            .join('\n');
+        return `export interface ${name} {\n${fields}\n}`;
+    });
+}
+
+function documentButton(source, name) {
+    const pattern = interfacePattern(name);
+    return source.replace(pattern, (interfaceSource) =>
+        interfaceSource.includes(BUTTON_DOC)
+            ? interfaceSource
+            : interfaceSource.replace(/\n(\s+readonly button: number)/, `\n${BUTTON_DOC}\n$1`),
+    );
+}
+
+function makeUnion(source, name) {
+    const pattern = new RegExp(`export const enum ${name} \\{\\n([\\s\\S]*?)\\n\\}`);
+    const match = source.match(pattern);
+    if (!match) {
+        if (source.includes(`export type ${name} =`)) {
+            return source;
+        }
+        throw new Error(`enum ${name} not found in index.d.ts`);
+    }
+    const values = [...match[1].matchAll(/=\s*('[^']*')/g)].map((value) => value[1]);
+    return source.replace(pattern, `export type ${name} = ${values.join(' | ')}`);
+}
+
+function overloadDragMonitorOn(source) {
+    const pattern = /(export class DragMonitor \{[\s\S]*?\n)(\s+)on\(event: 'mouse' \| [^\n]*\n/;
+    const match = source.match(pattern);
+    if (!match) {
+        if (source.includes("on(event: 'mouse', callback")) {
+            return source;
+        }
+        throw new Error('DragMonitor.on not found in index.d.ts');
+    }
+    const indent = match[2];
+    const overloads = DRAG_MONITOR_EVENTS.map(([event, payload]) => {
+        const options = event === 'drag' ? ', options?: DragListenerOptions' : '';
+        return `${indent}on(event: '${event}', callback: (err: null | Error, event: ${payload}) => void${options}): number\n`;
+    });
+    return source.replace(pattern, `$1${overloads.join('')}`);
+}
+
+function main() {
+    if (!fs.existsSync(file)) {
+        console.error('index.d.ts not found, run `napi build` first');
+        process.exit(1);
+    }
+
+    let source = fs.readFileSync(file, 'utf8');
+    for (const name of READONLY_INTERFACES) {
+        source = makeReadonly(source, name);
+    }
+
+    for (const name of BUTTON_INTERFACES) {
+        source = documentButton(source, name);
+    }
+
+    for (const name of STRING_ENUMS) {
+        source = makeUnion(source, name);
+    }
+
+    source = overloadDragMonitorOn(source);
+
+    const inputEvent = interfacePattern('InputEvent');
+    if (inputEvent.test(source)) {
+        source = source.replace(inputEvent, INPUT_EVENT);
+    } else if (!source.includes('export type InputEvent =')) {
+        throw new Error('interface InputEvent not found in index.d.ts');
+    }
+
+    fs.writeFileSync(file, source);
+}
+
+main();
@@ -0,0 +1,543 @@
+// 输入事件的二进制编码，用于在进程之间（MessagePort 等）转发事件时避免 JSON 序列化
+//
+// 布局（小端序），字段顺序即结构体字段顺序：
+//   u8  版本号（当前为 9）
+//   u8  类型：1 = mouse，2 = drag，3 = wheel
+//   之后为对应事件的字段：
+//   字符串 = u16 字节长度 + UTF-8，f64/i32/u32 为定长
+//   drag.annotations = u16 条数 + 每条 (字符串, 字符串)
+//   drag.reason = u8：0 = 无，1 = release，2 = programmatic，3 = timeout，4 = monitor-stopped（版本 5 起），
+//     5 = focus-change（版本 8 起）
+//   drag.path（版本 2 起）= u32 点数 + 每个点 (f64, f64)，u32::MAX 表示无轨迹
+//   isSynthetic（版本 3 起）= u8，追加在每种事件的末尾
+//   monotonicTimeNs（版本 4 起）= f64，追加在 mouse 和 drag 的末尾
+//   wheel 的 pixelDeltaX/pixelDeltaY（版本 6 起）= u8 是否有值 + (f64, f64)，
+//   之后为 phase = u8：0 = 无，1 = began，2 = changed，3 = ended，4 = momentum
+//   mouse 的 pointerType（版本 7 起）= u8：0 = mouse，1 = pen，2 = touch，之后为 pressure、tiltX、tiltY（f64）
+//   displayId（版本 9 起）= u32，追加在 mouse 和 drag 的末尾
+// 新增字段只能追加到末尾并提升版本号，旧版本的数据必须仍能解码
+
+use crate::{DragEndReason, DragEvent, DragEventType, InputEvent, MouseEvent, MouseEventType, Point, WheelEvent};
+use std::collections::HashMap;
+
+const VERSION: u8 = 9;
+
+const NO_PATH: u32 = u32::MAX;
+
+const KIND_MOUSE: u8 = 1;
+const KIND_DRAG: u8 = 2;
+const KIND_WHEEL: u8 = 3;
+
+const PHASES: [&str; 4] = ["began", "changed", "ended", "momentum"];
+
+const POINTER_TYPES: [&str; 3] = ["mouse", "pen", "touch"];
+
+struct Writer {
+    buffer: Vec<u8>,
+}
+
+impl Writer {
+    fn u8(&mut self, value: u8) {
+        self.buffer.push(value);
+    }
+
+    fn u16(&mut self, value: u16) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn i32(&mut self, value: i32) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn f64(&mut self, value: f64) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn str(&mut self, value: &str) -> Result<(), String> {
+        let length = u16::try_from(value.len()).map_err(|_| format!("String field too long: {} bytes", value.len()))?;
+        self.u16(length);
+        self.buffer.extend_from_slice(value.as_bytes());
+        Ok(())
+    }
+}
+
+struct Reader<'a> {
+    buffer: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], String> {
+        if self.buffer.len() < length {
+            return Err("Unexpected end of event buffer".to_string());
+        }
+        let (head, tail) = self.buffer.split_at(length);
+        self.buffer = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn i32(&mut self) -> Result<i32, String> {
+        Ok(i32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn f64(&mut self) -> Result<f64, String> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn str(&mut self) -> Result<String, String> {
+        let length = self.u16()? as usize;
+        String::from_utf8(self.take(length)?.to_vec()).map_err(|_| "Invalid UTF-8 in event buffer".to_string())
+    }
+
+    // isSynthetic 从版本 3 开始编码，更早的数据视为来自输入设备
+    fn synthetic(&mut self, version: u8) -> Result<bool, String> {
+        Ok(version >= 3 && self.u8()? != 0)
+    }
+
+    // monotonicTimeNs 从版本 4 开始编码，更早的数据为 0
+    fn monotonic_time_ns(&mut self, version: u8) -> Result<f64, String> {
+        if version >= 4 { self.f64() } else { Ok(0.0) }
+    }
+
+    // 滚轮的像素位移从版本 6 开始编码，更早的数据没有
+    fn pixel_delta(&mut self, version: u8) -> Result<Option<(f64, f64)>, String> {
+        if version < 6 || self.u8()? == 0 {
+            return Ok(None);
+        }
+        Ok(Some((self.f64()?, self.f64()?)))
+    }
+
+    // displayId 从版本 9 开始编码，更早的数据为 0
+    fn display_id(&mut self, version: u8) -> Result<u32, String> {
+        if version >= 9 { self.u32() } else { Ok(0) }
+    }
+
+    // pointerType、pressure、tiltX、tiltY 从版本 7 开始编码，更早的数据视为鼠标
+    fn pointer(&mut self, version: u8) -> Result<(String, f64, f64, f64), String> {
+        if version < 7 {
+            return Ok((POINTER_TYPES[0].to_string(), 0.0, 0.0, 0.0));
+        }
+        let index = self.u8()?;
+        let pointer_type = POINTER_TYPES
+            .get(index as usize)
+            .ok_or_else(|| format!("Unknown pointer type: {}", index))?;
+        Ok((pointer_type.to_string(), self.f64()?, self.f64()?, self.f64()?))
+    }
+
+    fn phase(&mut self, version: u8) -> Result<Option<String>, String> {
+        if version < 6 {
+            return Ok(None);
+        }
+        match self.u8()? {
+            0 => Ok(None),
+            index => PHASES
+                .get(index as usize - 1)
+                .map(|phase| Some(phase.to_string()))
+                .ok_or_else(|| format!("Unknown scroll phase: {}", index)),
+        }
+    }
+}
+
+pub fn encode(event: &InputEvent) -> Result<Vec<u8>, String> {
+    let mut writer = Writer { buffer: Vec::with_capacity(64) };
+    writer.u8(VERSION);
+    match (event.kind.as_str(), &event.mouse, &event.drag, &event.wheel) {
+        ("mouse", Some(mouse), _, _) => {
+            writer.u8(KIND_MOUSE);
+            writer.str(mouse.event_type.as_str())?;
+            writer.f64(mouse.x);
+            writer.f64(mouse.y);
+            writer.i32(mouse.button);
+            writer.f64(mouse.timestamp);
+            writer.str(&mouse.platform)?;
+            writer.u8(mouse.is_synthetic as u8);
+            writer.f64(mouse.monotonic_time_ns);
+            let pointer_type = POINTER_TYPES
+                .iter()
+                .position(|known| *known == mouse.pointer_type)
+                .ok_or_else(|| format!("Unknown pointer type: {}", mouse.pointer_type))?;
+            writer.u8(pointer_type as u8);
+            writer.f64(mouse.pressure);
+            writer.f64(mouse.tilt_x);
+            writer.f64(mouse.tilt_y);
+            writer.u32(mouse.display_id);
+        }
+        ("drag", _, Some(drag), _) => {
+            writer.u8(KIND_DRAG);
+            writer.str(drag.event_type.as_str())?;
+            writer.f64(drag.x);
+            writer.f64(drag.y);
+            writer.f64(drag.start_x);
+            writer.f64(drag.start_y);
+            writer.i32(drag.button);
+            writer.f64(drag.timestamp);
+            writer.str(&drag.platform)?;
+            writer.str(&drag.press_region)?;
+            writer.u32(drag.session_id);
+            let count = u16::try_from(drag.annotations.len()).map_err(|_| "Too many annotations".to_string())?;
+            writer.u16(count);
+            for (key, value) in &drag.annotations {
+                writer.str(key)?;
+                writer.str(value)?;
+            }
+            writer.u8(match drag.reason {
+                None => 0,
+                Some(DragEndReason::Release) => 1,
+                Some(DragEndReason::Programmatic) => 2,
+                Some(DragEndReason::Timeout) => 3,
+                Some(DragEndReason::MonitorStopped) => 4,
+                Some(DragEndReason::FocusChange) => 5,
+            });
+            match &drag.path {
+                None => writer.u32(NO_PATH),
+                Some(path) => {
+                    let count = u32::try_from(path.len())
+                        .ok()
+                        .filter(|count| *count != NO_PATH)
+                        .ok_or_else(|| "Drag path too long".to_string())?;
+                    writer.u32(count);
+                    for point in path {
+                        writer.f64(point.x);
+                        writer.f64(point.y);
+                    }
+                }
+            }
+            writer.u8(drag.is_synthetic as u8);
+            writer.f64(drag.monotonic_time_ns);
+            writer.u32(drag.display_id);
+        }
+        ("wheel", _, _, Some(wheel)) => {
+            writer.u8(KIND_WHEEL);
+            writer.str(&wheel.event_type)?;
+            writer.f64(wheel.x);
+            writer.f64(wheel.y);
+            writer.f64(wheel.delta_x);
+            writer.f64(wheel.delta_y);
+            writer.f64(wheel.timestamp);
+            writer.str(&wheel.platform)?;
+            writer.u8(wheel.is_synthetic as u8);
+            match (wheel.pixel_delta_x, wheel.pixel_delta_y) {
+                (Some(pixel_x), Some(pixel_y)) => {
+                    writer.u8(1);
+                    writer.f64(pixel_x);
+                    writer.f64(pixel_y);
+                }
+                _ => writer.u8(0),
+            }
+            let phase = match &wheel.phase {
+                None => 0,
+                Some(phase) => PHASES
+                    .iter()
+                    .position(|known| known == phase)
+                    .ok_or_else(|| format!("Unknown scroll phase: {}", phase))? as u8
+                    + 1,
+            };
+            writer.u8(phase);
+        }
+        (kind, _, _, _) => return Err(format!("Event of kind \"{}\" has no matching payload", kind)),
+    }
+    Ok(writer.buffer)
+}
+
+pub fn decode(buffer: &[u8]) -> Result<InputEvent, String> {
+    let mut reader = Reader { buffer };
+    let version = reader.u8()?;
+    if version == 0 || version > VERSION {
+        return Err(format!("Unsupported event buffer version: {}", version));
+    }
+
+    let event = match reader.u8()? {
+        KIND_MOUSE => {
+            let event_type = reader.str()?;
+            let event_type = MouseEventType::parse(&event_type).ok_or_else(|| format!("Unknown mouse event type: {}", event_type))?;
+            let x = reader.f64()?;
+            let y = reader.f64()?;
+            let button = reader.i32()?;
+            let timestamp = reader.f64()?;
+            let platform = reader.str()?;
+            let is_synthetic = reader.synthetic(version)?;
+            let monotonic_time_ns = reader.monotonic_time_ns(version)?;
+            let (pointer_type, pressure, tilt_x, tilt_y) = reader.pointer(version)?;
+            let display_id = reader.display_id(version)?;
+            InputEvent {
+                kind: "mouse".to_string(),
+                mouse: Some(MouseEvent {
+                    event_type,
+                    x,
+                    y,
+                    button,
+                    timestamp,
+                    monotonic_time_ns,
+                    platform,
+                    is_synthetic,
+                    pointer_type,
+                    pressure,
+                    tilt_x,
+                    tilt_y,
+                    display_id,
+                }),
+                drag: None,
+                wheel: None,
+            }
+        }
+        KIND_DRAG => {
+            let event_type = reader.str()?;
+            let event_type = DragEventType::parse(&event_type).ok_or_else(|| format!("Unknown drag event type: {}", event_type))?;
+            let x = reader.f64()?;
+            let y = reader.f64()?;
+            let start_x = reader.f64()?;
+            let start_y = reader.f64()?;
+            let button = reader.i32()?;
+            let timestamp = reader.f64()?;
+            let platform = reader.str()?;
+            let press_region = reader.str()?;
+            let session_id = reader.u32()?;
+            let mut annotations = HashMap::new();
+            for _ in 0..reader.u16()? {
+                let key = reader.str()?;
+                annotations.insert(key, reader.str()?);
+            }
+            let reason = match reader.u8()? {
+                0 => None,
+                1 => Some(DragEndReason::Release),
+                2 => Some(DragEndReason::Programmatic),
+                3 => Some(DragEndReason::Timeout),
+                4 if version >= 5 => Some(DragEndReason::MonitorStopped),
+                5 if version >= 8 => Some(DragEndReason::FocusChange),
+                other => return Err(format!("Unknown drag end reason: {}", other)),
+            };
+            let path_count = if version >= 2 { reader.u32()? } else { NO_PATH };
+            let path = match path_count {
+                NO_PATH => None,
+                count => {
+                    let mut path = Vec::new();
+                    for _ in 0..count {
+                        path.push(Point {
+                            x: reader.f64()?,
+                            y: reader.f64()?,
+                        });
+                    }
+                    Some(path)
+                }
+            };
+            let is_synthetic = reader.synthetic(version)?;
+            let monotonic_time_ns = reader.monotonic_time_ns(version)?;
+            let display_id = reader.display_id(version)?;
+            InputEvent {
+                kind: "drag".to_string(),
+                mouse: None,
+                drag: Some(DragEvent {
+                    event_type,
+                    x,
+                    y,
+                    start_x,
+                    start_y,
+                    button,
+                    timestamp,
+                    monotonic_time_ns,
+                    platform,
+                    press_region,
+                    session_id,
+                    annotations,
+                    reason,
+                    path,
+                    is_synthetic,
+                    display_id,
+                }),
+                wheel: None,
+            }
+        }
+        KIND_WHEEL => {
+            let event_type = reader.str()?;
+            let x = reader.f64()?;
+            let y = reader.f64()?;
+            let delta_x = reader.f64()?;
+            let delta_y = reader.f64()?;
+            let timestamp = reader.f64()?;
+            let platform = reader.str()?;
+            let is_synthetic = reader.synthetic(version)?;
+            let pixel_delta = reader.pixel_delta(version)?;
+            let phase = reader.phase(version)?;
+            InputEvent {
+                kind: "wheel".to_string(),
+                mouse: None,
+                drag: None,
+                wheel: Some(WheelEvent {
+                    event_type,
+                    x,
+                    y,
+                    delta_x,
+                    delta_y,
+                    timestamp,
+                    platform,
+                    is_synthetic,
+                    pixel_delta_x: pixel_delta.map(|(x, _)| x),
+                    pixel_delta_y: pixel_delta.map(|(_, y)| y),
+                    phase,
+                }),
+            }
+        }
+        other => return Err(format!("Unknown event kind: {}", other)),
+    };
+    Ok(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mouse_event() -> InputEvent {
+        InputEvent {
+            kind: "mouse".to_string(),
+            mouse: Some(MouseEvent {
+                event_type: MouseEventType::Mousedown,
+                x: 10.5,
+                y: -20.0,
+                button: 2,
+                timestamp: 1_700_000_000_000.0,
+                monotonic_time_ns: 123_456.0,
+                platform: "linux".to_string(),
+                is_synthetic: true,
+                pointer_type: "pen".to_string(),
+                pressure: 0.5,
+                tilt_x: 10.0,
+                tilt_y: -5.0,
+                display_id: 3,
+            }),
+            drag: None,
+            wheel: None,
+        }
+    }
+
+    fn drag_event(path: Option<Vec<Point>>) -> InputEvent {
+        let mut annotations = HashMap::new();
+        annotations.insert("source".to_string(), "文件列表".to_string());
+        InputEvent {
+            kind: "drag".to_string(),
+            mouse: None,
+            drag: Some(DragEvent {
+                event_type: DragEventType::Dragend,
+                x: 100.0,
+                y: 200.0,
+                start_x: 90.0,
+                start_y: 195.0,
+                button: 1,
+                timestamp: 1_700_000_000_500.0,
+                monotonic_time_ns: 654_321.0,
+                platform: "linux".to_string(),
+                press_region: "content".to_string(),
+                session_id: 42,
+                annotations,
+                reason: Some(DragEndReason::Timeout),
+                path,
+                is_synthetic: false,
+                display_id: 1,
+            }),
+            wheel: None,
+        }
+    }
+
+    fn wheel_event() -> InputEvent {
+        InputEvent {
+            kind: "wheel".to_string(),
+            mouse: None,
+            drag: None,
+            wheel: Some(WheelEvent {
+                event_type: "wheel".to_string(),
+                x: 1.0,
+                y: 2.0,
+                delta_x: 0.0,
+                delta_y: -3.0,
+                timestamp: 1_700_000_001_000.0,
+                platform: "linux".to_string(),
+                is_synthetic: false,
+                pixel_delta_x: Some(0.0),
+                pixel_delta_y: Some(-36.0),
+                phase: Some("momentum".to_string()),
+            }),
+        }
+    }
+
+    fn assert_round_trip(event: InputEvent) {
+        let decoded = decode(&encode(&event).unwrap()).unwrap();
+        assert_eq!(serde_json::to_value(&decoded).unwrap(), serde_json::to_value(&event).unwrap());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_round_trip(mouse_event());
+        assert_round_trip(drag_event(None));
+        assert_round_trip(drag_event(Some(Vec::new())));
+        assert_round_trip(drag_event(Some(vec![Point { x: 90.0, y: 195.0 }, Point { x: 100.0, y: 200.0 }])));
+        assert_round_trip(wheel_event());
+
+        for reason in [DragEndReason::MonitorStopped, DragEndReason::FocusChange] {
+            let mut cancelled = drag_event(None);
+            cancelled.drag.as_mut().unwrap().reason = Some(reason);
+            assert_round_trip(cancelled);
+        }
+    }
+
+    #[test]
+    fn test_decode_version_1_wheel() {
+        let mut writer = Writer { buffer: Vec::new() };
+        writer.u8(1);
+        writer.u8(KIND_WHEEL);
+        writer.str("wheel").unwrap();
+        for value in [1.0, 2.0, 0.0, -3.0, 1_700_000_001_000.0] {
+            writer.f64(value);
+        }
+        writer.str("win32").unwrap();
+
+        let wheel = decode(&writer.buffer).unwrap().wheel.unwrap();
+        assert_eq!(wheel.delta_y, -3.0);
+        assert_eq!(wheel.platform, "win32");
+        assert!(!wheel.is_synthetic);
+        assert_eq!(wheel.pixel_delta_x, None);
+        assert_eq!(wheel.phase, None);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let buffer = encode(&mouse_event()).unwrap();
+        for length in 0..buffer.len() {
+            assert!(decode(&buffer[..length]).is_err());
+        }
+
+        let mut future = buffer.clone();
+        future[0] = VERSION + 1;
+        assert!(decode(&future).is_err());
+        future[0] = 0;
+        assert!(decode(&future).is_err());
+
+        let mut unknown_kind = buffer;
+        unknown_kind[1] = 9;
+        assert!(decode(&unknown_kind).is_err());
+    }
+
+    #[test]
+    fn test_encode_rejects_mismatched_payload() {
+        let mut event = mouse_event();
+        event.kind = "drag".to_string();
+        assert!(encode(&event).is_err());
+
+        let mut event = wheel_event();
+        event.wheel.as_mut().unwrap().phase = Some("unknown".to_string());
+        assert!(encode(&event).is_err());
+    }
+}
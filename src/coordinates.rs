@@ -0,0 +1,167 @@
+// 对外报告的坐标空间：逻辑坐标（点）或物理坐标（像素）
+// 系统钩子在 macOS 上报告逻辑坐标，在 Windows/Linux 上报告物理坐标；选择非系统坐标空间时，
+// 每个显示器以自身左上角为不动点按缩放比例换算，与 Electron 的 screen.dipToScreenPoint 一致
+
+use crate::platform::{DisplayInfo, Rect};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoordinateSpace {
+    Logical,
+    Physical,
+}
+
+impl CoordinateSpace {
+    // 系统钩子和平台查询使用的坐标空间
+    pub const NATIVE: CoordinateSpace = if cfg!(target_os = "macos") {
+        CoordinateSpace::Logical
+    } else {
+        CoordinateSpace::Physical
+    };
+}
+
+fn contains(rect: &Rect, x: f64, y: f64) -> bool {
+    x >= rect.x && x < rect.x + rect.width && y >= rect.y && y < rect.y + rect.height
+}
+
+// 当前坐标空间，以及设置时的显示器布局快照（系统坐标空间下不需要快照）
+pub struct CoordinateMapping {
+    pub space: CoordinateSpace,
+    displays: Vec<DisplayInfo>,
+}
+
+impl CoordinateMapping {
+    pub fn native() -> Self {
+        Self {
+            space: CoordinateSpace::NATIVE,
+            displays: Vec::new(),
+        }
+    }
+
+    pub fn new(space: CoordinateSpace, displays: Vec<DisplayInfo>) -> Self {
+        if space == CoordinateSpace::NATIVE {
+            return Self::native();
+        }
+        Self { space, displays }
+    }
+
+    // 系统坐标换算到对外坐标时乘的系数
+    fn factor(&self, display: &DisplayInfo) -> f64 {
+        match self.space {
+            CoordinateSpace::Physical => display.scale_factor,
+            CoordinateSpace::Logical => 1.0 / display.scale_factor,
+        }
+    }
+
+    // 显示器在对外坐标空间中的范围
+    fn bounds_in_space(&self, display: &DisplayInfo) -> Rect {
+        let factor = self.factor(display);
+        Rect {
+            width: display.bounds.width * factor,
+            height: display.bounds.height * factor,
+            ..display.bounds
+        }
+    }
+
+    // 系统全局坐标 -> 对外全局坐标，不在任何显示器上的点保持不变
+    pub fn to_space(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let Some(display) = self.displays.iter().find(|d| contains(&d.bounds, x, y)) else {
+            return (x, y);
+        };
+        let (origin, factor) = (display.bounds, self.factor(display));
+        (origin.x + (x - origin.x) * factor, origin.y + (y - origin.y) * factor)
+    }
+
+    // 对外全局坐标 -> 系统全局坐标，用于命中测试、截屏等平台查询
+    pub fn to_native(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let Some(display) = self.displays.iter().find(|d| contains(&self.bounds_in_space(d), x, y)) else {
+            return (x, y);
+        };
+        let (origin, factor) = (display.bounds, self.factor(display));
+        (origin.x + (x - origin.x) / factor, origin.y + (y - origin.y) / factor)
+    }
+
+    // 显示器内的矩形（bounds、可用区域）换算到对外坐标空间
+    pub fn rect_to_space(&self, rect: Rect) -> Rect {
+        let Some(display) = self.displays.iter().find(|d| contains(&d.bounds, rect.x, rect.y)) else {
+            return rect;
+        };
+        let factor = self.factor(display);
+        let (x, y) = self.to_space((rect.x, rect.y));
+        Rect {
+            x,
+            y,
+            width: rect.width * factor,
+            height: rect.height * factor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 与系统坐标空间相反的空间，只有它需要换算
+    const OTHER: CoordinateSpace = match CoordinateSpace::NATIVE {
+        CoordinateSpace::Logical => CoordinateSpace::Physical,
+        CoordinateSpace::Physical => CoordinateSpace::Logical,
+    };
+
+    fn display(id: u32, x: f64, width: f64, height: f64, scale_factor: f64) -> DisplayInfo {
+        let bounds = Rect { x, y: 0.0, width, height };
+        DisplayInfo {
+            id,
+            bounds,
+            work_area: Rect { height: height - 40.0, ..bounds },
+            scale_factor,
+            primary: id == 1,
+        }
+    }
+
+    fn mapping() -> CoordinateMapping {
+        CoordinateMapping::new(OTHER, vec![display(1, 0.0, 1920.0, 1080.0, 2.0), display(2, 1920.0, 1280.0, 1024.0, 1.0)])
+    }
+
+    #[test]
+    fn test_native_space_is_identity() {
+        let mapping = CoordinateMapping::new(CoordinateSpace::NATIVE, vec![display(1, 0.0, 1920.0, 1080.0, 2.0)]);
+        assert_eq!(mapping.to_space((100.0, 50.0)), (100.0, 50.0));
+        assert_eq!(mapping.to_native((100.0, 50.0)), (100.0, 50.0));
+    }
+
+    #[test]
+    fn test_scales_around_display_origin() {
+        let mapping = mapping();
+        let expected = match OTHER {
+            CoordinateSpace::Logical => (50.0, 25.0),
+            CoordinateSpace::Physical => (200.0, 100.0),
+        };
+        assert_eq!(mapping.to_space((100.0, 50.0)), expected);
+        // 缩放比例为 1 的显示器不变
+        assert_eq!(mapping.to_space((2000.0, 10.0)), (2000.0, 10.0));
+        // 不在任何显示器上的点不变
+        assert_eq!(mapping.to_space((-50.0, -50.0)), (-50.0, -50.0));
+        assert_eq!(mapping.to_native((-50.0, -50.0)), (-50.0, -50.0));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mapping = mapping();
+        for point in [(0.0, 0.0), (100.0, 50.0), (959.5, 539.5), (1919.0, 1079.0), (1920.0, 0.0), (3199.0, 1023.0)] {
+            assert_eq!(mapping.to_native(mapping.to_space(point)), point);
+        }
+    }
+
+    #[test]
+    fn test_rect_to_space() {
+        let mapping = mapping();
+        let work_area = mapping.rect_to_space(Rect { x: 0.0, y: 0.0, width: 1920.0, height: 1040.0 });
+        let factor = match OTHER {
+            CoordinateSpace::Logical => 0.5,
+            CoordinateSpace::Physical => 2.0,
+        };
+        assert_eq!(work_area, Rect { x: 0.0, y: 0.0, width: 1920.0 * factor, height: 1040.0 * factor });
+
+        let outside = Rect { x: -100.0, y: 0.0, width: 10.0, height: 10.0 };
+        assert_eq!(mapping.rect_to_space(outside), outside);
+    }
+}
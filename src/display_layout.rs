@@ -0,0 +1,49 @@
+// 显示器布局缓存：每个事件都要找到坐标所在的显示器，不能每次都向系统查询。
+// 显示器配置变化的系统通知需要窗口或主线程运行循环，纯 Node 进程里收不到，因此按以下时机刷新：
+// 点不在任何缓存的显示器上（接入或移动了显示器）时立即刷新，此外每隔 REFRESH_INTERVAL 刷新一次，
+// 以发现分辨率和排列的变化。缓存是进程级的，所有窗口和 worker 共享
+
+use crate::platform::{self, Rect};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+// 点不在任何显示器上时（例如光标位于两个显示器之间的空隙）的最短刷新间隔，避免每个事件都查询系统
+const MISS_REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+
+struct Layout {
+    displays: Vec<(u32, Rect)>,
+    refreshed: Instant,
+}
+
+impl Layout {
+    fn load() -> Self {
+        Self {
+            displays: platform::display_bounds(),
+            refreshed: Instant::now(),
+        }
+    }
+
+    fn find(&self, x: f64, y: f64) -> Option<u32> {
+        self.displays
+            .iter()
+            .find(|(_, rect)| x >= rect.x && x < rect.x + rect.width && y >= rect.y && y < rect.y + rect.height)
+            .map(|(id, _)| *id)
+    }
+}
+
+static LAYOUT: Mutex<Option<Layout>> = Mutex::new(None);
+
+// 系统全局坐标所在的显示器 ID，不在任何显示器上时为 0
+pub fn display_id_at(x: f64, y: f64) -> u32 {
+    let mut layout = LAYOUT.lock().unwrap_or_else(PoisonError::into_inner);
+    let age = layout.as_ref().map(|layout| layout.refreshed.elapsed());
+    if age.is_some_and(|age| age < REFRESH_INTERVAL) {
+        let found = layout.as_ref().and_then(|layout| layout.find(x, y));
+        if found.is_some() || age.is_some_and(|age| age < MISS_REFRESH_INTERVAL) {
+            return found.unwrap_or(0);
+        }
+    }
+    layout.insert(Layout::load()).find(x, y).unwrap_or(0)
+}
@@ -0,0 +1,171 @@
+// 拖拽预览图：文件图标或图片缩略图（由平台层绘制），多个文件时在右上角加数量角标，输出 PNG
+// PNG 编码也供屏幕截取（captureScreenRegion）使用
+// 不依赖 napi，像素统一为非预乘的 RGBA，逐行从上到下
+
+// 方形 RGBA 画布
+pub struct Canvas {
+    pub size: u32,
+    pub pixels: Vec<u8>,
+}
+
+const BADGE_COLOR: [u8; 3] = [0xff, 0x3b, 0x30];
+const BADGE_TEXT_COLOR: [u8; 3] = [0xff, 0xff, 0xff];
+
+// 3x5 点阵数字与 "+"，每行低 3 位从左到右
+const GLYPHS: [[u8; 5]; 11] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111], // 0
+    [0b010, 0b110, 0b010, 0b010, 0b111], // 1
+    [0b111, 0b001, 0b111, 0b100, 0b111], // 2
+    [0b111, 0b001, 0b111, 0b001, 0b111], // 3
+    [0b101, 0b101, 0b111, 0b001, 0b001], // 4
+    [0b111, 0b100, 0b111, 0b001, 0b111], // 5
+    [0b111, 0b100, 0b111, 0b101, 0b111], // 6
+    [0b111, 0b001, 0b010, 0b010, 0b010], // 7
+    [0b111, 0b101, 0b111, 0b101, 0b111], // 8
+    [0b111, 0b101, 0b111, 0b001, 0b111], // 9
+    [0b000, 0b010, 0b111, 0b010, 0b000], // +
+];
+
+impl Canvas {
+    pub fn new(size: u32, pixels: Vec<u8>) -> Self {
+        debug_assert_eq!(pixels.len(), (size * size * 4) as usize);
+        Self { size, pixels }
+    }
+
+    // 以 coverage（0..=1）的不透明度把颜色叠加到 (x, y) 上
+    fn blend(&mut self, x: u32, y: u32, color: [u8; 3], coverage: f64) {
+        if x >= self.size || y >= self.size || coverage <= 0.0 {
+            return;
+        }
+        let index = ((y * self.size + x) * 4) as usize;
+        let pixel = &mut self.pixels[index..index + 4];
+        let src_alpha = coverage.min(1.0);
+        let dst_alpha = pixel[3] as f64 / 255.0;
+        let out_alpha = src_alpha + dst_alpha * (1.0 - src_alpha);
+        for channel in 0..3 {
+            let src = color[channel] as f64;
+            let dst = pixel[channel] as f64;
+            pixel[channel] = ((src * src_alpha + dst * dst_alpha * (1.0 - src_alpha)) / out_alpha).round() as u8;
+        }
+        pixel[3] = (out_alpha * 255.0).round() as u8;
+    }
+
+    // 在右上角绘制数量角标，超过 99 显示为 "99+"
+    pub fn draw_count_badge(&mut self, count: usize) {
+        let text: Vec<usize> = if count > 99 {
+            vec![9, 9, 10]
+        } else {
+            count.to_string().bytes().map(|digit| (digit - b'0') as usize).collect()
+        };
+
+        let scale = (self.size / 32).max(1);
+        let text_width = (text.len() as u32 * 4 - 1) * scale;
+        let text_height = 5 * scale;
+        let height = text_height + 4 * scale;
+        let width = (text_width + 4 * scale).max(height);
+        let radius = height as f64 / 2.0;
+        let left = self.size.saturating_sub(width) as f64;
+
+        // 胶囊形底色，边缘按到中轴线的距离做抗锯齿
+        for y in 0..height {
+            for x in 0..width {
+                let px = x as f64 + 0.5;
+                let py = y as f64 + 0.5;
+                let cx = px.clamp(radius, width as f64 - radius);
+                let distance = ((px - cx).powi(2) + (py - radius).powi(2)).sqrt();
+                self.blend(left as u32 + x, y, BADGE_COLOR, radius - distance + 0.5);
+            }
+        }
+
+        let text_left = left as u32 + (width - text_width) / 2;
+        let text_top = (height - text_height) / 2;
+        for (position, glyph) in text.iter().enumerate() {
+            let glyph_left = text_left + position as u32 * 4 * scale;
+            for (row, bits) in GLYPHS[*glyph].iter().enumerate() {
+                for column in 0..3 {
+                    if bits & (0b100 >> column) == 0 {
+                        continue;
+                    }
+                    for dy in 0..scale {
+                        for dx in 0..scale {
+                            let x = glyph_left + column * scale + dx;
+                            let y = text_top + row as u32 * scale + dy;
+                            self.blend(x, y, BADGE_TEXT_COLOR, 1.0);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn encode_png(&self) -> Vec<u8> {
+        encode_png(self.size, self.size, &self.pixels)
+    }
+}
+
+// 编码为 PNG（8 位 RGBA，不压缩的 deflate 块），不引入压缩库
+pub fn encode_png(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    let row_length = width as usize * 4;
+    let mut raw = Vec::with_capacity((row_length + 1) * height as usize);
+    for row in pixels.chunks(row_length) {
+        raw.push(0); // 过滤类型 None
+        raw.extend_from_slice(row);
+    }
+
+    let mut zlib = vec![0x78, 0x01];
+    let mut blocks = raw.chunks(0xffff).peekable();
+    if blocks.peek().is_none() {
+        zlib.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        zlib.push(blocks.peek().is_none() as u8);
+        zlib.extend_from_slice(&(block.len() as u16).to_le_bytes());
+        zlib.extend_from_slice(&(!(block.len() as u16)).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    header.extend_from_slice(&[8, 6, 0, 0, 0]); // 8 位、RGBA、deflate、默认过滤、不交错
+
+    let mut png = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib);
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for byte in chunk {
+            a += *byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
@@ -0,0 +1,79 @@
+// 系统级事件拦截（需启用 grab feature）：被选中的事件类型在送达任何应用之前被吞掉
+// 拦截钩子是进程级的，且 rdev::grab 会一直阻塞、没有停止接口，因此只在第一次使用时启动一个线程，
+// 之后通过位掩码决定吞掉哪些事件类型；掩码为 0 时所有事件原样放行
+
+use rdev::{grab, Event, EventType};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// 可被拦截的事件类型，与 MouseEvent.eventType / WheelEvent.eventType 的取值一致
+pub const EVENT_TYPES: [&str; 4] = ["mousedown", "mouseup", "mousemove", "wheel"];
+
+// 启动失败（没有辅助功能权限、无法打开 /dev/input 等）时 rdev::grab 会立即返回，
+// 在此时间内没有返回则认为拦截钩子已经就绪
+const STARTUP_TIMEOUT: Duration = Duration::from_millis(500);
+
+static GRABBED: AtomicU8 = AtomicU8::new(0);
+static STARTED: Mutex<bool> = Mutex::new(false);
+
+fn event_bit(event_type: &EventType) -> u8 {
+    let index = match event_type {
+        EventType::ButtonPress(_) => 0,
+        EventType::ButtonRelease(_) => 1,
+        EventType::MouseMove { .. } => 2,
+        EventType::Wheel { .. } => 3,
+    };
+    1 << index
+}
+
+fn mask_of(event_types: &[&str]) -> u8 {
+    EVENT_TYPES
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| event_types.contains(name))
+        .fold(0, |mask, (index, _)| mask | (1 << index))
+}
+
+fn ensure_started() -> Result<(), String> {
+    let mut started = STARTED.lock().map_err(|_| "Grab state poisoned".to_string())?;
+    if *started {
+        return Ok(());
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let result = grab(|event: Event| {
+            if GRABBED.load(Ordering::SeqCst) & event_bit(&event.event_type) != 0 {
+                None
+            } else {
+                Some(event)
+            }
+        });
+        let _ = sender.send(result);
+    });
+
+    match receiver.recv_timeout(STARTUP_TIMEOUT) {
+        Ok(Err(error)) => Err(format!("Failed to start event grab: {:?}", error)),
+        Ok(Ok(())) => Err("Event grab stopped unexpectedly".to_string()),
+        Err(_) => {
+            *started = true;
+            Ok(())
+        }
+    }
+}
+
+// 设置要吞掉的事件类型，空列表表示全部放行
+pub fn set(event_types: &[&str]) -> Result<(), String> {
+    let mask = mask_of(event_types);
+    if mask != 0 {
+        ensure_started()?;
+    }
+    GRABBED.store(mask, Ordering::SeqCst);
+    Ok(())
+}
+
+pub fn release() {
+    GRABBED.store(0, Ordering::SeqCst);
+}
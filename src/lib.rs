@@ -1,397 +1,4301 @@
 use napi::bindgen_prelude::*;
+use napi::{JsNumber, JsUnknown};
 use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode, ErrorStrategy};
 use napi_derive::napi;
-use rdev::{listen, Event, EventType, Button};
+use rdev::{listen, Event, EventType, Button, PointerType, ScrollPhase};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::any::Any;
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, TrySendError};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
-use std::time::UNIX_EPOCH;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+mod codec;
+mod coordinates;
+mod display_layout;
+mod drag_image;
+#[cfg(feature = "grab")]
+mod grab;
+mod log;
+mod paths;
+mod platform;
+mod state_machine;
+#[cfg(feature = "synthetic-load")]
+mod load;
+
+use coordinates::{CoordinateMapping, CoordinateSpace};
+use platform::WindowRegion;
+use state_machine::{DragPhase, DragStateMachine, DragTransition, PathRecorder, PointerInput, ThresholdMode, WheelThrottle};
 
 // region: Mouse Event Monitoring (鼠标事件监听系统)
 
-#[napi(object)]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct MouseEvent {
-    pub event_type: String,
-    pub x: f64,
-    pub y: f64,
-    pub button: i32,
-    pub timestamp: f64,
-    pub platform: String,
+// 鼠标事件类型
+#[napi(string_enum)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MouseEventType {
+    #[napi(value = "mousedown")]
+    Mousedown,
+    #[napi(value = "mouseup")]
+    Mouseup,
+    #[napi(value = "mousemove")]
+    Mousemove,
+}
+
+impl MouseEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MouseEventType::Mousedown => "mousedown",
+            MouseEventType::Mouseup => "mouseup",
+            MouseEventType::Mousemove => "mousemove",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "mousedown" => Some(MouseEventType::Mousedown),
+            "mouseup" => Some(MouseEventType::Mouseup),
+            "mousemove" => Some(MouseEventType::Mousemove),
+            _ => None,
+        }
+    }
+}
+
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MouseEvent {
+    pub event_type: MouseEventType,
+    pub x: f64,
+    pub y: f64,
+    pub button: i32,
+    pub timestamp: f64,
+    // 单调时钟时间（纳秒），不受系统校时影响，适合计算速度；起点任意，只在同一进程内可比较
+    pub monotonic_time_ns: f64,
+    pub platform: String,
+    // 由软件注入（模拟输入、远程控制等）而非输入设备产生
+    pub is_synthetic: bool,
+    // 产生事件的设备，无法识别时为 "mouse"
+    #[napi(ts_type = "'mouse' | 'pen' | 'touch'")]
+    pub pointer_type: String,
+    // 压力 [0, 1]；设备不提供压力时按 DOM 约定，有按键按下为 0.5，否则为 0
+    pub pressure: f64,
+    // 笔的倾斜角度（度）[-90, 90]，不提供时为 0
+    pub tilt_x: f64,
+    pub tilt_y: f64,
+    // 坐标所在的显示器（与 getDisplays 的 id 一致），不在任何显示器上时为 0
+    pub display_id: u32,
+}
+
+
+// 拖拽事件类型
+#[napi(string_enum)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DragEventType {
+    #[napi(value = "dragstart")]
+    Dragstart,
+    #[napi(value = "dragmove")]
+    Dragmove,
+    #[napi(value = "dragend")]
+    Dragend,
+    #[napi(value = "dragcancel")]
+    Dragcancel,
+}
+
+impl DragEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DragEventType::Dragstart => "dragstart",
+            DragEventType::Dragmove => "dragmove",
+            DragEventType::Dragend => "dragend",
+            DragEventType::Dragcancel => "dragcancel",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "dragstart" => Some(DragEventType::Dragstart),
+            "dragmove" => Some(DragEventType::Dragmove),
+            "dragend" => Some(DragEventType::Dragend),
+            "dragcancel" => Some(DragEventType::Dragcancel),
+            _ => None,
+        }
+    }
+}
+
+impl From<DragPhase> for DragEventType {
+    fn from(phase: DragPhase) -> Self {
+        match phase {
+            DragPhase::Start => DragEventType::Dragstart,
+            DragPhase::Move => DragEventType::Dragmove,
+            DragPhase::End => DragEventType::Dragend,
+            DragPhase::Cancel => DragEventType::Dragcancel,
+        }
+    }
+}
+
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DragEvent {
+    pub event_type: DragEventType,
+    pub x: f64,
+    pub y: f64,
+    pub start_x: f64,
+    pub start_y: f64,
+    pub button: i32,
+    pub timestamp: f64,
+    pub monotonic_time_ns: f64,
+    pub platform: String,
+    // 按下点所在的窗口区域："content" | "caption" | "resize" | "unknown"
+    #[napi(ts_type = "'content' | 'caption' | 'resize' | 'unknown'")]
+    pub press_region: String,
+    // 拖拽会话标识，每次 dragstart 递增
+    pub session_id: u32,
+    // JS 通过 annotateDrag 附加到当前会话的元数据
+    pub annotations: HashMap<String, String>,
+    // 拖拽结束的原因，只在 dragend/dragcancel 上有值
+    pub reason: Option<DragEndReason>,
+    // 启用轨迹记录时，dragend 上携带从起点到终点的拖拽轨迹
+    pub path: Option<Vec<Point>>,
+    // 触发本次状态变化的鼠标事件是软件注入的
+    pub is_synthetic: bool,
+    // 当前坐标所在的显示器，同 MouseEvent.displayId
+    pub display_id: u32,
+}
+
+// 拖拽结束/取消的原因
+#[napi(string_enum)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DragEndReason {
+    // 松开按键，正常结束（dragend）
+    #[napi(value = "release")]
+    Release,
+    // 调用 stopMouseMonitor/pauseMonitoring 时丢弃进行中的拖拽（dragcancel）
+    #[napi(value = "programmatic")]
+    Programmatic,
+    // 拖拽期间超过 setDragTimeout 设置的时间没有收到鼠标事件（dragcancel）
+    #[napi(value = "timeout")]
+    Timeout,
+    // 系统钩子线程 panic 或意外退出，监听已停止（dragcancel）
+    #[napi(value = "monitor-stopped")]
+    #[serde(rename = "monitor-stopped")]
+    MonitorStopped,
+    // setActiveAppFilter 指定的应用不再位于前台（dragcancel）
+    #[napi(value = "focus-change")]
+    #[serde(rename = "focus-change")]
+    FocusChange,
+}
+
+// 滚轮事件，delta 以滚动格数为单位，符号与 DOM 一致（正值表示向下/向右滚动）
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WheelEvent {
+    #[napi(ts_type = "'wheel'")]
+    pub event_type: String,
+    pub x: f64,
+    pub y: f64,
+    pub delta_x: f64,
+    pub delta_y: f64,
+    pub timestamp: f64,
+    pub platform: String,
+    pub is_synthetic: bool,
+    // 像素精度的位移，符号与 delta 一致；只有系统提供精确滚动数据时才有值（目前为 macOS）
+    pub pixel_delta_x: Option<f64>,
+    pub pixel_delta_y: Option<f64>,
+    // 触控板滚动手势的阶段，普通滚轮为空
+    #[napi(ts_type = "'began' | 'changed' | 'ended' | 'momentum'")]
+    pub phase: Option<String>,
+}
+
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CursorPosition {
+    pub x: f64,
+    pub y: f64,
+    pub display_id: u32,
+}
+
+// 显示器尺寸，单位与事件坐标一致（macOS 为点，Windows/Linux 为像素）
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplaySize {
+    pub width: f64,
+    pub height: f64,
+}
+
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Rectangle {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+// getWindowBounds 的查询条件，pid 与 nativeHandle 二选一
+#[napi(object)]
+pub struct WindowQuery {
+    // 取该进程最上层的可见窗口
+    pub pid: Option<u32>,
+    // BrowserWindow.getNativeWindowHandle() 返回的 Buffer
+    pub native_handle: Option<Buffer>,
+}
+
+// 显示器信息，bounds/workArea 与事件坐标处于同一坐标空间（相对参考显示器）
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Display {
+    // 与 CursorPosition.displayId 一致
+    pub id: u32,
+    pub bounds: Rectangle,
+    // 去掉菜单栏、Dock、任务栏之后的可用区域
+    pub work_area: Rectangle,
+    // 每个坐标单位对应的物理像素数
+    pub scale_factor: f64,
+    pub primary: bool,
+}
+
+// 触控板手势事件：缩放、旋转为连续手势，带 began/changed/ended 阶段；轻扫为一次性事件
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GestureEvent {
+    #[napi(ts_type = "'pinch' | 'rotate' | 'swipe'")]
+    pub event_type: String,
+    #[napi(ts_type = "'began' | 'changed' | 'ended'")]
+    pub phase: Option<String>,
+    // 手势发生时的光标位置
+    pub x: f64,
+    pub y: f64,
+    // pinch：相对上一个事件的缩放增量（0.1 表示放大 10%），其他手势为 0
+    pub scale: f64,
+    // rotate：相对上一个事件的旋转角度，逆时针为正，其他手势为 0
+    pub rotation: f64,
+    // swipe：方向，x 向右、y 向下为正，取值 -1/0/1，其他手势为 0
+    pub delta_x: f64,
+    pub delta_y: f64,
+    pub timestamp: f64,
+    pub platform: String,
+}
+
+// 系统剪贴板内容变化：报告变化后剪贴板提供的内容种类，不读取内容本身
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardEvent {
+    pub has_files: bool,
+    pub has_text: bool,
+    pub has_image: bool,
+    pub timestamp: f64,
+    pub platform: String,
+}
+
+// 挂载在窗口上的放置目标收到的文件拖拽；x、y 相对窗口内容区左上角，单位与页面中的 CSS 像素一致
+// （不是屏幕坐标，不受参考显示器和坐标空间设置影响）。drop-target-leave 时 paths 为空
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DropTargetEvent {
+    #[napi(ts_type = "'drop-target-enter' | 'drop-target-over' | 'drop-target-leave' | 'drop-target-drop'")]
+    pub event_type: String,
+    pub target_id: u32,
+    pub x: f64,
+    pub y: f64,
+    pub paths: Vec<String>,
+    pub timestamp: f64,
+    pub platform: String,
+}
+
+// 当前构建与平台支持的功能
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Capabilities {
+    // 构建时启用了 grab feature（rdev unstable_grab）
+    pub grab: bool,
+    // 支持 startExternalDragWatch
+    pub external_drag_watch: bool,
+    // 支持标题栏/边框命中测试（setWindowChromeDragMode）
+    pub window_region: bool,
+    // 支持 startFilePromiseDrag（需要 async-runtime feature）
+    pub file_promise_drag: bool,
+    // 支持 startGestureWatch
+    pub gestures: bool,
+    // 支持 attachDropTarget
+    pub drop_target: bool,
+}
+
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+// 当前按键与拖拽状态的快照，便于新打开的窗口与进行中的拖拽同步
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InputState {
+    pub mouse_pressed: bool,
+    pub pressed_buttons: Vec<i32>,
+    pub is_dragging: bool,
+    pub drag_start: Option<Point>,
+    pub drag_session_id: Option<u32>,
+    pub current_position: Option<Point>,
+}
+
+// 统一事件流的载荷，`kind` 为 "mouse"、"drag" 或 "wheel"，对应字段有值
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InputEvent {
+    #[napi(ts_type = "'mouse' | 'drag' | 'wheel'")]
+    pub kind: String,
+    pub mouse: Option<MouseEvent>,
+    pub drag: Option<DragEvent>,
+    pub wheel: Option<WheelEvent>,
+}
+
+// 监听过程中的错误：回调抛出异常、回调调用失败、系统钩子无法安装或监听线程意外退出
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorError {
+    // "callback"：某个回调出错，callback_id 为它的 ID；"listener"：监听线程出错；
+    // "monitor-stopped"：系统钩子线程退出，监听已停止
+    #[napi(ts_type = "'callback' | 'listener' | 'monitor-stopped'")]
+    pub kind: String,
+    pub callback_id: Option<u32>,
+    pub message: String,
+    pub timestamp: f64,
+}
+
+// 各类已注册回调的数量
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CallbackCounts {
+    pub mouse: u32,
+    pub drag: u32,
+    // onCurrentDrag 注册、尚未随会话结束移除的回调
+    pub current_drag: u32,
+    pub input: u32,
+    pub wheel: u32,
+    pub external_drag: u32,
+    pub region: u32,
+    pub screen_edge: u32,
+    pub theme: u32,
+    pub gesture: u32,
+    pub clipboard: u32,
+    pub drop_target: u32,
+    pub error: u32,
+    pub log: u32,
+}
+
+// 本环境监听状态的快照，用于排查收不到事件之类的问题
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostics {
+    pub is_monitoring: bool,
+    pub is_paused: bool,
+    // 前台应用过滤（setActiveAppFilter）当前是否允许分发事件
+    pub app_active: bool,
+    // 监听线程仍在运行；停止监听后线程保留，同样为 true
+    pub listener_thread_alive: bool,
+    // 钩子线程退出后正在等待自动重启
+    pub restart_pending: bool,
+    pub auto_monitor: bool,
+    pub callbacks: CallbackCounts,
+    pub input_state: InputState,
+    #[napi(ts_type = "'blocking' | 'nonblocking'")]
+    pub call_mode: String,
+    // 每个回调的队列上限，0 表示不限
+    pub max_queue_size: u32,
+    // 因队列已满而丢弃的事件数（getCallbackOverflowCount）
+    pub callback_overflows: u32,
+    // 钩子与分发线程之间的队列中等待处理的事件数，持续增长说明分发线程跟不上
+    pub dispatch_queue_depth: u32,
+    // 正在运行的后台监视
+    #[napi(ts_type = "Array<'external-drag' | 'theme' | 'active-app' | 'gesture' | 'clipboard'>")]
+    pub watches: Vec<String>,
+    pub drop_targets: u32,
+    pub holds_mouse_grab: bool,
+    // macOS 是否已授予辅助功能权限；其他平台不需要授权，为 null
+    pub input_permission: Option<bool>,
+    pub last_error: Option<MonitorError>,
+}
+
+// 插件内部的日志记录，target 为产生日志的子系统
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LogRecord {
+    #[napi(ts_type = "'error' | 'warn' | 'info' | 'debug'")]
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    pub timestamp: f64,
+}
+
+// 标题栏/窗口边框拖拽的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChromeDragMode {
+    // 不做命中测试，press_region 恒为 "unknown"
+    Off,
+    // 对按下点分类并写入 press_region
+    Classify,
+    // 分类，并且不为落在标题栏/边框上的按下产生拖拽事件
+    Suppress,
+}
+
+// 对外报告的按键编号方式，内部始终使用 native 编号
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ButtonNumbering {
+    // 0=无按键，1=左键，2=中键，3=右键，4=后退，5=前进
+    Native,
+    // 与 DOM MouseEvent.button 一致：0=左键，1=中键，2=右键，3=后退，4=前进，无按键为 -1
+    Dom,
+}
+
+impl ButtonNumbering {
+    fn map(&self, button: i32) -> i32 {
+        match self {
+            ButtonNumbering::Native => button,
+            ButtonNumbering::Dom => button - 1,
+        }
+    }
+
+    fn unmap(&self, button: i32) -> i32 {
+        match self {
+            ButtonNumbering::Native => button,
+            ButtonNumbering::Dom => button + 1,
+        }
+    }
+}
+
+// rdev 的按键到 native 编号；后退/前进键在所有平台上固定为 4/5，其余未知按键沿用系统给出的编号
+fn button_number(button: Button) -> i32 {
+    match button {
+        Button::Left => 1,
+        Button::Middle => 2,
+        Button::Right => 3,
+        Button::Back => 4,
+        Button::Forward => 5,
+        Button::Unknown(b) => b as i32,
+    }
+}
+
+// 外部拖拽检测关注的屏幕区域
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalDragZone {
+    pub id: String,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+// 外部拖拽经过区域的事件："external_drag_over_zone" | "external_drag_leave_zone"
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalDragEvent {
+    #[napi(ts_type = "'external_drag_over_zone' | 'external_drag_leave_zone'")]
+    pub event_type: String,
+    pub zone_id: String,
+    pub x: f64,
+    pub y: f64,
+    pub has_files: bool,
+    // 拖拽开始时的前台应用，通常就是拖拽的来源；系统不提供拖拽剪贴板的所有者，这只是推断
+    pub source_app: Option<FrontmostApplication>,
+    pub timestamp: f64,
+    pub platform: String,
+}
+
+// configure 的选项，缺省的字段保持当前设置
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorConfig {
+    // 注册鼠标/拖拽/滚轮/统一事件流/屏幕区域回调时，如果没有在监听则自动开始，缺省为 false
+    pub auto_start: Option<bool>,
+    // 从监听线程调用回调的方式：队列已满时 "blocking" 等待 JS 取走事件（缺省），"nonblocking" 丢弃事件并计入溢出数
+    #[napi(ts_type = "'blocking' | 'nonblocking'")]
+    pub call_mode: Option<String>,
+    // 每个回调最多排队的事件数，0 表示不限制（缺省）；只对之后注册的回调生效
+    pub max_queue_size: Option<u32>,
+    // 系统钩子线程异常退出后按退避时间自动重启，缺省为 false
+    pub auto_restart: Option<bool>,
+    // 可以开始拖拽的按键，按当前的按键编号方式（setButtonNumbering）给出；空数组表示所有按键（缺省）
+    pub drag_buttons: Option<Vec<i32>>,
+}
+
+// initialize 的选项，缺省的字段保持当前设置
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeOptions {
+    // 开始拖拽所需的移动距离（像素），同 setDragThreshold 的 thresholdPx
+    pub drag_threshold_px: Option<f64>,
+    // 按键按住的时间阈值（毫秒），缺省为 0 表示不使用
+    pub drag_threshold_ms: Option<u32>,
+    // "either"（缺省）：距离或按住时间任一超过阈值即开始拖拽；"both"：两者都超过才开始
+    #[napi(ts_type = "'either' | 'both'")]
+    pub drag_threshold_mode: Option<String>,
+    // 滚轮事件的最小分发间隔（毫秒），同 setWheelThrottle
+    pub throttle_ms: Option<u32>,
+    #[napi(ts_type = "'logical' | 'physical'")]
+    pub coordinate_space: Option<String>,
+    #[napi(ts_type = "'native' | 'dom'")]
+    pub buttons: Option<String>,
+    // 同 configure 的 autoStart
+    pub auto_start: Option<bool>,
+}
+
+// addScreenRegion 的选项
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenRegionOptions {
+    // 只在拖拽进行中判断进入/离开，缺省为 false
+    pub drag_only: Option<bool>,
+}
+
+// 光标进入/离开已注册区域的事件，坐标与事件坐标一致
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionEvent {
+    #[napi(ts_type = "'region-enter' | 'region-leave'")]
+    pub event_type: String,
+    pub region_id: String,
+    pub x: f64,
+    pub y: f64,
+    // 事件发生时是否有进行中的拖拽，以及该拖拽的会话标识
+    pub dragging: bool,
+    pub drag_session_id: Option<u32>,
+    pub timestamp: f64,
+    pub platform: String,
+}
+
+// setScreenEdgeDetection 的选项
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenEdgeOptions {
+    // 距离显示器外侧边缘多少像素以内算作到达边缘，缺省为 2
+    pub threshold: Option<f64>,
+    // 停留在边缘时重复报告的间隔（毫秒），缺省为 250，0 表示只在到达时报告一次
+    pub repeat_interval_ms: Option<u32>,
+}
+
+// 拖拽到达或离开显示器外侧边缘（角落）的事件，坐标与事件坐标一致
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenEdgeEvent {
+    #[napi(ts_type = "'drag-screen-edge' | 'drag-screen-edge-leave'")]
+    pub event_type: String,
+    #[napi(ts_type = "'top' | 'bottom' | 'left' | 'right' | 'top-left' | 'top-right' | 'bottom-left' | 'bottom-right'")]
+    pub edge: String,
+    pub display_id: u32,
+    pub x: f64,
+    pub y: f64,
+    // 在该边缘停留的时间（毫秒），到达时为 0
+    pub dwell_ms: f64,
+    pub drag_session_id: Option<u32>,
+    pub timestamp: f64,
+    pub platform: String,
+}
+
+// 前台应用：bundleIdOrPath 在 macOS 上为 bundle identifier（没有时为可执行文件路径），其他平台为可执行文件路径
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FrontmostApplication {
+    pub pid: u32,
+    pub name: String,
+    pub bundle_id_or_path: Option<String>,
+}
+
+impl From<platform::FrontmostApp> for FrontmostApplication {
+    fn from(app: platform::FrontmostApp) -> Self {
+        Self {
+            pid: app.pid,
+            name: app.name.clone().or_else(|| app.exe_name()).unwrap_or_default(),
+            bundle_id_or_path: app
+                .bundle_id
+                .or_else(|| app.executable.map(|path| path.to_string_lossy().into_owned())),
+        }
+    }
+}
+
+// setActiveAppFilter 的条件：前台应用与任一给出的字段相符时分发事件，比较不区分大小写
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveAppFilter {
+    // macOS 的 bundle identifier，例如 "com.apple.finder"
+    pub bundle_id: Option<String>,
+    // 可执行文件名，例如 "Code.exe"
+    pub exe_name: Option<String>,
+}
+
+// 系统外观："dark" | "light" | "unknown"，强调色为 "#rrggbb"
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemTheme {
+    #[napi(ts_type = "'dark' | 'light' | 'unknown'")]
+    pub appearance: String,
+    pub accent_color: Option<String>,
+    pub platform: String,
+}
+
+// startNativeFileDrag 的选项
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeFileDragOptions {
+    // 拖拽预览图片，缺省时使用系统文件图标
+    pub image_path: Option<String>,
+    // 预览图片边长（点），缺省为 64
+    pub image_size: Option<f64>,
+}
+
+// 拖出的虚拟文件，内容在目标接受放下之后由 JS 回调生成
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PromisedFile {
+    pub file_name: String,
+    // macOS 的 UTI（默认 public.data），其他平台忽略
+    pub file_type: Option<String>,
+}
+
+// 请求生成虚拟文件内容时传给回调的参数
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FilePromiseRequest {
+    pub index: u32,
+    pub file_name: String,
+}
+
+// 屏幕取色结果，hex 为 "#rrggbb"
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenColor {
+    pub r: u32,
+    pub g: u32,
+    pub b: u32,
+    pub a: u32,
+    pub hex: String,
+}
+
+// captureScreenRegion 截取的区域；指定 displayId 时坐标相对该显示器左上角，否则与事件坐标一致
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenRegion {
+    pub x: f64,
+    pub y: f64,
+    pub width: u32,
+    pub height: u32,
+    pub display_id: Option<u32>,
+}
+
+// captureScreenRegion 的输出格式
+#[napi(string_enum)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureFormat {
+    // 非预乘的 RGBA，逐行从上到下，长度为 width * height * 4
+    #[napi(value = "rgba")]
+    Rgba,
+    #[napi(value = "png")]
+    Png,
+}
+
+// createDragImage 的选项
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DragImageOptions {
+    // 预览图边长（像素），缺省为 64
+    pub size: Option<u32>,
+    // 多个文件时是否绘制数量角标，缺省为 true
+    pub badge: Option<bool>,
+}
+
+// onDragEvent 的订阅选项：每个回调各自过滤，不影响全局设置和其他回调
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DragListenerOptions {
+    // 只接收这些类型的事件，缺省接收全部
+    pub event_types: Option<Vec<DragEventType>>,
+    // 只接收这些按键的拖拽，编号与 DragEvent.button 一致
+    pub buttons: Option<Vec<i32>>,
+    // 与上一次交给该回调的位置相距小于该距离（像素）的 dragmove 被跳过，缺省为 0
+    pub min_move_delta: Option<f64>,
+    // 只接收起点落在该矩形内的拖拽，坐标与事件坐标一致
+    pub region: Option<Rectangle>,
+}
+
+// 单个显示器的拖拽阈值
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DragThresholdOverride {
+    pub display_id: u32,
+    pub threshold_px: f64,
+}
+
+// normalizePath 的选项
+#[napi(object)]
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NormalizePathOptions {
+    // Windows：加上 \\?\ 长路径前缀（默认去掉），其他平台忽略
+    pub long_path_prefix: Option<bool>,
+    // 解析符号链接，要求路径存在（默认不解析）
+    pub resolve_symlinks: Option<bool>,
+}
+
+// 已注册的屏幕区域，inside 为上一次判断时光标是否在区域内
+struct TrackedRegion {
+    id: String,
+    rect: Rectangle,
+    drag_only: bool,
+    inside: bool,
+}
+
+// 屏幕边缘检测：显示器边界在启用时换算为事件坐标并缓存
+struct ScreenEdgeDetection {
+    threshold: f64,
+    repeat_interval: Option<Duration>,
+    displays: Vec<(u32, Rectangle)>,
+    // 拖拽当前所在的边缘
+    dwell: Option<EdgeDwell>,
+}
+
+struct EdgeDwell {
+    edge: &'static str,
+    display_id: u32,
+    since: Instant,
+    next_report: Option<Instant>,
+}
+
+// 事件回调以 Arc 共享：dispatch 在持锁期间取得引用，释放锁之后再调用
+type Callback<T> = Arc<ThreadsafeFunction<T, ErrorStrategy::CalleeHandled>>;
+
+struct UnifiedMonitorState {
+    is_monitoring: bool,
+    // 暂停时保留系统钩子，但不再分发事件
+    is_paused: bool,
+    mouse_callbacks: HashMap<u32, Callback<MouseEvent>>,
+    drag_callbacks: HashMap<u32, (DragSubscription, Callback<DragEvent>)>,
+    // 绑定到单个拖拽会话的回调：(会话标识, 回调)，会话结束后自动移除
+    session_drag_callbacks: HashMap<u32, (u32, Callback<DragEvent>)>,
+    input_callbacks: HashMap<u32, Callback<InputEvent>>,
+    wheel_callbacks: HashMap<u32, Callback<WheelEvent>>,
+    external_drag_callbacks: HashMap<u32, Callback<ExternalDragEvent>>,
+    region_callbacks: HashMap<u32, Callback<RegionEvent>>,
+    screen_edge_callbacks: HashMap<u32, Callback<ScreenEdgeEvent>>,
+    theme_callbacks: HashMap<u32, Callback<SystemTheme>>,
+    gesture_callbacks: HashMap<u32, Callback<GestureEvent>>,
+    clipboard_callbacks: HashMap<u32, Callback<ClipboardEvent>>,
+    drop_target_callbacks: HashMap<u32, Callback<DropTargetEvent>>,
+    error_callbacks: HashMap<u32, ThreadsafeFunction<MonitorError, ErrorStrategy::CalleeHandled>>,
+    // 最近一次报告的错误，供 getDiagnostics 查看
+    last_error: Option<MonitorError>,
+    // 本环境在 log 模块中登记的接收端，第一次调用 onLog 时登记
+    log_sink: Option<u32>,
+    next_callback_id: u32,
+    // 系统钩子无法卸载，停止监听时监听线程保留，只通过这个标志停止处理事件，再次开始时复用
+    listener_running: Arc<AtomicBool>,
+    monitor_handle: Option<thread::JoinHandle<()>>,
+    // 自动启停：第一个依赖监听线程的回调注册时开始监听，最后一个移除时停止
+    auto_monitor: bool,
+    // 注册回调时如果没有在监听则自动开始（configure({ autoStart })）
+    auto_start: bool,
+    // 钩子线程异常退出后自动重启（configure({ autoRestart })），restart_pending 表示正在等待重启
+    auto_restart: bool,
+    restart_pending: bool,
+    // 分发事件时调用回调的方式与每个回调的队列上限（configure({ callMode, maxQueueSize })）
+    callback_call_mode: ThreadsafeFunctionCallMode,
+    callback_queue_size: usize,
+    // 因队列已满而丢弃的事件数：nonblocking 模式下回调的队列，或钩子与分发线程之间的队列；
+    // 钩子回调不加锁，直接持有一份 Arc
+    callback_overflows: Arc<AtomicU32>,
+    // 钩子与分发线程之间的队列中等待处理的事件数
+    dispatch_queue_depth: Arc<AtomicUsize>,
+    // Drag state
+    drag: DragStateMachine,
+    drag_path: PathRecorder,
+    // 按显示器覆盖的拖拽阈值：display_id -> 像素
+    threshold_overrides: HashMap<u32, f64>,
+    press_region: WindowRegion,
+    chrome_drag_mode: ChromeDragMode,
+    button_numbering: ButtonNumbering,
+    // 开始监听/恢复时是否根据系统按键状态补记按下
+    seed_from_button_state: bool,
+    // 忽略在本进程窗口内发生的点击和拖拽
+    ignore_own_process_events: bool,
+    // 当前按下落在本进程窗口内或排除区域内，对应的按下/松开事件不分发
+    suppressed_press: bool,
+    // 排除区域：(id, 矩形)，区域内发生的事件不分发（addIgnoreRegion）
+    ignore_regions: Vec<(u32, Rectangle)>,
+    next_ignore_region_id: u32,
+    // 丢弃软件注入的事件，它们既不分发也不驱动拖拽状态
+    ignore_synthetic_events: bool,
+    drag_annotations: HashMap<String, String>,
+    // 当前按下的所有按键
+    pressed_buttons: Vec<i32>,
+    // 拖拽超时：最近一次收到鼠标事件的时间、超时时长（None 表示不限制）和检查线程
+    last_input: Instant,
+    drag_timeout: Option<Duration>,
+    drag_timeout_watch: Option<Arc<AtomicBool>>,
+    // 可以开始拖拽的按键（native 编号），None 表示所有按键
+    drag_buttons: Option<Vec<i32>>,
+    // Wheel throttle
+    wheel_throttle: WheelThrottle,
+    // 屏幕区域进入/离开
+    screen_regions: Vec<TrackedRegion>,
+    // 拖拽到达屏幕边缘，None 表示未启用
+    screen_edges: Option<ScreenEdgeDetection>,
+    // External drag watch
+    external_drag_zones: Vec<ExternalDragZone>,
+    external_drag_watch: Option<Arc<AtomicBool>>,
+    // System theme watch
+    theme_watch: Option<Arc<AtomicBool>>,
+    // Active app filter：app_active 由轮询线程更新，为 false 时与暂停一样不分发事件
+    active_app_filter: Option<ActiveAppFilter>,
+    active_app_watch: Option<Arc<AtomicBool>>,
+    app_active: bool,
+    // Gesture watch
+    gesture_watch: Option<Arc<AtomicBool>>,
+    // Clipboard monitor
+    clipboard_watch: Option<Arc<AtomicBool>>,
+    // 本环境挂载的放置目标，环境销毁时卸载
+    drop_targets: Vec<u32>,
+    // 参考显示器：对外报告的坐标以它的左上角为原点，None 表示使用系统主显示器
+    reference_display: Option<u32>,
+    reference_origin: (f64, f64),
+    // 对外报告的坐标空间（逻辑/物理）
+    coordinates: CoordinateMapping,
+    // 本环境设置过系统级事件拦截，环境销毁时需要解除
+    holds_mouse_grab: bool,
+}
+
+impl UnifiedMonitorState {
+    // 暂停或前台应用不符合过滤条件时不驱动拖拽状态机，也不分发事件
+    fn dispatch_suspended(&self) -> bool {
+        self.is_paused || !self.app_active
+    }
+
+    fn new() -> Self {
+        Self {
+            is_monitoring: false,
+            is_paused: false,
+            mouse_callbacks: HashMap::new(),
+            drag_callbacks: HashMap::new(),
+            session_drag_callbacks: HashMap::new(),
+            input_callbacks: HashMap::new(),
+            wheel_callbacks: HashMap::new(),
+            external_drag_callbacks: HashMap::new(),
+            region_callbacks: HashMap::new(),
+            screen_edge_callbacks: HashMap::new(),
+            theme_callbacks: HashMap::new(),
+            gesture_callbacks: HashMap::new(),
+            clipboard_callbacks: HashMap::new(),
+            drop_target_callbacks: HashMap::new(),
+            error_callbacks: HashMap::new(),
+            last_error: None,
+            log_sink: None,
+            next_callback_id: 0,
+            listener_running: Arc::new(AtomicBool::new(false)),
+            monitor_handle: None,
+            auto_monitor: false,
+            auto_start: false,
+            auto_restart: false,
+            restart_pending: false,
+            callback_call_mode: ThreadsafeFunctionCallMode::Blocking,
+            callback_queue_size: 0,
+            callback_overflows: Arc::new(AtomicU32::new(0)),
+            dispatch_queue_depth: Arc::new(AtomicUsize::new(0)),
+            // Drag state
+            drag: DragStateMachine::new(),
+            drag_path: PathRecorder::new(),
+            threshold_overrides: HashMap::new(),
+            press_region: WindowRegion::Unknown,
+            chrome_drag_mode: ChromeDragMode::Off,
+            button_numbering: ButtonNumbering::Native,
+            seed_from_button_state: false,
+            ignore_own_process_events: false,
+            suppressed_press: false,
+            ignore_regions: Vec::new(),
+            next_ignore_region_id: 0,
+            ignore_synthetic_events: false,
+            drag_annotations: HashMap::new(),
+            pressed_buttons: Vec::new(),
+            last_input: Instant::now(),
+            drag_timeout: None,
+            drag_timeout_watch: None,
+            drag_buttons: None,
+            // Wheel throttle
+            wheel_throttle: WheelThrottle::new(),
+            screen_regions: Vec::new(),
+            screen_edges: None,
+            // External drag watch
+            external_drag_zones: Vec::new(),
+            external_drag_watch: None,
+            theme_watch: None,
+            active_app_filter: None,
+            active_app_watch: None,
+            app_active: true,
+            gesture_watch: None,
+            clipboard_watch: None,
+            drop_targets: Vec::new(),
+            reference_display: None,
+            reference_origin: (0.0, 0.0),
+            coordinates: CoordinateMapping::native(),
+            holds_mouse_grab: false,
+        }
+    }
+}
+
+// 一个 onDragEvent 回调的过滤条件，以及 minMoveDelta 需要的上一次交给它的位置
+struct DragSubscription {
+    options: DragListenerOptions,
+    last_position: Option<(f64, f64)>,
+}
+
+impl DragSubscription {
+    fn new(options: DragListenerOptions) -> Self {
+        Self { options, last_position: None }
+    }
+
+    fn accepts(&mut self, event: &DragEvent) -> bool {
+        // minMoveDelta 按会话计算：拖拽开始和结束时清掉上一次的位置，即使这两个事件本身被过滤
+        if event.event_type != DragEventType::Dragmove {
+            self.last_position = None;
+        }
+        let options = &self.options;
+        if options.event_types.as_ref().is_some_and(|types| !types.contains(&event.event_type)) {
+            return false;
+        }
+        if options.buttons.as_ref().is_some_and(|buttons| !buttons.contains(&event.button)) {
+            return false;
+        }
+        if let Some(region) = &options.region {
+            let (x, y) = (event.start_x, event.start_y);
+            if x < region.x || x >= region.x + region.width || y < region.y || y >= region.y + region.height {
+                return false;
+            }
+        }
+        if let (DragEventType::Dragmove, Some(min_delta), Some((last_x, last_y))) =
+            (&event.event_type, options.min_move_delta, self.last_position)
+        {
+            let (delta_x, delta_y) = (event.x - last_x, event.y - last_y);
+            if (delta_x * delta_x + delta_y * delta_y).sqrt() < min_delta {
+                return false;
+            }
+        }
+        self.last_position = Some((event.x, event.y));
+        true
+    }
+}
+
+// 每个 napi 环境（Electron 主进程、utility process、worker 等）持有一份独立的监听上下文，
+// 通过 instance data 绑定到模块环境上，避免多个上下文之间的状态串扰
+#[derive(Clone)]
+struct MonitorContext {
+    state: Arc<Mutex<UnifiedMonitorState>>,
+    last_position: Arc<Mutex<Option<(f64, f64)>>>,
+    // 日志回调不放在状态里：持有状态锁的代码也会写日志，接收端再获取状态锁会死锁
+    log_callbacks: Arc<Mutex<HashMap<u32, ThreadsafeFunction<LogRecord, ErrorStrategy::CalleeHandled>>>>,
+}
+
+impl MonitorContext {
+    fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(UnifiedMonitorState::new())),
+            last_position: Arc::new(Mutex::new(None)),
+            log_callbacks: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, UnifiedMonitorState>> {
+        self.state.lock().map_err(|_| Error::new(Status::GenericFailure, "Failed to acquire unified monitor state lock"))
+    }
+
+    // 环境销毁时释放所有回调并脱离监听线程，避免 tsfn 在环境销毁后被调用
+    fn release(&self) {
+        let drop_targets = match self.state.lock() {
+            Ok(mut state) => std::mem::take(&mut state.drop_targets),
+            Err(_) => Vec::new(),
+        };
+        // 卸载会释放系统持有的目标对象，不在持有状态锁时进行
+        for id in drop_targets {
+            platform::detach_drop_target(id);
+        }
+        if let Ok(mut log_callbacks) = self.log_callbacks.lock() {
+            log_callbacks.clear();
+        }
+        if let Ok(mut state) = self.state.lock() {
+            state.mouse_callbacks.clear();
+            state.drag_callbacks.clear();
+            state.session_drag_callbacks.clear();
+            state.input_callbacks.clear();
+            state.wheel_callbacks.clear();
+            state.external_drag_callbacks.clear();
+            state.region_callbacks.clear();
+            state.screen_edge_callbacks.clear();
+            state.theme_callbacks.clear();
+            state.gesture_callbacks.clear();
+            state.clipboard_callbacks.clear();
+            state.drop_target_callbacks.clear();
+            state.error_callbacks.clear();
+            if let Some(sink) = state.log_sink.take() {
+                log::remove_sink(sink);
+            }
+            if let Some(running) = state.external_drag_watch.take() {
+                running.store(false, Ordering::SeqCst);
+            }
+            if let Some(running) = state.drag_timeout_watch.take() {
+                running.store(false, Ordering::SeqCst);
+            }
+            if let Some(running) = state.theme_watch.take() {
+                running.store(false, Ordering::SeqCst);
+            }
+            if let Some(running) = state.gesture_watch.take() {
+                running.store(false, Ordering::SeqCst);
+            }
+            if let Some(running) = state.clipboard_watch.take() {
+                running.store(false, Ordering::SeqCst);
+            }
+            if let Some(running) = state.active_app_watch.take() {
+                running.store(false, Ordering::SeqCst);
+            }
+            state.listener_running.store(false, Ordering::SeqCst);
+            state.monitor_handle.take();
+            state.is_monitoring = false;
+            reset_drag_state(&mut state);
+            #[cfg(feature = "grab")]
+            if std::mem::take(&mut state.holds_mouse_grab) {
+                grab::release();
+            }
+        }
+    }
+}
+
+// 获取当前环境的监听上下文，首次调用时创建并注册清理钩子
+fn monitor_context(env: &Env) -> Result<MonitorContext> {
+    if let Some(ctx) = env.get_instance_data::<MonitorContext>()? {
+        return Ok(ctx.clone());
+    }
+
+    let ctx = MonitorContext::new();
+    env.set_instance_data(ctx.clone(), (), |_| {})?;
+
+    let mut env = *env;
+    env.add_env_cleanup_hook(ctx.clone(), |ctx| ctx.release())?;
+    Ok(ctx)
+}
+
+// 重置拖拽状态的辅助函数
+fn reset_drag_state(state: &mut std::sync::MutexGuard<'_, UnifiedMonitorState>) {
+    state.drag.reset();
+    state.press_region = WindowRegion::Unknown;
+    state.suppressed_press = false;
+    state.drag_annotations.clear();
+    state.drag_path.clear();
+}
+
+fn platform_name() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "macos"
+    } else if cfg!(target_os = "windows") {
+        "windows"
+    } else if cfg!(target_os = "linux") {
+        "linux"
+    } else {
+        "unknown"
+    }
+}
+
+fn event_timestamp(event: &Event) -> f64 {
+    event.time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+}
+
+fn now_timestamp() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+}
+
+// monotonicTimeNs 的起点，整个进程共用，不同环境（窗口、worker）收到的事件可以直接比较
+static MONOTONIC_ORIGIN: OnceLock<Instant> = OnceLock::new();
+
+fn monotonic_now_ns() -> f64 {
+    MONOTONIC_ORIGIN.get_or_init(Instant::now).elapsed().as_nanos() as f64
+}
+
+fn convert_rdev_mouse_event(event: &Event, monotonic_time_ns: f64) -> Option<MouseEvent> {
+    let platform = platform_name();
+    let timestamp = event_timestamp(event);
+    let pointer_type = match event.pointer.map(|pointer| pointer.pointer_type) {
+        Some(PointerType::Pen) => "pen",
+        Some(PointerType::Touch) => "touch",
+        Some(PointerType::Mouse) | None => "mouse",
+    };
+    // 压力缺失时先记为 0，监听函数根据按键状态补上默认值
+    let pressure = event.pointer.and_then(|pointer| pointer.pressure).unwrap_or(0.0);
+    let tilt_x = event.pointer.and_then(|pointer| pointer.tilt_x).unwrap_or(0.0);
+    let tilt_y = event.pointer.and_then(|pointer| pointer.tilt_y).unwrap_or(0.0);
+
+    match event.event_type {
+        EventType::ButtonPress(button) => {
+            let button_num = button_number(button);
+
+            Some(MouseEvent {
+                event_type: MouseEventType::Mousedown,
+                x: 0.0, // Will be updated with actual coordinates
+                y: 0.0, // Will be updated with actual coordinates
+                button: button_num,
+                timestamp,
+                monotonic_time_ns,
+                platform: platform.to_string(),
+                is_synthetic: event.synthetic,
+                pointer_type: pointer_type.to_string(),
+                pressure,
+                tilt_x,
+                tilt_y,
+                display_id: 0, // Will be updated with actual coordinates
+            })
+        }
+        EventType::ButtonRelease(button) => {
+            let button_num = button_number(button);
+
+            Some(MouseEvent {
+                event_type: MouseEventType::Mouseup,
+                x: 0.0, // Will be updated with actual coordinates
+                y: 0.0, // Will be updated with actual coordinates
+                button: button_num,
+                timestamp,
+                monotonic_time_ns,
+                platform: platform.to_string(),
+                is_synthetic: event.synthetic,
+                pointer_type: pointer_type.to_string(),
+                pressure,
+                tilt_x,
+                tilt_y,
+                display_id: 0, // Will be updated with actual coordinates
+            })
+        }
+        EventType::MouseMove { x, y } => {
+            Some(MouseEvent {
+                event_type: MouseEventType::Mousemove,
+                x,
+                y,
+                button: 0,
+                timestamp,
+                monotonic_time_ns,
+                platform: platform.to_string(),
+                is_synthetic: event.synthetic,
+                pointer_type: pointer_type.to_string(),
+                pressure,
+                tilt_x,
+                tilt_y,
+                display_id: 0, // Will be updated with actual coordinates
+            })
+        }
+        // 滚轮事件走独立的 wheel 通道，见 handle_wheel_event
+        EventType::Wheel { .. } => None,
+    }
+}
+
+// 点 (x, y)（事件坐标）是否落在 addIgnoreRegion 注册的任一忽略区域内
+fn in_ignore_region(state: &UnifiedMonitorState, x: f64, y: f64) -> bool {
+    state
+        .ignore_regions
+        .iter()
+        .any(|(_, rect)| x >= rect.x && x < rect.x + rect.width && y >= rect.y && y < rect.y + rect.height)
+}
+
+// 处理滚轮事件：过滤零位移、按节流间隔合并位移，并分发到 wheel 通道
+fn handle_wheel_event(ctx: &MonitorContext, event: &Event) {
+    let EventType::Wheel { delta_x, delta_y } = event.event_type else {
+        return;
+    };
+    let phase = event.scroll.and_then(|scroll| scroll.phase);
+    let pixel_delta = event.scroll.map(|scroll| (scroll.pixel_delta_x, scroll.pixel_delta_y));
+    // 手势的开始和结束即使没有位移也要送达，否则消费者无法知道惯性滚动何时停止
+    let boundary = matches!(phase, Some(ScrollPhase::Began | ScrollPhase::Ended));
+    if delta_x == 0 && delta_y == 0 && pixel_delta.is_none_or(|(x, y)| x == 0.0 && y == 0.0) && !boundary {
+        return;
+    }
+
+    let (x, y) = ctx.last_position.lock().ok().and_then(|p| *p).unwrap_or((0.0, 0.0));
+
+    let wheel_event = {
+        let Ok(mut state) = ctx.state.lock() else {
+            return;
+        };
+        if state.dispatch_suspended() || in_ignore_region(&state, x, y) {
+            return;
+        }
+
+        // rdev 中正值表示向上/向右滚动，这里统一为 DOM 约定（正值向下/向右）
+        // 节流窗口内只累积位移，等下一次允许分发时一并发出；手势边界总是立即分发
+        if let Some((pixel_x, pixel_y)) = pixel_delta {
+            state.wheel_throttle.push_pixels(pixel_x, -pixel_y);
+        }
+        let (delta_x, delta_y) = (delta_x as f64, -(delta_y as f64));
+        let (delta_x, delta_y) = if boundary {
+            state.wheel_throttle.flush(delta_x, delta_y, Instant::now())
+        } else {
+            let Some(delta) = state.wheel_throttle.push(delta_x, delta_y, Instant::now()) else {
+                return;
+            };
+            delta
+        };
+        let (pixel_delta_x, pixel_delta_y) = state.wheel_throttle.take_pixels();
+        let pixel_delta = pixel_delta.map(|_| (pixel_delta_x, pixel_delta_y));
+
+        WheelEvent {
+            event_type: "wheel".to_string(),
+            x,
+            y,
+            delta_x,
+            delta_y,
+            timestamp: event_timestamp(event),
+            platform: platform_name().to_string(),
+            is_synthetic: event.synthetic,
+            pixel_delta_x: pixel_delta.map(|(x, _)| x),
+            pixel_delta_y: pixel_delta.map(|(_, y)| y),
+            phase: phase.map(|phase| {
+                match phase {
+                    ScrollPhase::Began => "began",
+                    ScrollPhase::Changed => "changed",
+                    ScrollPhase::Ended => "ended",
+                    ScrollPhase::Momentum => "momentum",
+                }
+                .to_string()
+            }),
+        }
+    };
+
+    trigger_wheel_event(ctx, wheel_event);
+}
+
+
+// 根据状态机输出的拖拽状态变化构造拖拽事件
+fn make_drag_event(
+    state: &UnifiedMonitorState,
+    transition: &DragTransition,
+    (timestamp, monotonic_time_ns): (f64, f64),
+    reason: Option<DragEndReason>,
+    is_synthetic: bool,
+) -> DragEvent {
+    DragEvent {
+        event_type: transition.phase.into(),
+        x: transition.x,
+        y: transition.y,
+        start_x: transition.start_x,
+        start_y: transition.start_y,
+        button: state.button_numbering.map(transition.button),
+        timestamp,
+        monotonic_time_ns,
+        platform: platform_name().to_string(),
+        press_region: state.press_region.as_str().to_string(),
+        session_id: transition.session_id,
+        annotations: state.drag_annotations.clone(),
+        reason,
+        path: (transition.phase == DragPhase::End && state.drag_path.enabled)
+            .then(|| state.drag_path.points().iter().map(|&(x, y)| Point { x, y }).collect()),
+        is_synthetic,
+        display_id: display_id_of(state, (transition.x, transition.y)),
+    }
+}
+
+// 取消进行中的拖拽并构造 dragcancel 事件，拖拽状态总会被重置；调用方在释放锁之后再分发事件
+fn cancel_drag(ctx: &MonitorContext, state: &mut std::sync::MutexGuard<'_, UnifiedMonitorState>, reason: DragEndReason) -> Option<DragEvent> {
+    let (x, y) = ctx.last_position.lock().ok().and_then(|p| *p).unwrap_or((0.0, 0.0));
+    let drag_event = state.drag.cancel(x, y).map(|t| make_drag_event(state, &t, (now_timestamp(), monotonic_now_ns()), Some(reason), false));
+    reset_drag_state(state);
+    drag_event
+}
+
+// 取消进行中的拖拽，有拖拽时分发 dragcancel，reason 为取消的原因（停止/暂停监听、钩子退出、超时、失去焦点等）
+fn cancel_active_drag(ctx: &MonitorContext, reason: DragEndReason) -> Result<()> {
+    let drag_event = cancel_drag(ctx, &mut ctx.lock()?, reason);
+    if let Some(drag_event) = drag_event {
+        trigger_drag_event(ctx, drag_event);
+    }
+    Ok(())
+}
+
+// 按住时间阈值到期：按住不动也开始拖拽，位置取最近一次观察到的光标位置
+fn check_drag_hold(ctx: &MonitorContext) {
+    let position = ctx.last_position.lock().ok().and_then(|pos| *pos);
+    let drag_event = match ctx.state.lock() {
+        Ok(mut state) if !state.dispatch_suspended() => {
+            let (x, y) = position.or(state.drag.potential_drag_start).unwrap_or_default();
+            state.drag.check_hold(x, y).map(|transition| {
+                state.drag_path.record(&transition);
+                make_drag_event(&state, &transition, (now_timestamp(), monotonic_now_ns()), None, false)
+            })
+        }
+        _ => None,
+    };
+    if let Some(drag_event) = drag_event {
+        trigger_drag_event(ctx, drag_event);
+    }
+}
+
+fn classify_press_region(ctx: &MonitorContext, x: f64, y: f64) -> WindowRegion {
+    let (mode, (x, y)) = match ctx.state.lock() {
+        Ok(state) => (state.chrome_drag_mode, to_global_coordinates(&state, (x, y))),
+        Err(_) => return WindowRegion::Unknown,
+    };
+    if mode == ChromeDragMode::Off {
+        return WindowRegion::Unknown;
+    }
+    platform::window_region_at(x, y)
+}
+
+// 启用 ignore_own_process_events 时，判断按下点上的窗口是否属于本进程
+fn pressed_in_own_window(ctx: &MonitorContext, x: f64, y: f64) -> bool {
+    let (x, y) = match ctx.state.lock() {
+        Ok(state) if state.ignore_own_process_events => to_global_coordinates(&state, (x, y)),
+        _ => return false,
+    };
+    platform::window_pid_at(x, y) == Some(std::process::id())
+}
+
+// 按下点所在显示器的拖拽阈值覆盖，没有匹配的覆盖时返回 None（使用全局阈值）
+// 显示器查询可能较慢，因此在持锁之外完成
+fn press_threshold(ctx: &MonitorContext, x: f64, y: f64) -> Option<f64> {
+    let (x, y) = match ctx.state.lock() {
+        Ok(state) if !state.threshold_overrides.is_empty() => to_global_coordinates(&state, (x, y)),
+        _ => return None,
+    };
+    let display_id = platform::display_id_at(x, y);
+    ctx.state.lock().ok()?.threshold_overrides.get(&display_id).copied()
+}
+
+// 把系统全局坐标换算为对外坐标空间中相对参考显示器的坐标
+fn to_reference_coordinates(ctx: &MonitorContext, position: (f64, f64)) -> (f64, f64) {
+    let Ok(state) = ctx.state.lock() else {
+        return position;
+    };
+    let (x, y) = state.coordinates.to_space(position);
+    let (origin_x, origin_y) = state.reference_origin;
+    (x - origin_x, y - origin_y)
+}
+
+// 事件坐标所在的显示器 ID，不在任何显示器上时为 0
+fn display_id_of(state: &UnifiedMonitorState, position: (f64, f64)) -> u32 {
+    let (x, y) = to_global_coordinates(state, position);
+    display_layout::display_id_at(x, y)
+}
+
+// to_reference_coordinates 的逆变换，平台查询（命中测试、截屏等）使用系统全局坐标
+fn to_global_coordinates(state: &UnifiedMonitorState, (x, y): (f64, f64)) -> (f64, f64) {
+    let (origin_x, origin_y) = state.reference_origin;
+    state.coordinates.to_native((x + origin_x, y + origin_y))
+}
+
+// 持锁期间准备好、释放锁之后才执行的一次回调调用
+type PendingCall = (u32, Box<dyn FnOnce() -> Status + Send>);
+
+// 为每个回调准备一次调用，加入 calls，由调用方在释放状态锁之后通过 deliver 执行
+// blocking 模式下队列已满时调用会一直等到 JS 线程取走事件，而 JS 线程上的回调可能正需要状态锁，
+// 所以不能在持锁期间调用
+fn dispatch<'a, T: Clone + Send + 'static>(
+    state: &UnifiedMonitorState,
+    callbacks: impl IntoIterator<Item = (&'a u32, &'a Callback<T>)>,
+    value: &T,
+    calls: &mut Vec<PendingCall>,
+) {
+    let call_mode = state.callback_call_mode;
+    for (id, callback) in callbacks {
+        // 克隆 Arc 而不是 tsfn 本身：环境销毁后 tsfn 被标记为 aborted，此时克隆 tsfn 会 panic，而调用只会返回错误
+        let (callback, value) = (Arc::clone(callback), value.clone());
+        calls.push((*id, Box::new(move || callback.call(Ok(value), call_mode))));
+    }
+}
+
+// 依次执行 dispatch 准备的调用，某个回调调用失败不影响其余回调；失败通过 report_monitor_errors 报告
+fn deliver(ctx: &MonitorContext, calls: Vec<PendingCall>) {
+    let mut overflows = 0;
+    let mut failures = Vec::new();
+    for (id, call) in calls {
+        let status = call();
+        if status == Status::QueueFull {
+            // nonblocking 模式下队列已满，丢弃这次事件，不作为错误报告
+            overflows += 1;
+        } else if status != Status::Ok {
+            failures.push(MonitorError {
+                kind: "callback".to_string(),
+                callback_id: Some(id),
+                message: format!("Failed to queue callback: {}", status),
+                timestamp: now_timestamp(),
+            });
+        }
+    }
+    if overflows > 0 {
+        if let Ok(state) = ctx.state.lock() {
+            state.callback_overflows.fetch_add(overflows, Ordering::Relaxed);
+        }
+    }
+    report_monitor_errors(ctx, failures);
+}
+
+// 把错误分发给 onMonitorError 注册的回调，返回是否有回调接收
+fn report_monitor_errors(ctx: &MonitorContext, errors: Vec<MonitorError>) -> bool {
+    if errors.is_empty() {
+        return false;
+    }
+    let Ok(mut state) = ctx.state.lock() else {
+        return false;
+    };
+    for error in errors {
+        for callback in state.error_callbacks.values() {
+            callback.call(Ok(error.clone()), ThreadsafeFunctionCallMode::NonBlocking);
+        }
+        state.last_error = Some(error);
+    }
+    !state.error_callbacks.is_empty()
+}
+
+// 用户回调外面包一层 try/catch：抛出的异常交给 report，有 onMonitorError 回调接收时不再继续抛出，
+// 这样一个回调出错既不会影响其他回调，也不会变成进程级的未捕获异常
+const ISOLATE_CALLBACK_SCRIPT: &str = r#"(function (callback, id, report) {
+  return function () {
+    try {
+      return callback.apply(this, arguments);
+    } catch (error) {
+      if (!report(id, error)) throw error;
+    }
+  };
+})"#;
+
+fn isolate_callback(env: &Env, ctx: &MonitorContext, id: u32, callback: JsFunction) -> Result<JsFunction> {
+    let wrap: JsFunction = env.run_script(ISOLATE_CALLBACK_SCRIPT)?;
+    let report_ctx = ctx.clone();
+    let report = env.create_function_from_closure("reportCallbackError", move |call| {
+        let callback_id = call.get::<JsNumber>(0)?.get_uint32()?;
+        let message = call.get::<JsUnknown>(1)?.coerce_to_string()?.into_utf8()?.into_owned()?;
+        let error = MonitorError {
+            kind: "callback".to_string(),
+            callback_id: Some(callback_id),
+            message,
+            timestamp: now_timestamp(),
+        };
+        Ok(report_monitor_errors(&report_ctx, vec![error]))
+    })?;
+    let wrapped = wrap.call(None, &[callback.into_unknown(), env.create_uint32(id)?.into_unknown(), report.into_unknown()])?;
+    JsFunction::try_from(wrapped)
+}
+
+fn trigger_mouse_event(ctx: &MonitorContext, mut mouse_event: MouseEvent) {
+    let mut calls = Vec::new();
+    if let Ok(state) = ctx.state.lock() {
+        mouse_event.button = state.button_numbering.map(mouse_event.button);
+        dispatch(&state, &state.mouse_callbacks, &mouse_event, &mut calls);
+        if !state.input_callbacks.is_empty() {
+            let input_event = InputEvent {
+                kind: "mouse".to_string(),
+                mouse: Some(mouse_event),
+                drag: None,
+                wheel: None,
+            };
+            dispatch(&state, &state.input_callbacks, &input_event, &mut calls);
+        }
+    }
+    deliver(ctx, calls);
+}
+
+
+fn trigger_drag_event(ctx: &MonitorContext, drag_event: DragEvent) {
+    let mut calls = Vec::new();
+    let mut removed_session_callbacks = false;
+    if let Ok(mut state) = ctx.state.lock() {
+        let accepted: Vec<u32> = state
+            .drag_callbacks
+            .iter_mut()
+            .filter_map(|(id, (subscription, _))| subscription.accepts(&drag_event).then_some(*id))
+            .collect();
+        let callbacks = state
+            .drag_callbacks
+            .iter()
+            .filter(|(id, _)| accepted.contains(id))
+            .map(|(id, (_, callback))| (id, callback));
+        dispatch(&state, callbacks, &drag_event, &mut calls);
+        let session_callbacks = state
+            .session_drag_callbacks
+            .iter()
+            .filter(|(_, (session_id, _))| *session_id == drag_event.session_id)
+            .map(|(id, (_, callback))| (id, callback));
+        dispatch(&state, session_callbacks, &drag_event, &mut calls);
+        if drag_event.reason.is_some() {
+            // 会话已结束（dragend/dragcancel），移除绑定到它的回调
+            let count = state.session_drag_callbacks.len();
+            state.session_drag_callbacks.retain(|_, (session_id, _)| *session_id != drag_event.session_id);
+            removed_session_callbacks = state.session_drag_callbacks.len() != count;
+        }
+        if !state.input_callbacks.is_empty() {
+            let input_event = InputEvent {
+                kind: "drag".to_string(),
+                mouse: None,
+                drag: Some(drag_event),
+                wheel: None,
+            };
+            dispatch(&state, &state.input_callbacks, &input_event, &mut calls);
+        }
+    }
+    deliver(ctx, calls);
+    // 移除的会话回调可能是最后的监听者，释放锁之后再按回调数量启停
+    if removed_session_callbacks {
+        let _ = sync_auto_monitor(ctx, false);
+    }
+}
+
+fn trigger_wheel_event(ctx: &MonitorContext, wheel_event: WheelEvent) {
+    let mut calls = Vec::new();
+    if let Ok(state) = ctx.state.lock() {
+        dispatch(&state, &state.wheel_callbacks, &wheel_event, &mut calls);
+        if !state.input_callbacks.is_empty() {
+            let input_event = InputEvent {
+                kind: "wheel".to_string(),
+                mouse: None,
+                drag: None,
+                wheel: Some(wheel_event),
+            };
+            dispatch(&state, &state.input_callbacks, &input_event, &mut calls);
+        }
+    }
+    deliver(ctx, calls);
+}
+
+// 统一的事件监听函数，只处理鼠标事件
+// monotonic_time_ns 为事件进入队列时的单调时钟，事件在分发线程上处理时可能已经排队了一段时间
+fn unified_event_listener(ctx: MonitorContext) -> impl FnMut(Event, f64) {
+    move |event: Event, monotonic_time_ns: f64| {
+        // 启用 ignore_synthetic_events 时，软件注入的事件在进入任何处理之前丢弃
+        if event.synthetic && ctx.state.lock().map(|state| state.ignore_synthetic_events).unwrap_or(false) {
+            return;
+        }
+
+        if let EventType::Wheel { .. } = event.event_type {
+            handle_wheel_event(&ctx, &event);
+            return;
+        }
+
+        // 尝试作为鼠标事件处理
+        if let Some(mut mouse_event) = convert_rdev_mouse_event(&event, monotonic_time_ns) {
+            // 处理鼠标事件的坐标
+            if mouse_event.event_type != MouseEventType::Mousemove {
+                if let Some((x, y)) = ctx.last_position.lock().ok().and_then(|p| *p) {
+                    mouse_event.x = x;
+                    mouse_event.y = y;
+                }
+            } else {
+                // 移动事件携带系统全局坐标，换算后再记录，之后的按键和滚轮事件直接沿用
+                (mouse_event.x, mouse_event.y) = to_reference_coordinates(&ctx, (mouse_event.x, mouse_event.y));
+                if let Ok(mut pos) = ctx.last_position.lock() {
+                    *pos = Some((mouse_event.x, mouse_event.y));
+                }
+            }
+
+            // 按键状态在暂停期间也保持跟踪；暂停期间只跟踪坐标和按键，不驱动拖拽状态机也不分发事件
+            if let Ok(mut state) = ctx.state.lock() {
+                state.last_input = Instant::now();
+                match mouse_event.event_type {
+                    MouseEventType::Mousedown if !state.pressed_buttons.contains(&mouse_event.button) => {
+                        state.pressed_buttons.push(mouse_event.button);
+                    }
+                    MouseEventType::Mouseup => state.pressed_buttons.retain(|b| *b != mouse_event.button),
+                    _ => {}
+                }
+                mouse_event.display_id = display_id_of(&state, (mouse_event.x, mouse_event.y));
+                if event.pointer.and_then(|pointer| pointer.pressure).is_none() && !state.pressed_buttons.is_empty() {
+                    mouse_event.pressure = 0.5;
+                }
+                if state.dispatch_suspended() {
+                    return;
+                }
+            }
+
+            // 按下时对按下点做窗口区域命中测试，查询可能阻塞，因此在持锁之前完成
+            let (press_region, threshold, own_press) = if mouse_event.event_type == MouseEventType::Mousedown {
+                (
+                    classify_press_region(&ctx, mouse_event.x, mouse_event.y),
+                    press_threshold(&ctx, mouse_event.x, mouse_event.y),
+                    pressed_in_own_window(&ctx, mouse_event.x, mouse_event.y),
+                )
+            } else {
+                (WindowRegion::Unknown, None, false)
+            };
+            // 本进程窗口内或排除区域内的按下及其松开、排除区域内的移动不分发鼠标事件
+            let mut suppress_mouse_event = false;
+
+            // 拖拽状态检测逻辑，状态机本身见 state_machine.rs
+            let input = match mouse_event.event_type {
+                MouseEventType::Mousedown => PointerInput::Press { button: mouse_event.button, x: mouse_event.x, y: mouse_event.y },
+                MouseEventType::Mousemove => PointerInput::Move { x: mouse_event.x, y: mouse_event.y },
+                MouseEventType::Mouseup => PointerInput::Release { button: mouse_event.button, x: mouse_event.x, y: mouse_event.y },
+            };
+            let drag_event = match ctx.state.lock() {
+                Ok(mut state) => {
+                    // 不参与拖拽的按键既不开始也不结束拖拽，只作为普通鼠标事件分发
+                    let drag_button = match input {
+                        PointerInput::Press { button, .. } | PointerInput::Release { button, .. } => {
+                            state.drag_buttons.as_ref().is_none_or(|buttons| buttons.contains(&button))
+                        }
+                        PointerInput::Move { .. } => true,
+                    };
+                    let ignored = in_ignore_region(&state, mouse_event.x, mouse_event.y);
+                    let transition = if drag_button { state.drag.handle(input) } else { None };
+                    if let (true, PointerInput::Press { .. }) = (drag_button, input) {
+                        state.press_region = press_region;
+                        state.drag.press_threshold = threshold;
+                        // 在标题栏/边框上按下时不记录起点，这次按下就不会产生拖拽
+                        if state.chrome_drag_mode == ChromeDragMode::Suppress && press_region.is_window_chrome() {
+                            state.drag.ignore_press();
+                        }
+                        if own_press || ignored {
+                            state.drag.ignore_press();
+                            state.suppressed_press = true;
+                        }
+                    }
+                    // 不参与拖拽的按键没有按下状态可跟踪，按各自的位置判断
+                    suppress_mouse_event = match input {
+                        PointerInput::Press { .. } | PointerInput::Release { .. } if drag_button => state.suppressed_press,
+                        _ => ignored,
+                    };
+                    // 排除区域内的 dragmove 不分发；开始和结束总是分发，保证会话完整
+                    let transition = transition.filter(|t| !(ignored && t.phase == DragPhase::Move));
+                    let drag_event = transition.map(|t| {
+                        state.drag_path.record(&t);
+                        let reason = (t.phase == DragPhase::End).then_some(DragEndReason::Release);
+                        make_drag_event(&state, &t, (mouse_event.timestamp, mouse_event.monotonic_time_ns), reason, mouse_event.is_synthetic)
+                    });
+                    if let (true, PointerInput::Release { .. }) = (drag_button, input) {
+                        // 无论是否开始拖拽，都重置所有状态
+                        reset_drag_state(&mut state);
+                    }
+                    drag_event
+                }
+                Err(_) => None,
+            };
+            // 锁已释放，再分发拖拽事件
+            if let Some(drag_event) = drag_event {
+                trigger_drag_event(&ctx, drag_event);
+            }
+
+            // 移动和松开（拖拽结束）都可能改变所在的区域
+            if mouse_event.event_type != MouseEventType::Mousedown {
+                let (region_events, edge_events) = match ctx.state.lock() {
+                    Ok(mut state) => (
+                        update_screen_regions(&mut state, mouse_event.x, mouse_event.y, mouse_event.timestamp),
+                        update_screen_edge(&mut state, mouse_event.x, mouse_event.y, mouse_event.timestamp),
+                    ),
+                    Err(_) => (Vec::new(), Vec::new()),
+                };
+                trigger_region_events(&ctx, region_events);
+                trigger_screen_edge_events(&ctx, edge_events);
+            }
+
+            if !suppress_mouse_event {
+                trigger_mouse_event(&ctx, mouse_event);
+            }
+        }
+        // 忽略所有非鼠标事件
+    }
+}
+
+// Mouse API functions
+#[napi]
+pub fn start_mouse_monitor(env: Env) -> Result<()> {
+    start_unified_monitor(&monitor_context(&env)?)
+}
+
+#[napi]
+pub fn stop_mouse_monitor(env: Env) -> Result<()> {
+    let ctx = monitor_context(&env)?;
+    cancel_active_drag(&ctx, DragEndReason::Programmatic)?;
+    stop_unified_monitor(&ctx)
+}
+
+#[napi(ts_args_type = "callback: (err: null | Error, event: MouseEvent) => void")]
+pub fn on_mouse_event(env: Env, callback: JsFunction) -> Result<u32> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let id = state.next_callback_id + 1;
+    state.next_callback_id = id;
+    let tsfn: ThreadsafeFunction<MouseEvent, ErrorStrategy::CalleeHandled> = isolate_callback(&env, &ctx, id, callback)?.create_threadsafe_function(state.callback_queue_size, |ctx| Ok(vec![ctx.value]))?;
+    state.mouse_callbacks.insert(id, Arc::new(tsfn));
+    drop(state);
+    sync_auto_monitor(&ctx, true)?;
+    Ok(id)
+}
+
+#[napi]
+pub fn remove_mouse_event_listener(env: Env, id: u32) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let removed = ctx.lock()?.mouse_callbacks.remove(&id).is_some();
+    sync_auto_monitor(&ctx, false)?;
+    Ok(removed)
+}
+
+
+// Drag API functions
+// options 中的过滤条件只作用于这一个回调
+#[napi(ts_args_type = "callback: (err: null | Error, event: DragEvent) => void, options?: DragListenerOptions")]
+pub fn on_drag_event(env: Env, callback: JsFunction, options: Option<DragListenerOptions>) -> Result<u32> {
+    let options = options.unwrap_or(DragListenerOptions { event_types: None, buttons: None, min_move_delta: None, region: None });
+    if let Some(min_delta) = options.min_move_delta {
+        if !min_delta.is_finite() || min_delta < 0.0 {
+            return Err(Error::new(Status::InvalidArg, format!("Invalid minimum move delta: {}", min_delta)));
+        }
+    }
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let id = state.next_callback_id + 1;
+    state.next_callback_id = id;
+    let tsfn: ThreadsafeFunction<DragEvent, ErrorStrategy::CalleeHandled> = isolate_callback(&env, &ctx, id, callback)?.create_threadsafe_function(state.callback_queue_size, |ctx| Ok(vec![ctx.value]))?;
+    state.drag_callbacks.insert(id, (DragSubscription::new(options), Arc::new(tsfn)));
+    drop(state);
+    sync_auto_monitor(&ctx, true)?;
+    Ok(id)
+}
+
+#[napi]
+pub fn remove_drag_event_listener(env: Env, id: u32) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let removed = ctx.lock()?.drag_callbacks.remove(&id).is_some();
+    sync_auto_monitor(&ctx, false)?;
+    Ok(removed)
+}
+
+// 注册只接收当前拖拽会话事件的回调，会话在 dragend/dragcancel 之后自动注销
+// 当前没有进行中的拖拽时不注册，返回 null
+#[napi(ts_args_type = "callback: (err: null | Error, event: DragEvent) => void")]
+pub fn on_current_drag(env: Env, callback: JsFunction) -> Result<Option<u32>> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let Some(session_id) = state.drag.drag_session_id else {
+        return Ok(None);
+    };
+    let id = state.next_callback_id + 1;
+    state.next_callback_id = id;
+    let tsfn: ThreadsafeFunction<DragEvent, ErrorStrategy::CalleeHandled> = isolate_callback(&env, &ctx, id, callback)?.create_threadsafe_function(state.callback_queue_size, |ctx| Ok(vec![ctx.value]))?;
+    state.session_drag_callbacks.insert(id, (session_id, Arc::new(tsfn)));
+    Ok(Some(id))
+}
+
+// 在会话结束之前提前注销，会话已结束时返回 false
+#[napi]
+pub fn remove_current_drag_listener(env: Env, id: u32) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let removed = ctx.lock()?.session_drag_callbacks.remove(&id).is_some();
+    sync_auto_monitor(&ctx, false)?;
+    Ok(removed)
+}
+
+// 设置标题栏/窗口边框拖拽的处理方式："off"（默认）、"classify" 或 "suppress"
+#[napi(ts_args_type = "mode: 'off' | 'classify' | 'suppress'")]
+pub fn set_window_chrome_drag_mode(env: Env, mode: String) -> Result<()> {
+    let mode = match mode.as_str() {
+        "off" => ChromeDragMode::Off,
+        "classify" => ChromeDragMode::Classify,
+        "suppress" => ChromeDragMode::Suppress,
+        _ => {
+            return Err(Error::new(
+                Status::InvalidArg,
+                format!("Unknown window chrome drag mode: {}", mode),
+            ))
+        }
+    };
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    state.chrome_drag_mode = mode;
+    Ok(())
+}
+
+// 开始监听或恢复时主按键已被按住的情况下，是否补记这次按下以便仍能检测到拖拽，默认关闭
+#[napi]
+pub fn set_seed_from_button_state(env: Env, enabled: bool) -> Result<()> {
+    let ctx = monitor_context(&env)?;
+    ctx.lock()?.seed_from_button_state = enabled;
+    Ok(())
+}
+
+// 忽略在本进程（Electron 主进程）窗口内按下的点击和拖拽，只监听应用之外的操作，默认关闭
+// 按下点的窗口按所属进程匹配；移动和滚轮事件不受影响
+#[napi]
+pub fn set_ignore_own_process_events(env: Env, enabled: bool) -> Result<()> {
+    let ctx = monitor_context(&env)?;
+    ctx.lock()?.ignore_own_process_events = enabled;
+    Ok(())
+}
+
+// 丢弃软件注入的事件（isSynthetic 为 true），只处理来自输入设备的操作，默认关闭
+// 被丢弃的事件不分发，也不参与拖拽检测
+#[napi]
+pub fn set_ignore_synthetic_events(env: Env, enabled: bool) -> Result<()> {
+    let ctx = monitor_context(&env)?;
+    ctx.lock()?.ignore_synthetic_events = enabled;
+    Ok(())
+}
+
+fn parse_button_numbering(numbering: &str) -> Result<ButtonNumbering> {
+    match numbering {
+        "native" => Ok(ButtonNumbering::Native),
+        "dom" => Ok(ButtonNumbering::Dom),
+        _ => Err(Error::new(
+            Status::InvalidArg,
+            format!("Unknown button numbering: {}", numbering),
+        )),
+    }
+}
+
+// 设置对外报告的按键编号方式："native"（默认，左/中/右 = 1/2/3）或 "dom"（左/中/右 = 0/1/2）
+// 对 MouseEvent、DragEvent 和 getInputState 一致生效
+#[napi(ts_args_type = "numbering: 'native' | 'dom'")]
+pub fn set_button_numbering(env: Env, numbering: String) -> Result<()> {
+    let numbering = parse_button_numbering(&numbering)?;
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    state.button_numbering = numbering;
+    Ok(())
+}
+
+// Wheel API functions
+#[napi(ts_args_type = "callback: (err: null | Error, event: WheelEvent) => void")]
+pub fn on_wheel_event(env: Env, callback: JsFunction) -> Result<u32> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let id = state.next_callback_id + 1;
+    state.next_callback_id = id;
+    let tsfn: ThreadsafeFunction<WheelEvent, ErrorStrategy::CalleeHandled> = isolate_callback(&env, &ctx, id, callback)?.create_threadsafe_function(state.callback_queue_size, |ctx| Ok(vec![ctx.value]))?;
+    state.wheel_callbacks.insert(id, Arc::new(tsfn));
+    drop(state);
+    sync_auto_monitor(&ctx, true)?;
+    Ok(id)
+}
+
+#[napi]
+pub fn remove_wheel_event_listener(env: Env, id: u32) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let removed = ctx.lock()?.wheel_callbacks.remove(&id).is_some();
+    sync_auto_monitor(&ctx, false)?;
+    Ok(removed)
+}
+
+// 设置滚轮事件的最小分发间隔（毫秒），间隔内的位移会被合并，0 表示不节流
+#[napi]
+pub fn set_wheel_throttle(env: Env, throttle_ms: u32) -> Result<()> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    state.wheel_throttle.throttle_ms = throttle_ms;
+    Ok(())
+}
+
+fn check_drag_threshold(threshold: f64) -> Result<()> {
+    if !threshold.is_finite() || threshold < 0.0 {
+        return Err(Error::new(Status::InvalidArg, format!("Invalid drag threshold: {}", threshold)));
+    }
+    Ok(())
+}
+
+// 设置拖拽阈值（像素）：overrides 按按下点所在的显示器覆盖全局阈值，未覆盖的显示器使用 thresholdPx
+// 对下一次按下生效，进行中的拖拽不受影响
+#[napi]
+pub fn set_drag_threshold(env: Env, threshold_px: f64, overrides: Option<Vec<DragThresholdOverride>>) -> Result<()> {
+    let overrides = overrides.unwrap_or_default();
+    for threshold in std::iter::once(threshold_px).chain(overrides.iter().map(|o| o.threshold_px)) {
+        check_drag_threshold(threshold)?;
+    }
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    state.drag.drag_threshold = threshold_px;
+    state.threshold_overrides = overrides.into_iter().map(|o| (o.display_id, o.threshold_px)).collect();
+    Ok(())
+}
+
+// 记录拖拽轨迹并附加到 dragend 的 path 上，无需把每个 dragmove 都交给 JS 处理
+// 与上一个记录点距离小于 minDistancePx 的移动被丢弃（默认 0，记录所有移动）；对下一次拖拽生效
+#[napi]
+pub fn set_drag_path_recording(env: Env, enabled: bool, min_distance_px: Option<f64>) -> Result<()> {
+    let min_distance = min_distance_px.unwrap_or(0.0);
+    if !min_distance.is_finite() || min_distance < 0.0 {
+        return Err(Error::new(Status::InvalidArg, format!("Invalid drag path min distance: {}", min_distance)));
+    }
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    state.drag_path.enabled = enabled;
+    state.drag_path.min_distance = min_distance;
+    Ok(())
+}
+
+// Unified input stream API functions
+// 鼠标事件与拖拽事件都由同一个监听线程按顺序分发，因此这里收到的事件保持全局顺序
+#[napi(ts_args_type = "callback: (err: null | Error, event: InputEvent) => void")]
+pub fn on_input_event(env: Env, callback: JsFunction) -> Result<u32> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let id = state.next_callback_id + 1;
+    state.next_callback_id = id;
+    let tsfn: ThreadsafeFunction<InputEvent, ErrorStrategy::CalleeHandled> = isolate_callback(&env, &ctx, id, callback)?.create_threadsafe_function(state.callback_queue_size, |ctx| Ok(vec![ctx.value]))?;
+    state.input_callbacks.insert(id, Arc::new(tsfn));
+    drop(state);
+    sync_auto_monitor(&ctx, true)?;
+    Ok(id)
+}
+
+#[napi]
+pub fn remove_input_event_listener(env: Env, id: u32) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let removed = ctx.lock()?.input_callbacks.remove(&id).is_some();
+    sync_auto_monitor(&ctx, false)?;
+    Ok(removed)
+}
+
+// 注册错误回调：其他回调抛出的异常、回调调用失败、系统钩子启动失败都通过它报告
+// 有错误回调时，回调抛出的异常不再作为未捕获异常抛出；错误回调自身不做隔离
+#[napi(ts_args_type = "callback: (err: null | Error, error: MonitorError) => void")]
+pub fn on_monitor_error(env: Env, callback: JsFunction) -> Result<u32> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let id = state.next_callback_id + 1;
+    state.next_callback_id = id;
+    let tsfn: ThreadsafeFunction<MonitorError, ErrorStrategy::CalleeHandled> = callback.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+    state.error_callbacks.insert(id, tsfn);
+    Ok(id)
+}
+
+#[napi]
+pub fn remove_monitor_error_listener(env: Env, id: u32) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    Ok(state.error_callbacks.remove(&id).is_some())
+}
+
+// Unified monitoring functions
+// 开始监听或恢复时主按键已被按住，钩子收不到这次按下；启用 seed_from_button_state 时按系统按键状态补记，
+// 之后第一次移动的位置作为拖拽起点，移动超过阈值后照常产生 dragstart
+fn seed_held_button(state: &mut UnifiedMonitorState) {
+    if !state.seed_from_button_state || state.drag.mouse_pressed || !platform::primary_button_pressed() {
+        return;
+    }
+    state.drag.seed_press(1);
+    if !state.pressed_buttons.contains(&1) {
+        state.pressed_buttons.push(1);
+    }
+}
+
+// 钩子线程与分发线程之间的队列上限，约为 1000Hz 鼠标一秒的输入
+const DISPATCH_QUEUE_CAPACITY: usize = 1024;
+
+// 钩子线程异常退出后自动重启的退避时间：从 RESTART_BACKOFF_MIN 开始每次翻倍，最长 RESTART_BACKOFF_MAX；
+// 钩子稳定运行超过 RESTART_BACKOFF_MAX 之后重新从最短的开始
+const RESTART_BACKOFF_MIN: Duration = Duration::from_secs(1);
+const RESTART_BACKOFF_MAX: Duration = Duration::from_secs(30);
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+// 没有注册 onMonitorError 时仍然输出到 stderr
+fn report_or_log(ctx: &MonitorContext, kind: &str, message: String) {
+    let error = MonitorError {
+        kind: kind.to_string(),
+        callback_id: None,
+        message: message.clone(),
+        timestamp: now_timestamp(),
+    };
+    if !report_monitor_errors(ctx, vec![error]) {
+        log::error("monitor", message);
+    }
+}
+
+// 监听线程：启动分发线程，然后在单独的线程里运行系统钩子并等待它退出（watchdog）
+// 钩子线程 panic 或 listen() 返回时停止监听并报告 "monitor-stopped"，开启 autoRestart 时按退避时间重启钩子
+fn monitor_supervisor(ctx: MonitorContext, running: Arc<AtomicBool>, overflows: Arc<AtomicU32>, queue_depth: Arc<AtomicUsize>) {
+    // 系统钩子回调只负责入队，拖拽状态计算、加锁和回调分发都在分发线程上完成，
+    // 这样 JS 繁忙或锁竞争不会拖慢钩子（Windows 会移除响应过慢的低级钩子）。
+    // 队列有上限，分发线程跟不上时丢弃新事件并计数，钩子从不等待，积压的旧输入也不会滞后重放
+    let (sender, receiver) = mpsc::sync_channel::<(Event, f64)>(DISPATCH_QUEUE_CAPACITY);
+    let dispatcher_ctx = ctx.clone();
+    let dispatcher_running = running.clone();
+    let dispatcher_queue_depth = queue_depth.clone();
+    thread::spawn(move || {
+        let mut listener = unified_event_listener(dispatcher_ctx.clone());
+        // 所有发送端释放后分发线程结束；停止监听前已入队的事件直接丢弃
+        loop {
+            // 等待按住时间阈值时最多等到阈值到期，到期后即使没有新事件也要检查是否开始拖拽；
+            // 拖拽停在屏幕边缘时同样需要按时报告停留时间
+            let deadline = match dispatcher_ctx.state.lock() {
+                Ok(state) if dispatcher_running.load(Ordering::SeqCst) && !state.dispatch_suspended() => {
+                    [state.drag.hold_deadline(), screen_edge_deadline(&state)].into_iter().flatten().min()
+                }
+                _ => None,
+            };
+            let received = match deadline {
+                Some(deadline) => receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+                None => receiver.recv().map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+            };
+            if received.is_ok() {
+                dispatcher_queue_depth.fetch_sub(1, Ordering::Relaxed);
+            }
+            let result = match received {
+                Ok(_) | Err(mpsc::RecvTimeoutError::Timeout) if !dispatcher_running.load(Ordering::SeqCst) => continue,
+                Ok((event, monotonic_time_ns)) => panic::catch_unwind(AssertUnwindSafe(|| listener(event, monotonic_time_ns))),
+                Err(mpsc::RecvTimeoutError::Timeout) => panic::catch_unwind(AssertUnwindSafe(|| {
+                    check_drag_hold(&dispatcher_ctx);
+                    check_screen_edge(&dispatcher_ctx);
+                })),
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    dispatcher_queue_depth.store(0, Ordering::Relaxed);
+                    break;
+                }
+            };
+            // 单个事件的处理 panic 时分发线程不能退出，否则钩子仍在运行而事件再也不会被处理
+            if let Err(payload) = result {
+                dispatcher_ctx.state.clear_poison();
+                report_or_log(&dispatcher_ctx, "listener", format!("Event processing panicked: {}", panic_message(&*payload)));
+            }
+        }
+    });
+
+    let mut backoff = RESTART_BACKOFF_MIN;
+    loop {
+        let hook_sender = sender.clone();
+        let hook_running = running.clone();
+        let hook_overflows = overflows.clone();
+        let hook_queue_depth = queue_depth.clone();
+        let started = Instant::now();
+        let hook = thread::spawn(move || {
+            listen(move |event: Event| {
+                if !hook_running.load(Ordering::SeqCst) {
+                    return;
+                }
+                // 入队之前计数，分发线程取出事件时不会先于这里减到负数
+                hook_queue_depth.fetch_add(1, Ordering::Relaxed);
+                if let Err(error) = hook_sender.try_send((event, monotonic_now_ns())) {
+                    hook_queue_depth.fetch_sub(1, Ordering::Relaxed);
+                    if let TrySendError::Full(_) = error {
+                        hook_overflows.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            })
+        });
+        let reason = match hook.join() {
+            Ok(Ok(())) => "Input listener exited".to_string(),
+            Ok(Err(error)) => {
+                let message = format!("Error listening to input events: {:?}", error);
+                report_or_log(&ctx, "listener", message.clone());
+                message
+            }
+            Err(payload) => format!("Input listener panicked: {}", panic_message(&*payload)),
+        };
+
+        // 钩子已经不在了，进行中的拖拽无法再正常结束
+        let _ = cancel_active_drag(&ctx, DragEndReason::MonitorStopped);
+        let Ok(mut state) = ctx.state.lock() else {
+            return;
+        };
+        let was_monitoring = state.is_monitoring;
+        running.store(false, Ordering::SeqCst);
+        state.is_monitoring = false;
+        state.restart_pending = was_monitoring && state.auto_restart;
+        if !state.restart_pending {
+            // 让下一次 startMouseMonitor 启动新的监听线程，而不是复用这个即将结束的线程
+            state.monitor_handle = None;
+        }
+        let restart = state.restart_pending;
+        drop(state);
+
+        if was_monitoring {
+            report_or_log(&ctx, "monitor-stopped", reason);
+        }
+        if !restart {
+            return;
+        }
+
+        if started.elapsed() > RESTART_BACKOFF_MAX {
+            backoff = RESTART_BACKOFF_MIN;
+        }
+        log::info("monitor", format!("Restarting input listener in {} ms", backoff.as_millis()));
+        thread::sleep(backoff);
+        backoff = (backoff * 2).min(RESTART_BACKOFF_MAX);
+
+        // 退避期间调用了 stopMouseMonitor 时放弃重启
+        let Ok(mut state) = ctx.state.lock() else {
+            return;
+        };
+        if !state.restart_pending {
+            state.monitor_handle = None;
+            return;
+        }
+        state.restart_pending = false;
+        seed_held_button(&mut state);
+        running.store(true, Ordering::SeqCst);
+        state.is_monitoring = true;
+    }
+}
+
+fn start_unified_monitor(ctx: &MonitorContext) -> Result<()> {
+    let mut state = ctx.lock()?;
+
+    if state.is_monitoring {
+        return Ok(());
+    }
+
+    seed_held_button(&mut state);
+    state.listener_running.store(true, Ordering::SeqCst);
+    state.is_monitoring = true;
+
+    // 之前停止时保留下来的监听线程仍在运行，直接复用
+    if state.monitor_handle.as_ref().is_some_and(|handle| !handle.is_finished()) {
+        log::debug("monitor", "Monitoring resumed on the existing listener thread");
+        return Ok(());
+    }
+    log::debug("monitor", "Starting input listener thread");
+
+    let listener_ctx = ctx.clone();
+    let running = state.listener_running.clone();
+    let overflows = state.callback_overflows.clone();
+    let queue_depth = state.dispatch_queue_depth.clone();
+    let handle = thread::spawn(move || monitor_supervisor(listener_ctx, running, overflows, queue_depth));
+
+    state.monitor_handle = Some(handle);
+    Ok(())
+}
+
+fn stop_unified_monitor(ctx: &MonitorContext) -> Result<()> {
+    let mut state = ctx.lock()?;
+    state.restart_pending = false;
+
+    if !state.is_monitoring {
+        return Ok(());
+    }
+
+    // rdev::listen 不会返回，不能 join；监听线程保留，下次 start 时复用
+    state.listener_running.store(false, Ordering::SeqCst);
+    state.is_monitoring = false;
+    log::debug("monitor", "Monitoring stopped");
+    Ok(())
+}
+
+// 依赖监听线程的回调数量（鼠标、拖拽、滚轮、统一事件流、屏幕区域）
+fn monitor_listener_count(state: &UnifiedMonitorState) -> usize {
+    state.mouse_callbacks.len()
+        + state.drag_callbacks.len()
+        + state.session_drag_callbacks.len()
+        + state.wheel_callbacks.len()
+        + state.input_callbacks.len()
+        + state.region_callbacks.len()
+        + state.screen_edge_callbacks.len()
+}
+
+// 自动启停模式下，根据是否还有依赖监听线程的回调开始或停止监听；
+// 只开启 autoStart 时，注册回调（listener_added）会在未监听时开始监听，但不会自动停止
+fn sync_auto_monitor(ctx: &MonitorContext, listener_added: bool) -> Result<()> {
+    let (listeners, is_monitoring) = {
+        let state = ctx.lock()?;
+        if !(state.auto_monitor || (state.auto_start && listener_added)) {
+            return Ok(());
+        }
+        (monitor_listener_count(&state), state.is_monitoring)
+    };
+    // 刚注册了回调时 listeners 一定大于 0，因此只开启 autoStart 时不会走到停止的分支
+    if listeners > 0 && !is_monitoring {
+        start_unified_monitor(ctx)
+    } else if listeners == 0 && is_monitoring {
+        cancel_active_drag(ctx, DragEndReason::Programmatic)?;
+        stop_unified_monitor(ctx)
+    } else {
+        Ok(())
+    }
+}
+
+// 修改监听配置，只更新传入的字段
+#[napi]
+pub fn configure(env: Env, config: MonitorConfig) -> Result<()> {
+    let call_mode = match config.call_mode.as_deref() {
+        None => None,
+        Some("blocking") => Some(ThreadsafeFunctionCallMode::Blocking),
+        Some("nonblocking") => Some(ThreadsafeFunctionCallMode::NonBlocking),
+        Some(other) => return Err(Error::new(Status::InvalidArg, format!("Unknown call mode: {}", other))),
+    };
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    if let Some(auto_start) = config.auto_start {
+        state.auto_start = auto_start;
+    }
+    if let Some(call_mode) = call_mode {
+        state.callback_call_mode = call_mode;
+    }
+    if let Some(auto_restart) = config.auto_restart {
+        state.auto_restart = auto_restart;
+    }
+    if let Some(max_queue_size) = config.max_queue_size {
+        state.callback_queue_size = max_queue_size as usize;
+    }
+    if let Some(drag_buttons) = config.drag_buttons {
+        let numbering = state.button_numbering;
+        state.drag_buttons = (!drag_buttons.is_empty())
+            .then(|| drag_buttons.iter().map(|button| numbering.unmap(*button)).collect());
+    }
+    Ok(())
+}
+
+// nonblocking 模式下因回调队列已满而丢弃的事件总数
+#[napi]
+pub fn get_callback_overflow_count(env: Env) -> Result<u32> {
+    Ok(monitor_context(&env)?.lock()?.callback_overflows.load(Ordering::Relaxed))
+}
+
+// 在开始监听之前一次性配置整个子系统，只更新传入的字段；所有字段校验通过后才会生效
+// 监听进行中调用会失败，需要先 stopMouseMonitor
+#[napi]
+pub fn initialize(env: Env, options: InitializeOptions) -> Result<()> {
+    if let Some(threshold) = options.drag_threshold_px {
+        check_drag_threshold(threshold)?;
+    }
+    let numbering = options.buttons.as_deref().map(parse_button_numbering).transpose()?;
+    let mapping = options.coordinate_space.as_deref().map(coordinate_mapping).transpose()?;
+    let threshold_mode = match options.drag_threshold_mode.as_deref() {
+        None => None,
+        Some("either") => Some(ThresholdMode::Either),
+        Some("both") => Some(ThresholdMode::Both),
+        Some(other) => return Err(Error::new(Status::InvalidArg, format!("Unknown drag threshold mode: {}", other))),
+    };
+
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    if state.is_monitoring {
+        return Err(Error::new(
+            Status::GenericFailure,
+            "initialize must be called before the monitor is started".to_string(),
+        ));
+    }
+    if let Some(threshold) = options.drag_threshold_px {
+        state.drag.drag_threshold = threshold;
+    }
+    if let Some(threshold_ms) = options.drag_threshold_ms {
+        state.drag.drag_threshold_ms = threshold_ms;
+    }
+    if let Some(threshold_mode) = threshold_mode {
+        state.drag.threshold_mode = threshold_mode;
+    }
+    if let Some(throttle_ms) = options.throttle_ms {
+        state.wheel_throttle.throttle_ms = throttle_ms;
+    }
+    if let Some(mapping) = mapping {
+        state.coordinates = mapping;
+    }
+    if let Some(numbering) = numbering {
+        state.button_numbering = numbering;
+    }
+    if let Some(auto_start) = options.auto_start {
+        state.auto_start = auto_start;
+    }
+    Ok(())
+}
+
+// 开启或关闭自动启停；开启时立即按当前回调数量开始或停止监听
+#[napi]
+pub fn set_auto_monitor(env: Env, enabled: bool) -> Result<()> {
+    let ctx = monitor_context(&env)?;
+    ctx.lock()?.auto_monitor = enabled;
+    sync_auto_monitor(&ctx, false)
+}
+
+// 移除所有鼠标事件回调，返回移除的数量
+#[napi]
+pub fn remove_all_mouse_listeners(env: Env) -> Result<u32> {
+    let ctx = monitor_context(&env)?;
+    let removed = {
+        let mut state = ctx.lock()?;
+        let removed = state.mouse_callbacks.len();
+        state.mouse_callbacks.clear();
+        removed
+    };
+    sync_auto_monitor(&ctx, false)?;
+    Ok(removed as u32)
+}
+
+// 移除所有拖拽事件回调（包括 onCurrentDrag 注册的会话回调），返回移除的数量
+#[napi]
+pub fn remove_all_drag_listeners(env: Env) -> Result<u32> {
+    let ctx = monitor_context(&env)?;
+    let removed = {
+        let mut state = ctx.lock()?;
+        let removed = state.drag_callbacks.len() + state.session_drag_callbacks.len();
+        state.drag_callbacks.clear();
+        state.session_drag_callbacks.clear();
+        removed
+    };
+    sync_auto_monitor(&ctx, false)?;
+    Ok(removed as u32)
+}
+
+#[napi]
+pub fn is_monitoring(env: Env) -> Result<bool> {
+    Ok(monitor_context(&env)?.lock()?.is_monitoring)
+}
+
+#[napi]
+pub fn get_input_state(env: Env) -> Result<InputState> {
+    let ctx = monitor_context(&env)?;
+    let current_position = current_position(&ctx);
+    let state = ctx.lock()?;
+    Ok(input_state(&state, current_position))
+}
+
+fn current_position(ctx: &MonitorContext) -> Option<Point> {
+    ctx.last_position
+        .lock()
+        .ok()
+        .and_then(|p| *p)
+        .map(|(x, y)| Point { x, y })
+}
+
+fn input_state(state: &UnifiedMonitorState, current_position: Option<Point>) -> InputState {
+    InputState {
+        mouse_pressed: state.drag.mouse_pressed,
+        pressed_buttons: state.pressed_buttons.iter().map(|b| state.button_numbering.map(*b)).collect(),
+        is_dragging: state.drag.is_dragging,
+        drag_start: state.drag.drag_start_position.map(|(x, y)| Point { x, y }),
+        drag_session_id: state.drag.drag_session_id,
+        current_position,
+    }
+}
+
+// 监听状态、回调数量、拖拽状态、队列配置、权限和最近一次错误的快照
+#[napi]
+pub fn get_diagnostics(env: Env) -> Result<Diagnostics> {
+    let ctx = monitor_context(&env)?;
+    let current_position = current_position(&ctx);
+    let log_callbacks = ctx.log_callbacks.lock().map(|callbacks| callbacks.len() as u32).unwrap_or(0);
+    let state = ctx.lock()?;
+    let watches = [
+        ("external-drag", state.external_drag_watch.is_some()),
+        ("theme", state.theme_watch.is_some()),
+        ("active-app", state.active_app_watch.is_some()),
+        ("gesture", state.gesture_watch.is_some()),
+        ("clipboard", state.clipboard_watch.is_some()),
+    ];
+    Ok(Diagnostics {
+        is_monitoring: state.is_monitoring,
+        is_paused: state.is_paused,
+        app_active: state.app_active,
+        listener_thread_alive: state.monitor_handle.as_ref().is_some_and(|handle| !handle.is_finished()),
+        restart_pending: state.restart_pending,
+        auto_monitor: state.auto_monitor,
+        callbacks: CallbackCounts {
+            mouse: state.mouse_callbacks.len() as u32,
+            drag: state.drag_callbacks.len() as u32,
+            current_drag: state.session_drag_callbacks.len() as u32,
+            input: state.input_callbacks.len() as u32,
+            wheel: state.wheel_callbacks.len() as u32,
+            external_drag: state.external_drag_callbacks.len() as u32,
+            region: state.region_callbacks.len() as u32,
+            screen_edge: state.screen_edge_callbacks.len() as u32,
+            theme: state.theme_callbacks.len() as u32,
+            gesture: state.gesture_callbacks.len() as u32,
+            clipboard: state.clipboard_callbacks.len() as u32,
+            drop_target: state.drop_target_callbacks.len() as u32,
+            error: state.error_callbacks.len() as u32,
+            log: log_callbacks,
+        },
+        input_state: input_state(&state, current_position),
+        call_mode: match state.callback_call_mode {
+            ThreadsafeFunctionCallMode::Blocking => "blocking",
+            ThreadsafeFunctionCallMode::NonBlocking => "nonblocking",
+        }
+        .to_string(),
+        max_queue_size: state.callback_queue_size as u32,
+        callback_overflows: state.callback_overflows.load(Ordering::Relaxed),
+        dispatch_queue_depth: state.dispatch_queue_depth.load(Ordering::Relaxed) as u32,
+        watches: watches.iter().filter(|(_, active)| *active).map(|(name, _)| name.to_string()).collect(),
+        drop_targets: state.drop_targets.len() as u32,
+        holds_mouse_grab: state.holds_mouse_grab,
+        input_permission: platform::input_permission_granted(),
+        last_error: state.last_error.clone(),
+    })
+}
+
+// 会话元数据的大小限制，避免每个拖拽事件携带过大的载荷
+const MAX_ANNOTATIONS: usize = 16;
+const MAX_ANNOTATION_KEY_LEN: usize = 64;
+const MAX_ANNOTATION_VALUE_LEN: usize = 1024;
+
+// 为指定的拖拽会话附加元数据，之后该会话的 dragmove/dragend 事件会带上这些数据
+// 会话已结束或不是当前会话时返回 false
+#[napi]
+pub fn annotate_drag(env: Env, session_id: u32, key: String, value: String) -> Result<bool> {
+    if key.len() > MAX_ANNOTATION_KEY_LEN || value.len() > MAX_ANNOTATION_VALUE_LEN {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!(
+                "Drag annotation key must be at most {} bytes and value at most {} bytes",
+                MAX_ANNOTATION_KEY_LEN, MAX_ANNOTATION_VALUE_LEN
+            ),
+        ));
+    }
+
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    if state.drag.drag_session_id != Some(session_id) {
+        return Ok(false);
+    }
+    if !state.drag_annotations.contains_key(&key) && state.drag_annotations.len() >= MAX_ANNOTATIONS {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!("A drag session can carry at most {} annotations", MAX_ANNOTATIONS),
+        ));
+    }
+    state.drag_annotations.insert(key, value);
+    Ok(true)
+}
+
+// 直接向系统查询当前光标位置，不依赖监听钩子，未开始监听时也可使用
+#[napi]
+pub fn get_cursor_position(env: Env) -> Result<CursorPosition> {
+    let ctx = monitor_context(&env)?;
+    let (x, y) = platform::cursor_position()
+        .ok_or_else(|| Error::new(Status::GenericFailure, "Failed to query cursor position"))?;
+    let display_id = platform::display_id_at(x, y);
+    let (x, y) = to_reference_coordinates(&ctx, (x, y));
+    Ok(CursorPosition { x, y, display_id })
+}
+
+// 主显示器尺寸，单位与事件坐标一致，可直接用于换算事件坐标的相对位置
+#[napi]
+pub fn get_primary_display_size() -> Result<DisplaySize> {
+    let (width, height) = platform::primary_display_size()
+        .ok_or_else(|| Error::new(Status::GenericFailure, "Failed to query primary display size"))?;
+    Ok(DisplaySize { width, height })
+}
+
+// 列出所有显示器，用于把事件坐标对应到具体屏幕
+#[napi]
+pub fn get_displays(env: Env) -> Result<Vec<Display>> {
+    let ctx = monitor_context(&env)?;
+    let state = ctx.lock()?;
+    let (origin_x, origin_y) = state.reference_origin;
+    let to_rectangle = |rect: platform::Rect| {
+        let rect = state.coordinates.rect_to_space(rect);
+        Rectangle {
+            x: rect.x - origin_x,
+            y: rect.y - origin_y,
+            width: rect.width,
+            height: rect.height,
+        }
+    };
+    Ok(platform::displays()
+        .into_iter()
+        .map(|display| Display {
+            id: display.id,
+            bounds: to_rectangle(display.bounds),
+            work_area: to_rectangle(display.work_area),
+            scale_factor: display.scale_factor,
+            primary: display.primary,
+        })
+        .collect())
+}
+
+// 窗口句柄 Buffer 按本机字节序保存指针大小的值（NSView*、HWND 或 X11 窗口 ID）
+fn native_handle(buffer: &Buffer) -> Result<usize> {
+    match buffer.len() {
+        8 => Ok(u64::from_ne_bytes(buffer[..].try_into().unwrap()) as usize),
+        4 => Ok(u32::from_ne_bytes(buffer[..].try_into().unwrap()) as usize),
+        length => Err(Error::new(Status::InvalidArg, format!("Invalid window handle length: {}", length))),
+    }
+}
+
+// 窗口的外框，与事件坐标处于同一坐标空间，可直接用于 addScreenRegion、addIgnoreRegion 等；
+// 找不到窗口时返回 null
+#[napi]
+pub fn get_window_bounds(env: Env, query: WindowQuery) -> Result<Option<Rectangle>> {
+    let bounds = match (query.pid, &query.native_handle) {
+        (Some(pid), None) => platform::process_window_bounds(pid),
+        (None, Some(buffer)) => platform::window_bounds(native_handle(buffer)?),
+        _ => return Err(Error::new(Status::InvalidArg, "Exactly one of pid and nativeHandle is required")),
+    };
+    let ctx = monitor_context(&env)?;
+    let state = ctx.lock()?;
+    let (origin_x, origin_y) = state.reference_origin;
+    Ok(bounds.map(|rect| {
+        let rect = state.coordinates.rect_to_space(rect);
+        Rectangle {
+            x: rect.x - origin_x,
+            y: rect.y - origin_y,
+            width: rect.width,
+            height: rect.height,
+        }
+    }))
+}
+
+#[napi]
+pub fn get_capabilities() -> Capabilities {
+    Capabilities {
+        grab: cfg!(feature = "grab"),
+        external_drag_watch: cfg!(target_os = "macos"),
+        window_region: cfg!(any(target_os = "macos", target_os = "windows")),
+        file_promise_drag: cfg!(all(feature = "async-runtime", any(target_os = "macos", target_os = "windows"))),
+        gestures: cfg!(target_os = "macos"),
+        drop_target: cfg!(any(target_os = "macos", target_os = "windows")),
+    }
+}
+
+// 设置参考显示器，之后所有事件坐标、光标位置和外部拖拽区域都以该显示器左上角为原点
+// 传入 null 恢复为系统主显示器；显示器布局变化后需要重新设置
+#[napi]
+pub fn set_reference_display(env: Env, display_id: Option<u32>) -> Result<()> {
+    let origin = match display_id {
+        Some(id) => platform::display_origin(id)
+            .ok_or_else(|| Error::new(Status::InvalidArg, format!("Unknown display id: {}", id)))?,
+        None => (0.0, 0.0),
+    };
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    state.reference_display = display_id;
+    state.reference_origin = origin;
+    Ok(())
+}
+
+#[napi]
+pub fn get_reference_display(env: Env) -> Result<Option<u32>> {
+    Ok(monitor_context(&env)?.lock()?.reference_display)
+}
+
+fn coordinate_mapping(space: &str) -> Result<CoordinateMapping> {
+    let space = match space {
+        "logical" => CoordinateSpace::Logical,
+        "physical" => CoordinateSpace::Physical,
+        _ => {
+            return Err(Error::new(
+                Status::InvalidArg,
+                format!("Unknown coordinate space: {}", space),
+            ))
+        }
+    };
+    let displays = if space == CoordinateSpace::NATIVE { Vec::new() } else { platform::displays() };
+    Ok(CoordinateMapping::new(space, displays))
+}
+
+// 设置对外报告的坐标空间："logical"（点）或 "physical"（像素），默认使用系统坐标空间
+// （macOS 为逻辑坐标，Windows/Linux 为物理坐标）；换算使用调用时的显示器布局，布局变化后需要重新设置
+#[napi(ts_args_type = "space: 'logical' | 'physical'")]
+pub fn set_coordinate_space(env: Env, space: String) -> Result<()> {
+    let mapping = coordinate_mapping(&space)?;
+    let ctx = monitor_context(&env)?;
+    ctx.lock()?.coordinates = mapping;
+    Ok(())
+}
+
+#[napi(ts_return_type = "'logical' | 'physical'")]
+pub fn get_coordinate_space(env: Env) -> Result<String> {
+    let space = match monitor_context(&env)?.lock()?.coordinates.space {
+        CoordinateSpace::Logical => "logical",
+        CoordinateSpace::Physical => "physical",
+    };
+    Ok(space.to_string())
+}
+
+// 暂停分发：系统钩子保持安装，避免代价高昂的 stop/start 循环
+// 暂停时进行中的拖拽会以 dragcancel 结束，恢复后需要重新按下鼠标才会产生新的拖拽
+#[napi]
+pub fn pause_monitoring(env: Env) -> Result<()> {
+    let ctx = monitor_context(&env)?;
+    cancel_active_drag(&ctx, DragEndReason::Programmatic)?;
+    ctx.lock()?.is_paused = true;
+    Ok(())
+}
+
+#[napi]
+pub fn resume_monitoring(env: Env) -> Result<()> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    state.is_paused = false;
+    state.wheel_throttle.clear();
+    seed_held_button(&mut state);
+    Ok(())
+}
+
+#[napi]
+pub fn is_paused(env: Env) -> Result<bool> {
+    Ok(monitor_context(&env)?.lock()?.is_paused)
+}
+
+// 检查拖拽超时的轮询间隔
+const DRAG_TIMEOUT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+fn drag_timeout_watch_loop(ctx: MonitorContext, running: Arc<AtomicBool>) {
+    while running.load(Ordering::SeqCst) {
+        thread::sleep(DRAG_TIMEOUT_POLL_INTERVAL);
+        let expired = match ctx.state.lock() {
+            Ok(state) => state.drag.is_dragging
+                && state.drag_timeout.is_some_and(|timeout| state.last_input.elapsed() >= timeout),
+            Err(_) => return,
+        };
+        if expired {
+            let _ = cancel_active_drag(&ctx, DragEndReason::Timeout);
+        }
+    }
+}
+
+// 设置拖拽超时（毫秒）：拖拽期间超过这么久没有收到鼠标事件时以 dragcancel 结束拖拽，0 表示不限制
+// 用于系统钩子丢失松开事件的情况（例如在 Windows 上于管理员权限窗口内松开）
+#[napi]
+pub fn set_drag_timeout(env: Env, timeout_ms: u32) -> Result<()> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    if timeout_ms == 0 {
+        state.drag_timeout = None;
+        if let Some(running) = state.drag_timeout_watch.take() {
+            running.store(false, Ordering::SeqCst);
+        }
+        return Ok(());
+    }
+
+    state.drag_timeout = Some(Duration::from_millis(timeout_ms as u64));
+    if state.drag_timeout_watch.is_none() {
+        let running = Arc::new(AtomicBool::new(true));
+        state.drag_timeout_watch = Some(running.clone());
+        let watch_ctx = ctx.clone();
+        thread::spawn(move || drag_timeout_watch_loop(watch_ctx, running));
+    }
+    Ok(())
+}
+
+// 返回所有对外类型（事件载荷、状态快照、配置）的 JSON Schema，直接由 Rust 结构体生成，
+// 字段名与 JS 侧一致（camelCase），供校验器和其他语言的代码生成器使用
+#[napi]
+pub fn get_schema() -> Result<String> {
+    let mut generator = schemars::gen::SchemaSettings::draft07().into_generator();
+    generator.subschema_for::<MouseEvent>();
+    generator.subschema_for::<DragEvent>();
+    generator.subschema_for::<WheelEvent>();
+    generator.subschema_for::<InputEvent>();
+    generator.subschema_for::<ExternalDragEvent>();
+    generator.subschema_for::<RegionEvent>();
+    generator.subschema_for::<GestureEvent>();
+    generator.subschema_for::<ClipboardEvent>();
+    generator.subschema_for::<DropTargetEvent>();
+    generator.subschema_for::<LogRecord>();
+    generator.subschema_for::<ScreenRegionOptions>();
+    generator.subschema_for::<ScreenEdgeOptions>();
+    generator.subschema_for::<ScreenEdgeEvent>();
+    generator.subschema_for::<InputState>();
+    generator.subschema_for::<CursorPosition>();
+    generator.subschema_for::<DisplaySize>();
+    generator.subschema_for::<Display>();
+    generator.subschema_for::<Capabilities>();
+    generator.subschema_for::<SystemTheme>();
+    generator.subschema_for::<ExternalDragZone>();
+    generator.subschema_for::<NativeFileDragOptions>();
+    generator.subschema_for::<NormalizePathOptions>();
+    generator.subschema_for::<DragThresholdOverride>();
+    generator.subschema_for::<DragListenerOptions>();
+    generator.subschema_for::<ActiveAppFilter>();
+    generator.subschema_for::<FrontmostApplication>();
+    generator.subschema_for::<PromisedFile>();
+    generator.subschema_for::<FilePromiseRequest>();
+    generator.subschema_for::<DragImageOptions>();
+    generator.subschema_for::<ScreenColor>();
+    generator.subschema_for::<ScreenRegion>();
+    generator.subschema_for::<CaptureFormat>();
+    generator.subschema_for::<MonitorError>();
+    generator.subschema_for::<MonitorConfig>();
+    generator.subschema_for::<InitializeOptions>();
+
+    let schema = serde_json::json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "electron-dragfile-plugin",
+        "definitions": generator.definitions(),
+    });
+    serde_json::to_string_pretty(&schema).map_err(|e| Error::new(Status::GenericFailure, format!("Failed to serialize schema: {}", e)))
+}
+
+// 把 InputEvent 编码为紧凑的二进制格式（布局见 codec.rs），便于跨进程转发
+#[napi]
+pub fn serialize_event(event: InputEvent) -> Result<Buffer> {
+    codec::encode(&event)
+        .map(Buffer::from)
+        .map_err(|message| Error::new(Status::InvalidArg, message))
+}
+
+#[napi]
+pub fn deserialize_event(buffer: Buffer) -> Result<InputEvent> {
+    codec::decode(&buffer).map_err(|message| Error::new(Status::InvalidArg, message))
+}
+
+// endregion
+
+// region: DragMonitor (事件发射器风格的封装)
+
+// DragMonitor.on 支持的事件名，每种对应一组模块级的 onXxx/removeXxxListener
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MonitorEventName {
+    Mouse,
+    Drag,
+    Wheel,
+    Input,
+    Region,
+    ScreenEdge,
+    Error,
+}
+
+impl MonitorEventName {
+    fn parse(name: &str) -> Result<Self> {
+        match name {
+            "mouse" => Ok(MonitorEventName::Mouse),
+            "drag" => Ok(MonitorEventName::Drag),
+            "wheel" => Ok(MonitorEventName::Wheel),
+            "input" => Ok(MonitorEventName::Input),
+            "region" => Ok(MonitorEventName::Region),
+            "screen-edge" => Ok(MonitorEventName::ScreenEdge),
+            "error" => Ok(MonitorEventName::Error),
+            _ => Err(Error::new(Status::InvalidArg, format!("Unknown event name: {}", name))),
+        }
+    }
+
+    // 只从状态中移除回调，返回回调是否存在
+    fn remove_callback(self, state: &mut UnifiedMonitorState, id: u32) -> bool {
+        match self {
+            MonitorEventName::Mouse => state.mouse_callbacks.remove(&id).is_some(),
+            MonitorEventName::Drag => state.drag_callbacks.remove(&id).is_some(),
+            MonitorEventName::Wheel => state.wheel_callbacks.remove(&id).is_some(),
+            MonitorEventName::Input => state.input_callbacks.remove(&id).is_some(),
+            MonitorEventName::Region => state.region_callbacks.remove(&id).is_some(),
+            MonitorEventName::ScreenEdge => state.screen_edge_callbacks.remove(&id).is_some(),
+            MonitorEventName::Error => state.error_callbacks.remove(&id).is_some(),
+        }
+    }
+
+    // 与对应的 removeXxxListener 相同，不需要 Env
+    fn remove(self, ctx: &MonitorContext, id: u32) -> Result<bool> {
+        let removed = {
+            let mut state = ctx.lock()?;
+            self.remove_callback(&mut state, id)
+        };
+        // 错误回调不依赖监听线程，不参与自动启停
+        if self != MonitorEventName::Error {
+            sync_auto_monitor(ctx, false)?;
+        }
+        Ok(removed)
+    }
+}
+
+// 模块级函数的对象封装：监听状态与模块级函数共享（每个 Node 环境一份），对象只记录自己注册的回调。
+// 对象被回收时移除这些回调，但不停止监听
+#[napi]
+pub struct DragMonitor {
+    ctx: MonitorContext,
+    listeners: HashMap<u32, MonitorEventName>,
+}
+
+#[napi]
+impl DragMonitor {
+    #[napi(constructor)]
+    pub fn new(env: Env) -> Result<Self> {
+        Ok(DragMonitor {
+            ctx: monitor_context(&env)?,
+            listeners: HashMap::new(),
+        })
+    }
+
+    // options 只用于 "drag"，与 onDragEvent 的 options 相同
+    #[napi(
+        ts_args_type = "event: 'mouse' | 'drag' | 'wheel' | 'input' | 'region' | 'screen-edge' | 'error', callback: (err: null | Error, event: any) => void, options?: DragListenerOptions"
+    )]
+    pub fn on(&mut self, env: Env, event: String, callback: JsFunction, options: Option<DragListenerOptions>) -> Result<u32> {
+        let name = MonitorEventName::parse(&event)?;
+        if options.is_some() && name != MonitorEventName::Drag {
+            return Err(Error::new(Status::InvalidArg, format!("Listener options are not supported for '{}'", event)));
+        }
+        let id = match name {
+            MonitorEventName::Mouse => on_mouse_event(env, callback)?,
+            MonitorEventName::Drag => on_drag_event(env, callback, options)?,
+            MonitorEventName::Wheel => on_wheel_event(env, callback)?,
+            MonitorEventName::Input => on_input_event(env, callback)?,
+            MonitorEventName::Region => on_region_event(env, callback)?,
+            MonitorEventName::ScreenEdge => on_screen_edge_event(env, callback)?,
+            MonitorEventName::Error => on_monitor_error(env, callback)?,
+        };
+        self.listeners.insert(id, name);
+        Ok(id)
+    }
+
+    // 只能移除本对象注册的回调，其他 id 返回 false
+    #[napi]
+    pub fn off(&mut self, id: u32) -> Result<bool> {
+        match self.listeners.remove(&id) {
+            Some(name) => name.remove(&self.ctx, id),
+            None => Ok(false),
+        }
+    }
+
+    #[napi]
+    pub fn start(&mut self) -> Result<()> {
+        start_unified_monitor(&self.ctx)
+    }
+
+    #[napi]
+    pub fn stop(&mut self) -> Result<()> {
+        cancel_active_drag(&self.ctx, DragEndReason::Programmatic)?;
+        stop_unified_monitor(&self.ctx)
+    }
+}
+
+// 在 GC 的终结器中运行，此时环境可能正在销毁：只移除回调，不经过自动启停，
+// 因此既不会取消进行中的拖拽（那会向其他回调分发 dragcancel），也不会停止整个环境共享的监听
+impl Drop for DragMonitor {
+    fn drop(&mut self) {
+        let Ok(mut state) = self.ctx.state.lock() else {
+            return;
+        };
+        for (id, name) in self.listeners.drain() {
+            name.remove_callback(&mut state, id);
+        }
+    }
+}
+
+// endregion
+
+// region: Logging (插件日志)
+
+// 日志级别："off" | "error" | "warn"（默认）| "info" | "debug"，进程内所有环境共享
+#[napi(ts_args_type = "level: 'off' | 'error' | 'warn' | 'info' | 'debug'")]
+pub fn set_log_level(level: String) -> Result<()> {
+    let parsed = match level.as_str() {
+        "off" => None,
+        name => Some(log::Level::parse(name).ok_or_else(|| Error::new(Status::InvalidArg, format!("Unknown log level: {}", level)))?),
+    };
+    log::set_level(parsed);
+    Ok(())
+}
+
+// 另外把日志追加写入文件，传入 null 关闭；进程内所有环境共享
+#[napi]
+pub fn set_log_file(path: Option<String>) -> Result<()> {
+    log::set_file(path.as_deref().map(std::path::Path::new)).map_err(|message| Error::new(Status::GenericFailure, message))
+}
+
+// 注册日志回调；日志是进程级的，回调也会收到其他环境产生的日志，有回调接收的日志不再写到 stderr
+#[napi(ts_args_type = "callback: (err: null | Error, record: LogRecord) => void")]
+pub fn on_log(env: Env, callback: JsFunction) -> Result<u32> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let id = state.next_callback_id + 1;
+    state.next_callback_id = id;
+    let tsfn: ThreadsafeFunction<LogRecord, ErrorStrategy::CalleeHandled> = isolate_callback(&env, &ctx, id, callback)?.create_threadsafe_function(state.callback_queue_size, |ctx| Ok(vec![ctx.value]))?;
+    ctx.log_callbacks
+        .lock()
+        .map_err(|_| Error::new(Status::GenericFailure, "Failed to acquire log callback lock"))?
+        .insert(id, tsfn);
+
+    if state.log_sink.is_none() {
+        let log_callbacks = ctx.log_callbacks.clone();
+        state.log_sink = Some(log::add_sink(Arc::new(move |record| {
+            let Ok(callbacks) = log_callbacks.lock() else {
+                return false;
+            };
+            let log_record = LogRecord {
+                level: record.level.as_str().to_string(),
+                target: record.target.to_string(),
+                message: record.message.clone(),
+                timestamp: record.timestamp,
+            };
+            // 写日志的地方可能持有状态锁，这里不读取 callback_call_mode，失败也不再报告
+            for callback in callbacks.values() {
+                callback.call(Ok(log_record.clone()), ThreadsafeFunctionCallMode::NonBlocking);
+            }
+            !callbacks.is_empty()
+        })));
+    }
+    Ok(id)
+}
+
+#[napi]
+pub fn remove_log_listener(env: Env, id: u32) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let removed = ctx
+        .log_callbacks
+        .lock()
+        .map_err(|_| Error::new(Status::GenericFailure, "Failed to acquire log callback lock"))?
+        .remove(&id)
+        .is_some();
+    Ok(removed)
+}
+
+// endregion
+
+// region: Screen Regions (屏幕区域进入/离开)
+
+// 区域判断在监听线程上完成，JS 只在光标跨过区域边界时收到事件，不需要处理每一次移动
+
+// 根据光标位置和拖拽状态重新判断每个区域，返回需要分发的进入/离开事件
+fn update_screen_regions(state: &mut UnifiedMonitorState, x: f64, y: f64, timestamp: f64) -> Vec<RegionEvent> {
+    if state.screen_regions.is_empty() {
+        return Vec::new();
+    }
+    let dragging = state.drag.is_dragging;
+    let drag_session_id = state.drag.drag_session_id;
+    let mut events = Vec::new();
+    for region in &mut state.screen_regions {
+        let rect = &region.rect;
+        let inside = (dragging || !region.drag_only)
+            && x >= rect.x
+            && x < rect.x + rect.width
+            && y >= rect.y
+            && y < rect.y + rect.height;
+        if inside == region.inside {
+            continue;
+        }
+        region.inside = inside;
+        events.push(RegionEvent {
+            event_type: if inside { "region-enter" } else { "region-leave" }.to_string(),
+            region_id: region.id.clone(),
+            x,
+            y,
+            dragging,
+            drag_session_id,
+            timestamp,
+            platform: platform_name().to_string(),
+        });
+    }
+    events
+}
+
+fn trigger_region_events(ctx: &MonitorContext, region_events: Vec<RegionEvent>) {
+    if region_events.is_empty() {
+        return;
+    }
+    let mut calls = Vec::new();
+    if let Ok(state) = ctx.state.lock() {
+        for region_event in region_events {
+            dispatch(&state, &state.region_callbacks, &region_event, &mut calls);
+        }
+    }
+    deliver(ctx, calls);
+}
+
+// 注册（或替换同一 id 的）屏幕区域，坐标与事件坐标一致；光标下一次移动时开始判断
+#[napi]
+pub fn add_screen_region(env: Env, id: String, rect: Rectangle, options: Option<ScreenRegionOptions>) -> Result<()> {
+    if !(rect.width >= 0.0 && rect.height >= 0.0) {
+        return Err(Error::new(Status::InvalidArg, format!("Invalid screen region size: {}x{}", rect.width, rect.height)));
+    }
+    let drag_only = options.and_then(|options| options.drag_only).unwrap_or(false);
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    match state.screen_regions.iter_mut().find(|region| region.id == id) {
+        // 保留 inside，替换后仍在区域内时不会重复产生 region-enter
+        Some(region) => {
+            region.rect = rect;
+            region.drag_only = drag_only;
+        }
+        None => state.screen_regions.push(TrackedRegion {
+            id,
+            rect,
+            drag_only,
+            inside: false,
+        }),
+    }
+    Ok(())
+}
+
+// 添加排除区域，坐标与事件坐标一致：区域内的鼠标移动和滚轮事件不分发，
+// 在区域内按下的按键不产生拖拽，它的按下/松开也不分发；进行中的拖拽经过区域时只跳过 dragmove
+#[napi]
+pub fn add_ignore_region(env: Env, rect: Rectangle) -> Result<u32> {
+    if !(rect.width >= 0.0 && rect.height >= 0.0) {
+        return Err(Error::new(Status::InvalidArg, format!("Invalid ignore region size: {}x{}", rect.width, rect.height)));
+    }
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let id = state.next_ignore_region_id + 1;
+    state.next_ignore_region_id = id;
+    state.ignore_regions.push((id, rect));
+    Ok(id)
+}
+
+// 区域不存在时返回 false
+#[napi]
+pub fn remove_ignore_region(env: Env, id: u32) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let count = state.ignore_regions.len();
+    state.ignore_regions.retain(|(region_id, _)| *region_id != id);
+    Ok(state.ignore_regions.len() != count)
+}
+
+// 移除区域，不产生 region-leave；区域不存在时返回 false
+#[napi]
+pub fn remove_screen_region(env: Env, id: String) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let count = state.screen_regions.len();
+    state.screen_regions.retain(|region| region.id != id);
+    Ok(state.screen_regions.len() != count)
+}
+
+#[napi(ts_args_type = "callback: (err: null | Error, event: RegionEvent) => void")]
+pub fn on_region_event(env: Env, callback: JsFunction) -> Result<u32> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let id = state.next_callback_id + 1;
+    state.next_callback_id = id;
+    let tsfn: ThreadsafeFunction<RegionEvent, ErrorStrategy::CalleeHandled> = isolate_callback(&env, &ctx, id, callback)?.create_threadsafe_function(state.callback_queue_size, |ctx| Ok(vec![ctx.value]))?;
+    state.region_callbacks.insert(id, Arc::new(tsfn));
+    drop(state);
+    sync_auto_monitor(&ctx, true)?;
+    Ok(id)
+}
+
+#[napi]
+pub fn remove_region_event_listener(env: Env, id: u32) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let removed = ctx.lock()?.region_callbacks.remove(&id).is_some();
+    sync_auto_monitor(&ctx, false)?;
+    Ok(removed)
+}
+
+// endregion
+
+// region: Screen Edges (拖拽到达屏幕边缘)
+
+// 与屏幕区域一样在分发线程上判断。停在边缘时光标不再移动、不会产生事件，
+// 因此重复报告由分发线程的计时器驱动
+
+const DEFAULT_SCREEN_EDGE_THRESHOLD: f64 = 2.0;
+const DEFAULT_SCREEN_EDGE_REPEAT_MS: u32 = 250;
+
+// 点所在显示器的外侧边缘及显示器 ID；与另一台显示器相邻的一侧不算边缘，拖拽会直接越过它
+fn screen_edge_at(detection: &ScreenEdgeDetection, x: f64, y: f64) -> Option<(&'static str, u32)> {
+    let inside = |rect: &Rectangle, x: f64, y: f64| x >= rect.x && x < rect.x + rect.width && y >= rect.y && y < rect.y + rect.height;
+    let on_any_display = |x: f64, y: f64| detection.displays.iter().any(|(_, rect)| inside(rect, x, y));
+    let (display_id, rect) = detection.displays.iter().find(|(_, rect)| inside(rect, x, y))?;
+    let threshold = detection.threshold;
+    let left = x < rect.x + threshold && !on_any_display(rect.x - 1.0, y);
+    let right = x >= rect.x + rect.width - threshold && !on_any_display(rect.x + rect.width, y);
+    let top = y < rect.y + threshold && !on_any_display(x, rect.y - 1.0);
+    let bottom = y >= rect.y + rect.height - threshold && !on_any_display(x, rect.y + rect.height);
+    let edge = match (top, bottom, left, right) {
+        (true, _, true, _) => "top-left",
+        (true, _, _, true) => "top-right",
+        (_, true, true, _) => "bottom-left",
+        (_, true, _, true) => "bottom-right",
+        (true, _, _, _) => "top",
+        (_, true, _, _) => "bottom",
+        (_, _, true, _) => "left",
+        (_, _, _, true) => "right",
+        _ => return None,
+    };
+    Some((edge, *display_id))
+}
+
+// 下一次需要检查边缘的时间：停留中为下一次重复报告，拖拽已经结束（例如被取消）时立即检查以报告离开
+fn screen_edge_deadline(state: &UnifiedMonitorState) -> Option<Instant> {
+    let dwell = state.screen_edges.as_ref()?.dwell.as_ref()?;
+    if state.drag.is_dragging {
+        dwell.next_report
+    } else {
+        Some(dwell.since)
+    }
+}
+
+// 根据光标位置和拖拽状态更新所在的边缘，返回需要分发的到达/停留/离开事件
+fn update_screen_edge(state: &mut UnifiedMonitorState, x: f64, y: f64, timestamp: f64) -> Vec<ScreenEdgeEvent> {
+    let dragging = state.drag.is_dragging;
+    let drag_session_id = state.drag.drag_session_id;
+    let Some(detection) = state.screen_edges.as_mut() else {
+        return Vec::new();
+    };
+    let now = Instant::now();
+    let current = if dragging { screen_edge_at(detection, x, y) } else { None };
+    let edge_event = |event_type: &str, edge: &str, display_id: u32, since: Instant| ScreenEdgeEvent {
+        event_type: event_type.to_string(),
+        edge: edge.to_string(),
+        display_id,
+        x,
+        y,
+        dwell_ms: now.saturating_duration_since(since).as_secs_f64() * 1000.0,
+        drag_session_id,
+        timestamp,
+        platform: platform_name().to_string(),
+    };
+
+    let mut events = Vec::new();
+    let staying = detection
+        .dwell
+        .as_ref()
+        .is_some_and(|dwell| current == Some((dwell.edge, dwell.display_id)));
+    if staying {
+        let repeat_interval = detection.repeat_interval;
+        if let Some(dwell) = detection.dwell.as_mut().filter(|dwell| dwell.next_report.is_some_and(|next| now >= next)) {
+            events.push(edge_event("drag-screen-edge", dwell.edge, dwell.display_id, dwell.since));
+            dwell.next_report = repeat_interval.map(|interval| now + interval);
+        }
+        return events;
+    }
+
+    if let Some(dwell) = detection.dwell.take() {
+        events.push(edge_event("drag-screen-edge-leave", dwell.edge, dwell.display_id, dwell.since));
+    }
+    if let Some((edge, display_id)) = current {
+        events.push(edge_event("drag-screen-edge", edge, display_id, now));
+        detection.dwell = Some(EdgeDwell {
+            edge,
+            display_id,
+            since: now,
+            next_report: detection.repeat_interval.map(|interval| now + interval),
+        });
+    }
+    events
+}
+
+// 计时器到期：光标没有移动，按最近一次观察到的位置重新判断
+fn check_screen_edge(ctx: &MonitorContext) {
+    let position = ctx.last_position.lock().ok().and_then(|pos| *pos);
+    let edge_events = match (ctx.state.lock(), position) {
+        (Ok(mut state), Some((x, y))) if !state.dispatch_suspended() => update_screen_edge(&mut state, x, y, now_timestamp()),
+        _ => Vec::new(),
+    };
+    trigger_screen_edge_events(ctx, edge_events);
+}
+
+fn trigger_screen_edge_events(ctx: &MonitorContext, edge_events: Vec<ScreenEdgeEvent>) {
+    if edge_events.is_empty() {
+        return;
+    }
+    let mut calls = Vec::new();
+    if let Ok(state) = ctx.state.lock() {
+        for edge_event in edge_events {
+            dispatch(&state, &state.screen_edge_callbacks, &edge_event, &mut calls);
+        }
+    }
+    deliver(ctx, calls);
+}
+
+// 启用（或替换设置）拖拽到达屏幕边缘的检测，传入 null 关闭；
+// 显示器边界在调用时读取，显示器布局或参考显示器变化后需要重新设置
+#[napi]
+pub fn set_screen_edge_detection(env: Env, options: Option<ScreenEdgeOptions>) -> Result<()> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let Some(options) = options else {
+        state.screen_edges = None;
+        return Ok(());
+    };
+    let threshold = options.threshold.unwrap_or(DEFAULT_SCREEN_EDGE_THRESHOLD);
+    if !threshold.is_finite() || threshold <= 0.0 {
+        return Err(Error::new(Status::InvalidArg, format!("Invalid screen edge threshold: {}", threshold)));
+    }
+    let repeat_interval = match options.repeat_interval_ms.unwrap_or(DEFAULT_SCREEN_EDGE_REPEAT_MS) {
+        0 => None,
+        ms => Some(Duration::from_millis(ms as u64)),
+    };
+    let (origin_x, origin_y) = state.reference_origin;
+    let displays = platform::displays()
+        .into_iter()
+        .map(|display| {
+            let rect = state.coordinates.rect_to_space(display.bounds);
+            let bounds = Rectangle {
+                x: rect.x - origin_x,
+                y: rect.y - origin_y,
+                width: rect.width,
+                height: rect.height,
+            };
+            (display.id, bounds)
+        })
+        .collect();
+    state.screen_edges = Some(ScreenEdgeDetection {
+        threshold,
+        repeat_interval,
+        displays,
+        dwell: None,
+    });
+    Ok(())
+}
+
+#[napi(ts_args_type = "callback: (err: null | Error, event: ScreenEdgeEvent) => void")]
+pub fn on_screen_edge_event(env: Env, callback: JsFunction) -> Result<u32> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let id = state.next_callback_id + 1;
+    state.next_callback_id = id;
+    let tsfn: ThreadsafeFunction<ScreenEdgeEvent, ErrorStrategy::CalleeHandled> = isolate_callback(&env, &ctx, id, callback)?.create_threadsafe_function(state.callback_queue_size, |ctx| Ok(vec![ctx.value]))?;
+    state.screen_edge_callbacks.insert(id, Arc::new(tsfn));
+    drop(state);
+    sync_auto_monitor(&ctx, true)?;
+    Ok(id)
+}
+
+#[napi]
+pub fn remove_screen_edge_event_listener(env: Env, id: u32) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let removed = ctx.lock()?.screen_edge_callbacks.remove(&id).is_some();
+    sync_auto_monitor(&ctx, false)?;
+    Ok(removed)
+}
+
+// endregion
+
+// region: External Drag Detection (外部拖拽检测，仅 macOS)
+
+// 不创建覆盖窗口、也不成为正式的拖放目标：轮询系统拖拽剪贴板的 changeCount 与主按键状态，
+// 按下后 changeCount 变化即认为有外部拖拽正在进行，再用光标位置判断经过了哪些区域。
+// 结果只是尽力而为的推断，适合高亮之类的非关键交互，不能替代真正的拖放。
+
+const DEFAULT_EXTERNAL_DRAG_POLL_MS: u32 = 50;
+
+fn external_drag_zone_at(ctx: &MonitorContext, x: f64, y: f64) -> Option<String> {
+    let state = ctx.state.lock().ok()?;
+    state
+        .external_drag_zones
+        .iter()
+        .find(|zone| x >= zone.x && x < zone.x + zone.width && y >= zone.y && y < zone.y + zone.height)
+        .map(|zone| zone.id.clone())
+}
+
+fn trigger_external_drag_event(
+    ctx: &MonitorContext,
+    event_type: &str,
+    zone_id: String,
+    (x, y): (f64, f64),
+    source_app: Option<FrontmostApplication>,
+) {
+    let external_drag_event = ExternalDragEvent {
+        event_type: event_type.to_string(),
+        zone_id,
+        x,
+        y,
+        has_files: platform::drag_pasteboard_has_files(),
+        source_app,
+        timestamp: now_timestamp(),
+        platform: platform_name().to_string(),
+    };
+    let mut calls = Vec::new();
+    if let Ok(state) = ctx.state.lock() {
+        dispatch(&state, &state.external_drag_callbacks, &external_drag_event, &mut calls);
+    }
+    deliver(ctx, calls);
+}
+
+fn external_drag_watch_loop(ctx: MonitorContext, running: Arc<AtomicBool>, interval: Duration) {
+    let mut was_pressed = false;
+    let mut press_change_count = None;
+    let mut active_zone: Option<String> = None;
+    let mut last_position = (0.0, 0.0);
+    let mut was_dragging = false;
+    let mut source_app = None;
+
+    while running.load(Ordering::SeqCst) {
+        let pressed = platform::primary_button_pressed();
+        let change_count = platform::drag_pasteboard_change_count();
+        if pressed && !was_pressed {
+            press_change_count = change_count;
+        }
+        was_pressed = pressed;
+
+        // 按下之后拖拽剪贴板被写入，说明有拖拽会话正在进行
+        let dragging = pressed && change_count.is_some() && change_count != press_change_count;
+        if dragging && !was_dragging {
+            // 拖拽刚开始时来源应用还在前台，之后前台可能随光标经过的窗口变化
+            source_app = platform::frontmost_app().map(FrontmostApplication::from);
+        }
+        was_dragging = dragging;
+        let zone = if dragging {
+            platform::cursor_position().and_then(|position| {
+                let position = to_reference_coordinates(&ctx, position);
+                last_position = position;
+                external_drag_zone_at(&ctx, position.0, position.1)
+            })
+        } else {
+            None
+        };
+
+        if zone != active_zone {
+            if let Some(zone_id) = active_zone.take() {
+                trigger_external_drag_event(&ctx, "external_drag_leave_zone", zone_id, last_position, source_app.clone());
+            }
+            if let Some(zone_id) = zone.clone() {
+                trigger_external_drag_event(&ctx, "external_drag_over_zone", zone_id, last_position, source_app.clone());
+            }
+            active_zone = zone;
+        }
+
+        thread::sleep(interval);
+    }
+}
+
+// 开始（或更新区域后继续）外部拖拽检测，仅 macOS 支持
+#[napi]
+pub fn start_external_drag_watch(env: Env, zones: Vec<ExternalDragZone>, poll_interval_ms: Option<u32>) -> Result<()> {
+    if !cfg!(target_os = "macos") {
+        return Err(Error::new(
+            Status::GenericFailure,
+            "External drag detection is only supported on macOS",
+        ));
+    }
+
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    state.external_drag_zones = zones;
+    if state.external_drag_watch.is_some() {
+        return Ok(());
+    }
+
+    let running = Arc::new(AtomicBool::new(true));
+    state.external_drag_watch = Some(running.clone());
+    let interval = Duration::from_millis(poll_interval_ms.unwrap_or(DEFAULT_EXTERNAL_DRAG_POLL_MS).max(1) as u64);
+    let watch_ctx = ctx.clone();
+    thread::spawn(move || external_drag_watch_loop(watch_ctx, running, interval));
+    Ok(())
+}
+
+#[napi]
+pub fn stop_external_drag_watch(env: Env) -> Result<()> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    if let Some(running) = state.external_drag_watch.take() {
+        running.store(false, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
+#[napi(ts_args_type = "callback: (err: null | Error, event: ExternalDragEvent) => void")]
+pub fn on_external_drag_event(env: Env, callback: JsFunction) -> Result<u32> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let id = state.next_callback_id + 1;
+    state.next_callback_id = id;
+    let tsfn: ThreadsafeFunction<ExternalDragEvent, ErrorStrategy::CalleeHandled> = isolate_callback(&env, &ctx, id, callback)?.create_threadsafe_function(state.callback_queue_size, |ctx| Ok(vec![ctx.value]))?;
+    state.external_drag_callbacks.insert(id, Arc::new(tsfn));
+    Ok(id)
+}
+
+#[napi]
+pub fn remove_external_drag_event_listener(env: Env, id: u32) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    Ok(state.external_drag_callbacks.remove(&id).is_some())
+}
+
+// endregion
+
+// region: Gesture Watch (触控板手势，仅 macOS)
+
+// 手势不经过鼠标钩子，由单独的线程通过只监听的事件 tap 接收，只在 startGestureWatch 之后运行。
+// Windows 的 WM_GESTURE/WM_TOUCH 只发送给注册过的窗口，Linux 没有系统级的手势事件，因此都不支持
+
+fn gesture_event(ctx: &MonitorContext, gesture: platform::Gesture) -> GestureEvent {
+    let (x, y) = platform::cursor_position()
+        .map(|position| to_reference_coordinates(ctx, position))
+        .unwrap_or((0.0, 0.0));
+    let (event_type, scale, rotation, (delta_x, delta_y)) = match gesture.kind {
+        platform::GestureKind::Pinch { magnification } => ("pinch", magnification, 0.0, (0.0, 0.0)),
+        platform::GestureKind::Rotate { degrees } => ("rotate", 0.0, degrees, (0.0, 0.0)),
+        platform::GestureKind::Swipe { delta_x, delta_y } => ("swipe", 0.0, 0.0, (delta_x, delta_y)),
+    };
+    GestureEvent {
+        event_type: event_type.to_string(),
+        phase: gesture.phase.map(|phase| {
+            match phase {
+                platform::GesturePhase::Began => "began",
+                platform::GesturePhase::Changed => "changed",
+                platform::GesturePhase::Ended => "ended",
+            }
+            .to_string()
+        }),
+        x,
+        y,
+        scale,
+        rotation,
+        delta_x,
+        delta_y,
+        timestamp: now_timestamp(),
+        platform: platform_name().to_string(),
+    }
+}
+
+fn gesture_watch_thread(ctx: MonitorContext, running: Arc<AtomicBool>) {
+    let callback_ctx = ctx.clone();
+    let on_gesture: platform::GestureCallback = Box::new(move |gesture| {
+        let gesture_event = gesture_event(&callback_ctx, gesture);
+        let mut calls = Vec::new();
+        if let Ok(state) = callback_ctx.state.lock() {
+            dispatch(&state, &state.gesture_callbacks, &gesture_event, &mut calls);
+        }
+        deliver(&callback_ctx, calls);
+    });
+
+    if let Err(message) = platform::watch_gestures(running.clone(), on_gesture) {
+        // 启动失败（通常是没有“辅助功能”权限）时清除状态，允许之后重新开始
+        if let Ok(mut state) = ctx.state.lock() {
+            if state.gesture_watch.as_ref().is_some_and(|watch| Arc::ptr_eq(watch, &running)) {
+                state.gesture_watch = None;
+            }
+        }
+        report_or_log(&ctx, "listener", format!("Gesture watch failed: {}", message));
+    }
+}
+
+// 开始监听触控板手势，仅 macOS 支持；与鼠标监听相互独立，可以单独开启
+#[napi]
+pub fn start_gesture_watch(env: Env) -> Result<()> {
+    if !cfg!(target_os = "macos") {
+        return Err(Error::new(Status::GenericFailure, "Gesture events are only supported on macOS"));
+    }
+
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    if state.gesture_watch.is_some() {
+        return Ok(());
+    }
+
+    let running = Arc::new(AtomicBool::new(true));
+    state.gesture_watch = Some(running.clone());
+    let watch_ctx = ctx.clone();
+    thread::spawn(move || gesture_watch_thread(watch_ctx, running));
+    Ok(())
+}
+
+#[napi]
+pub fn stop_gesture_watch(env: Env) -> Result<()> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    if let Some(running) = state.gesture_watch.take() {
+        running.store(false, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
+#[napi(ts_args_type = "callback: (err: null | Error, event: GestureEvent) => void")]
+pub fn on_gesture_event(env: Env, callback: JsFunction) -> Result<u32> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let id = state.next_callback_id + 1;
+    state.next_callback_id = id;
+    let tsfn: ThreadsafeFunction<GestureEvent, ErrorStrategy::CalleeHandled> = isolate_callback(&env, &ctx, id, callback)?.create_threadsafe_function(state.callback_queue_size, |ctx| Ok(vec![ctx.value]))?;
+    state.gesture_callbacks.insert(id, Arc::new(tsfn));
+    Ok(id)
+}
+
+#[napi]
+pub fn remove_gesture_event_listener(env: Env, id: u32) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    Ok(state.gesture_callbacks.remove(&id).is_some())
+}
+
+// endregion
+
+// region: Clipboard Monitor (系统剪贴板变化)
+
+// 与手势监听一样在单独的线程上运行，与鼠标监听相互独立。macOS 轮询通用剪贴板的 changeCount，
+// Windows 用 AddClipboardFormatListener，X11 用 XFixes 的选区所有者通知
+
+fn clipboard_watch_thread(ctx: MonitorContext, running: Arc<AtomicBool>) {
+    let callback_ctx = ctx.clone();
+    let on_change: platform::ClipboardCallback = Box::new(move |contents| {
+        let clipboard_event = ClipboardEvent {
+            has_files: contents.files,
+            has_text: contents.text,
+            has_image: contents.image,
+            timestamp: now_timestamp(),
+            platform: platform_name().to_string(),
+        };
+        let mut calls = Vec::new();
+        if let Ok(state) = callback_ctx.state.lock() {
+            dispatch(&state, &state.clipboard_callbacks, &clipboard_event, &mut calls);
+        }
+        deliver(&callback_ctx, calls);
+    });
+
+    if let Err(message) = platform::watch_clipboard(running.clone(), on_change) {
+        if let Ok(mut state) = ctx.state.lock() {
+            if state.clipboard_watch.as_ref().is_some_and(|watch| Arc::ptr_eq(watch, &running)) {
+                state.clipboard_watch = None;
+            }
+        }
+        report_or_log(&ctx, "listener", format!("Clipboard monitor failed: {}", message));
+    }
+}
+
+// 开始监听剪贴板变化；开始时剪贴板里已有的内容不报告
+#[napi]
+pub fn start_clipboard_monitor(env: Env) -> Result<()> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    if state.clipboard_watch.is_some() {
+        return Ok(());
+    }
+
+    let running = Arc::new(AtomicBool::new(true));
+    state.clipboard_watch = Some(running.clone());
+    let watch_ctx = ctx.clone();
+    thread::spawn(move || clipboard_watch_thread(watch_ctx, running));
+    Ok(())
+}
+
+#[napi]
+pub fn stop_clipboard_monitor(env: Env) -> Result<()> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    if let Some(running) = state.clipboard_watch.take() {
+        running.store(false, Ordering::SeqCst);
+    }
+    Ok(())
 }
 
+#[napi(ts_args_type = "callback: (err: null | Error, event: ClipboardEvent) => void")]
+pub fn on_clipboard_change(env: Env, callback: JsFunction) -> Result<u32> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let id = state.next_callback_id + 1;
+    state.next_callback_id = id;
+    let tsfn: ThreadsafeFunction<ClipboardEvent, ErrorStrategy::CalleeHandled> = isolate_callback(&env, &ctx, id, callback)?.create_threadsafe_function(state.callback_queue_size, |ctx| Ok(vec![ctx.value]))?;
+    state.clipboard_callbacks.insert(id, Arc::new(tsfn));
+    Ok(id)
+}
 
-#[napi(object)]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct DragEvent {
-    pub event_type: String,
-    pub x: f64,
-    pub y: f64,
-    pub start_x: f64,
-    pub start_y: f64,
-    pub button: i32,
-    pub timestamp: f64,
-    pub platform: String,
+#[napi]
+pub fn remove_clipboard_change_listener(env: Env, id: u32) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    Ok(state.clipboard_callbacks.remove(&id).is_some())
 }
 
-struct UnifiedMonitorState {
-    is_monitoring: bool,
-    mouse_callbacks: HashMap<u32, ThreadsafeFunction<MouseEvent, ErrorStrategy::CalleeHandled>>,
-    drag_callbacks: HashMap<u32, ThreadsafeFunction<DragEvent, ErrorStrategy::CalleeHandled>>,
-    next_callback_id: u32,
-    shutdown_sender: Option<std::sync::mpsc::Sender<()>>,
-    monitor_handle: Option<thread::JoinHandle<()>>,
-    // Drag state
-    is_dragging: bool,
-    drag_start_position: Option<(f64, f64)>,
-    drag_button: Option<i32>,
-    // Distance threshold detection
-    mouse_pressed: bool,
-    potential_drag_start: Option<(f64, f64)>,
-    drag_threshold: f64,
+// endregion
+
+// region: Active App Filter (前台应用过滤)
+
+// 与系统主题一样轮询：前台切换没有可以在无窗口进程里接收的跨平台通知。
+// 结果缓存在 app_active 中，监听线程处理事件时只读取这个标志
+
+const ACTIVE_APP_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+fn app_filter_matches(filter: &ActiveAppFilter, app: &platform::FrontmostApp) -> bool {
+    let field_matches = |expected: &Option<String>, actual: &Option<String>| match (expected, actual) {
+        (Some(expected), Some(actual)) => expected.eq_ignore_ascii_case(actual),
+        _ => false,
+    };
+    field_matches(&filter.bundle_id, &app.bundle_id) || field_matches(&filter.exe_name, &app.exe_name())
 }
 
-impl UnifiedMonitorState {
-    fn new() -> Self {
-        Self {
-            is_monitoring: false,
-            mouse_callbacks: HashMap::new(),
-            drag_callbacks: HashMap::new(),
-            next_callback_id: 0,
-            shutdown_sender: None,
-            monitor_handle: None,
-            // Drag state
-            is_dragging: false,
-            drag_start_position: None,
-            drag_button: None,
-            // Distance threshold detection
-            mouse_pressed: false,
-            potential_drag_start: None,
-            drag_threshold: 5.0, // 5 pixels threshold
+// 按当前前台应用更新 app_active，返回是否刚从符合变为不符合；无法确定前台应用时保持原状
+fn update_app_active(state: &mut UnifiedMonitorState, frontmost: Option<platform::FrontmostApp>) -> bool {
+    let active = match (&state.active_app_filter, frontmost) {
+        (None, _) => true,
+        (Some(_), None) => state.app_active,
+        (Some(filter), Some(app)) => app_filter_matches(filter, &app),
+    };
+    let deactivated = state.app_active && !active;
+    state.app_active = active;
+    deactivated
+}
+
+fn active_app_watch_loop(ctx: MonitorContext, running: Arc<AtomicBool>) {
+    while running.load(Ordering::SeqCst) {
+        thread::sleep(ACTIVE_APP_POLL_INTERVAL);
+        let frontmost = platform::frontmost_app();
+        let deactivated = match ctx.state.lock() {
+            Ok(mut state) if running.load(Ordering::SeqCst) => update_app_active(&mut state, frontmost),
+            _ => return,
+        };
+        // 切到其他应用时进行中的拖拽无法再看到松开，与暂停一样取消
+        if deactivated {
+            let _ = cancel_active_drag(&ctx, DragEndReason::FocusChange);
         }
     }
 }
 
-lazy_static::lazy_static! {
-    static ref UNIFIED_STATE: Arc<Mutex<UnifiedMonitorState>> = Arc::new(Mutex::new(UnifiedMonitorState::new()));
-    static ref LAST_POSITION: Arc<Mutex<Option<(f64, f64)>>> = Arc::new(Mutex::new(None));
+// 当前的前台应用，无法确定时返回 null；在 dragstart 回调里调用即可得到开始拖拽时用户所在的应用
+#[napi]
+pub fn get_frontmost_application() -> Option<FrontmostApplication> {
+    platform::frontmost_app().map(FrontmostApplication::from)
 }
 
-// 重置拖拽状态的辅助函数
-fn reset_drag_state(state: &mut std::sync::MutexGuard<'_, UnifiedMonitorState>) {
-    state.mouse_pressed = false;
-    state.is_dragging = false;
-    state.potential_drag_start = None;
-    state.drag_start_position = None;
-    state.drag_button = None;
+// 只在指定应用位于前台时分发事件；传入 null 取消过滤
+// 不在前台期间与 pauseMonitoring 相同：按键状态继续跟踪，进行中的拖拽以 dragcancel 结束
+#[napi]
+pub fn set_active_app_filter(env: Env, filter: Option<ActiveAppFilter>) -> Result<()> {
+    if filter.as_ref().is_some_and(|filter| filter.bundle_id.is_none() && filter.exe_name.is_none()) {
+        return Err(Error::new(Status::InvalidArg, "Active app filter needs a bundleId or exeName"));
+    }
+    let frontmost = filter.as_ref().and_then(|_| platform::frontmost_app());
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    state.active_app_filter = filter;
+    let deactivated = update_app_active(&mut state, frontmost);
+
+    if state.active_app_filter.is_none() {
+        if let Some(running) = state.active_app_watch.take() {
+            running.store(false, Ordering::SeqCst);
+        }
+    } else if state.active_app_watch.is_none() {
+        let running = Arc::new(AtomicBool::new(true));
+        state.active_app_watch = Some(running.clone());
+        let watch_ctx = ctx.clone();
+        thread::spawn(move || active_app_watch_loop(watch_ctx, running));
+    }
+    drop(state);
+
+    if deactivated {
+        cancel_active_drag(&ctx, DragEndReason::FocusChange)?;
+    }
+    Ok(())
 }
 
-fn convert_rdev_mouse_event(event: &Event) -> Option<MouseEvent> {
-    let platform = if cfg!(target_os = "macos") {
-        "macos"
-    } else if cfg!(target_os = "windows") {
-        "windows"
-    } else if cfg!(target_os = "linux") {
-        "linux"
-    } else {
-        "unknown"
-    };
+// endregion
 
-    let timestamp = event.time
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_secs_f64();
+// region: System Theme (系统主题)
 
-    match event.event_type {
-        EventType::ButtonPress(button) => {
-            let button_num = match button {
-                Button::Left => 1,
-                Button::Middle => 2,
-                Button::Right => 3,
-                Button::Unknown(b) => b as i32,
-            };
+// 系统没有跨平台的主题变化通知可以在无窗口的进程里接收，这里与外部拖拽检测一样采用轮询，
+// 只在有回调注册时运行
 
-            Some(MouseEvent {
-                event_type: "mousedown".to_string(),
-                x: 0.0, // Will be updated with actual coordinates
-                y: 0.0, // Will be updated with actual coordinates
-                button: button_num,
-                timestamp,
-                platform: platform.to_string(),
-            })
-        }
-        EventType::ButtonRelease(button) => {
-            let button_num = match button {
-                Button::Left => 1,
-                Button::Middle => 2,
-                Button::Right => 3,
-                Button::Unknown(b) => b as i32,
-            };
+const THEME_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
-            Some(MouseEvent {
-                event_type: "mouseup".to_string(),
-                x: 0.0, // Will be updated with actual coordinates
-                y: 0.0, // Will be updated with actual coordinates
-                button: button_num,
-                timestamp,
-                platform: platform.to_string(),
-            })
+fn current_system_theme() -> SystemTheme {
+    let theme = platform::system_theme();
+    SystemTheme {
+        appearance: match theme.dark {
+            Some(true) => "dark",
+            Some(false) => "light",
+            None => "unknown",
         }
-        EventType::MouseMove { x, y } => {
-            Some(MouseEvent {
-                event_type: "mousemove".to_string(),
-                x,
-                y,
-                button: 0,
-                timestamp,
-                platform: platform.to_string(),
-            })
+        .to_string(),
+        accent_color: theme.accent_color.map(|(r, g, b)| format!("#{:02x}{:02x}{:02x}", r, g, b)),
+        platform: platform_name().to_string(),
+    }
+}
+
+fn system_theme_watch_loop(ctx: MonitorContext, running: Arc<AtomicBool>) {
+    let mut last_theme = platform::system_theme();
+    while running.load(Ordering::SeqCst) {
+        thread::sleep(THEME_POLL_INTERVAL);
+        let theme = platform::system_theme();
+        if theme == last_theme {
+            continue;
         }
-        EventType::Wheel { delta_x: _, delta_y: _ } => {
-            Some(MouseEvent {
-                event_type: "wheel".to_string(),
-                x: 0.0,
-                y: 0.0,
-                button: 0,
-                timestamp,
-                platform: platform.to_string(),
-            })
+        last_theme = theme;
+
+        let system_theme = current_system_theme();
+        let mut calls = Vec::new();
+        if let Ok(state) = ctx.state.lock() {
+            dispatch(&state, &state.theme_callbacks, &system_theme, &mut calls);
         }
+        deliver(&ctx, calls);
     }
 }
 
+#[napi]
+pub fn get_system_theme() -> SystemTheme {
+    current_system_theme()
+}
+
+// 注册系统主题变化回调，首个回调注册时开始轮询
+#[napi(ts_args_type = "callback: (err: null | Error, event: SystemTheme) => void")]
+pub fn on_system_theme_change(env: Env, callback: JsFunction) -> Result<u32> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let id = state.next_callback_id + 1;
+    state.next_callback_id = id;
+    let tsfn: ThreadsafeFunction<SystemTheme, ErrorStrategy::CalleeHandled> = isolate_callback(&env, &ctx, id, callback)?.create_threadsafe_function(state.callback_queue_size, |ctx| Ok(vec![ctx.value]))?;
+    state.theme_callbacks.insert(id, Arc::new(tsfn));
+
+    if state.theme_watch.is_none() {
+        let running = Arc::new(AtomicBool::new(true));
+        state.theme_watch = Some(running.clone());
+        let watch_ctx = ctx.clone();
+        thread::spawn(move || system_theme_watch_loop(watch_ctx, running));
+    }
+    Ok(id)
+}
 
-fn trigger_mouse_event(mouse_event: MouseEvent) {
-    if let Ok(state) = UNIFIED_STATE.lock() {
-        for callback in state.mouse_callbacks.values() {
-            callback.call(Ok(mouse_event.clone()), ThreadsafeFunctionCallMode::Blocking);
+// 移除回调，最后一个回调移除后停止轮询
+#[napi]
+pub fn remove_system_theme_listener(env: Env, id: u32) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    let removed = state.theme_callbacks.remove(&id).is_some();
+    if state.theme_callbacks.is_empty() {
+        if let Some(running) = state.theme_watch.take() {
+            running.store(false, Ordering::SeqCst);
         }
     }
+    Ok(removed)
 }
 
+// endregion
 
-fn trigger_drag_event(drag_event: DragEvent) {
-    if let Ok(state) = UNIFIED_STATE.lock() {
-        for callback in state.drag_callbacks.values() {
-            callback.call(Ok(drag_event.clone()), ThreadsafeFunctionCallMode::Blocking);
-        }
+// region: Native File Drag (拖出文件)
+
+const DEFAULT_DRAG_IMAGE_SIZE: f64 = 64.0;
+
+// 从当前光标位置发起系统拖拽，把文件拖出到 Finder 或其他应用
+// 返回的 Promise 在拖拽结束后以目标接受的操作（"copy" | "move" | "link" | "none"）完成
+#[napi(ts_return_type = "Promise<string>")]
+pub fn start_native_file_drag(env: Env, paths: Vec<String>, options: Option<NativeFileDragOptions>) -> Result<napi::JsObject> {
+    if paths.is_empty() {
+        return Err(Error::new(Status::InvalidArg, "At least one path is required"));
     }
+    let options = options.unwrap_or(NativeFileDragOptions {
+        image_path: None,
+        image_size: None,
+    });
+
+    let (deferred, promise) = env.create_deferred()?;
+    platform::start_file_drag(
+        &paths,
+        options.image_path.as_deref(),
+        options.image_size.unwrap_or(DEFAULT_DRAG_IMAGE_SIZE),
+        Box::new(move |result| match result {
+            Ok(effect) => deferred.resolve(move |_| Ok(effect.as_str().to_string())),
+            Err(message) => deferred.reject(Error::new(Status::GenericFailure, message)),
+        }),
+    );
+    Ok(promise)
 }
 
-// 统一的事件监听函数，只处理鼠标事件
-fn unified_event_listener() -> impl FnMut(Event) {
-    move |event: Event| {
-        // 尝试作为鼠标事件处理
-        if let Some(mut mouse_event) = convert_rdev_mouse_event(&event) {
-            // 处理鼠标事件的坐标
-            if mouse_event.event_type != "mousemove" {
-                if let Some((x, y)) = LAST_POSITION.lock().ok().and_then(|p| *p) {
-                    mouse_event.x = x;
-                    mouse_event.y = y;
-                }
-            } else {
-                if let Ok(mut pos) = LAST_POSITION.lock() {
-                    *pos = Some((mouse_event.x, mouse_event.y));
-                }
-            }
+// 拖出尚不存在的虚拟文件：目标接受放下后才调用 provide 生成内容
+// provide 收到 FilePromiseRequest，返回（或以 Promise 返回）写好内容的临时文件路径，该文件会被复制到放下的位置
+// 拖拽线程需要等待 JS 返回的 Promise，依赖 napi 的 tokio 运行时，只在启用 async-runtime feature 时提供
+#[cfg(feature = "async-runtime")]
+#[napi(
+    ts_args_type = "files: PromisedFile[], provide: (err: null | Error, request: FilePromiseRequest) => string | Promise<string>, options?: NativeFileDragOptions",
+    ts_return_type = "Promise<string>"
+)]
+pub fn start_file_promise_drag(
+    env: Env,
+    files: Vec<PromisedFile>,
+    provide: JsFunction,
+    options: Option<NativeFileDragOptions>,
+) -> Result<napi::JsObject> {
+    if files.is_empty() {
+        return Err(Error::new(Status::InvalidArg, "At least one file is required"));
+    }
+    let options = options.unwrap_or(NativeFileDragOptions {
+        image_path: None,
+        image_size: None,
+    });
 
-            // 拖拽状态检测逻辑
-            if let Ok(mut state) = UNIFIED_STATE.lock() {
-                match mouse_event.event_type.as_str() {
-                    "mousedown" => {
-                        // 记录鼠标按下状态，但不立即开始拖拽
-                        state.mouse_pressed = true;
-                        state.potential_drag_start = Some((mouse_event.x, mouse_event.y));
-                        state.drag_button = Some(mouse_event.button);
-                        // 不触发 dragstart 事件，等待移动距离超过阈值
-                    }
-                    "mousemove" => {
-                        if state.mouse_pressed {
-                            if let Some((start_x, start_y)) = state.potential_drag_start {
-                                // 计算移动距离
-                                let delta_x = mouse_event.x - start_x;
-                                let delta_y = mouse_event.y - start_y;
-                                let distance = (delta_x * delta_x + delta_y * delta_y).sqrt();
-
-                                if distance >= state.drag_threshold {
-                                    if !state.is_dragging {
-                                        // 首次超过阈值，开始拖拽
-                                        state.is_dragging = true;
-                                        state.drag_start_position = Some((start_x, start_y));
-
-                                        // 触发拖拽开始事件
-                                        let drag_event = DragEvent {
-                                            event_type: "dragstart".to_string(),
-                                            x: mouse_event.x,
-                                            y: mouse_event.y,
-                                            start_x: start_x,
-                                            start_y: start_y,
-                                            button: state.drag_button.unwrap_or(0),
-                                            timestamp: mouse_event.timestamp,
-                                            platform: mouse_event.platform.clone(),
-                                        };
-                                        drop(state); // 释放锁
-                                        trigger_drag_event(drag_event);
-                                    } else {
-                                        // 已经在拖拽中，触发拖拽移动事件
-                                        let drag_event = DragEvent {
-                                            event_type: "dragmove".to_string(),
-                                            x: mouse_event.x,
-                                            y: mouse_event.y,
-                                            start_x,
-                                            start_y,
-                                            button: state.drag_button.unwrap_or(0),
-                                            timestamp: mouse_event.timestamp,
-                                            platform: mouse_event.platform.clone(),
-                                        };
-                                        drop(state); // 释放锁
-                                        trigger_drag_event(drag_event);
-                                    }
-                                } else {
-                                    // 距离未超过阈值，不触发事件
-                                    drop(state); // 释放锁
-                                }
-                            } else {
-                                drop(state); // 释放锁
-                            }
-                        } else {
-                            drop(state); // 释放锁
-                        }
-                    }
-                    "mouseup" => {
-                        if state.mouse_pressed {
-                            if state.is_dragging {
-                                // 正在拖拽中，触发拖拽结束事件
-                                // is_dragging 为 true 时，drag_start_position 应该总是有值
-                                if let Some((start_x, start_y)) = state.drag_start_position {
-                                    let drag_event = DragEvent {
-                                        event_type: "dragend".to_string(),
-                                        x: mouse_event.x,
-                                        y: mouse_event.y,
-                                        start_x,
-                                        start_y,
-                                        button: state.drag_button.unwrap_or(0),
-                                        timestamp: mouse_event.timestamp,
-                                        platform: mouse_event.platform.clone(),
-                                    };
-                                    reset_drag_state(&mut state);
-                                    drop(state); // 释放锁
-                                    trigger_drag_event(drag_event);
-                                }
-                            } else {
-                                // 无论是否开始拖拽，都重置所有状态
-                                reset_drag_state(&mut state);
-                                drop(state); // 释放锁
-                            }
-                        } else {
-                            drop(state); // 释放锁
-                        }
-                    }
-                    _ => {
-                        // 其他鼠标事件，不处理拖拽
-                        drop(state); // 释放锁
-                    }
-                }
+    let mut tsfn: ThreadsafeFunction<FilePromiseRequest, ErrorStrategy::CalleeHandled> =
+        provide.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+    // 系统可能在拖拽结束很久之后才释放 provider，不让它阻止 Node 退出
+    tsfn.unref(&env)?;
+    let file_names: Vec<String> = files.iter().map(|file| file.file_name.clone()).collect();
+    let provider: platform::FilePromiseProvider = Arc::new(move |index| {
+        let request = FilePromiseRequest {
+            index: index as u32,
+            file_name: file_names[index].clone(),
+        };
+        napi::bindgen_prelude::block_on(async {
+            match tsfn.call_async::<Either<String, Promise<String>>>(Ok(request)).await? {
+                Either::A(path) => Ok(path),
+                Either::B(promise) => promise.await,
             }
+        })
+        .map(std::path::PathBuf::from)
+        .map_err(|e| format!("File promise provider failed: {}", e.reason))
+    });
+    let files: Vec<platform::PromisedFile> = files
+        .into_iter()
+        .map(|file| platform::PromisedFile {
+            #[cfg(any(target_os = "macos", target_os = "windows"))]
+            file_name: file.file_name,
+            file_type: file.file_type,
+        })
+        .collect();
 
-            trigger_mouse_event(mouse_event);
-        }
-        // 忽略所有非鼠标事件
+    let (deferred, promise) = env.create_deferred()?;
+    platform::start_file_promise_drag(
+        &files,
+        options.image_path.as_deref(),
+        options.image_size.unwrap_or(DEFAULT_DRAG_IMAGE_SIZE),
+        provider,
+        Box::new(move |result| match result {
+            Ok(effect) => deferred.resolve(move |_| Ok(effect.as_str().to_string())),
+            Err(message) => deferred.reject(Error::new(Status::GenericFailure, message)),
+        }),
+    );
+    Ok(promise)
+}
+
+// 无法拖出时的备选方案：以“复制文件”的形式写入系统剪贴板，使用与拖出文件相同的剪贴板格式
+#[napi]
+pub fn write_files_to_clipboard(paths: Vec<String>) -> Result<()> {
+    if paths.is_empty() {
+        return Err(Error::new(Status::InvalidArg, "At least one path is required"));
     }
+    platform::write_files_to_clipboard(&paths).map_err(|message| Error::new(Status::GenericFailure, message))
 }
 
-// Mouse API functions
+// 剪贴板中的文件路径，剪贴板不包含文件时返回空数组
 #[napi]
-pub fn start_mouse_monitor() -> Result<()> {
-    start_unified_monitor()
+pub fn read_files_from_clipboard() -> Result<Vec<String>> {
+    platform::read_files_from_clipboard().map_err(|message| Error::new(Status::GenericFailure, message))
 }
 
+// endregion
+// region: Drop Target (窗口放置目标)
+
+// 不依赖监听线程：系统在窗口所在的 UI 线程上回调放置目标，事件经由 tsfn 交给监听器
+
+// windowHandle 为 BrowserWindow.getNativeWindowHandle() 返回的 Buffer（macOS 为 NSView 指针，Windows 为 HWND）
+// 只能在 Electron 主进程中调用；挂载后拖入该窗口的文件由插件接收，页面不再收到文件的 drop 事件
 #[napi]
-pub fn stop_mouse_monitor() -> Result<()> {
-    stop_unified_monitor()
+pub fn attach_drop_target(env: Env, window_handle: Buffer) -> Result<u32> {
+    let handle = native_handle(&window_handle)?;
+    let ctx = monitor_context(&env)?;
+    let callback_ctx = ctx.clone();
+    let on_event: platform::DropTargetCallback = Box::new(move |event| {
+        let drop_target_event = DropTargetEvent {
+            event_type: event.phase.as_str().to_string(),
+            target_id: event.target_id,
+            x: event.x,
+            y: event.y,
+            paths: event.paths,
+            timestamp: now_timestamp(),
+            platform: platform_name().to_string(),
+        };
+        let mut calls = Vec::new();
+        if let Ok(state) = callback_ctx.state.lock() {
+            dispatch(&state, &state.drop_target_callbacks, &drop_target_event, &mut calls);
+        }
+        deliver(&callback_ctx, calls);
+    });
+
+    let id = platform::attach_drop_target(handle, on_event).map_err(|message| Error::new(Status::GenericFailure, message))?;
+    ctx.lock()?.drop_targets.push(id);
+    Ok(id)
 }
 
+// 卸载后恢复窗口原来的拖放处理；id 不存在时返回 false
 #[napi]
-pub fn on_mouse_event(callback: JsFunction) -> Result<u32> {
-    let mut state = UNIFIED_STATE.lock().map_err(|_| Error::new(Status::GenericFailure, "Failed to acquire unified monitor state lock"))?;
+pub fn detach_drop_target(env: Env, id: u32) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let owned = {
+        let mut state = ctx.lock()?;
+        let count = state.drop_targets.len();
+        state.drop_targets.retain(|target_id| *target_id != id);
+        state.drop_targets.len() != count
+    };
+    Ok(owned && platform::detach_drop_target(id))
+}
+
+#[napi(ts_args_type = "callback: (err: null | Error, event: DropTargetEvent) => void")]
+pub fn on_drop_target_event(env: Env, callback: JsFunction) -> Result<u32> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
     let id = state.next_callback_id + 1;
     state.next_callback_id = id;
-    let tsfn: ThreadsafeFunction<MouseEvent, ErrorStrategy::CalleeHandled> = callback.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
-    state.mouse_callbacks.insert(id, tsfn);
+    let tsfn: ThreadsafeFunction<DropTargetEvent, ErrorStrategy::CalleeHandled> = isolate_callback(&env, &ctx, id, callback)?.create_threadsafe_function(state.callback_queue_size, |ctx| Ok(vec![ctx.value]))?;
+    state.drop_target_callbacks.insert(id, Arc::new(tsfn));
     Ok(id)
 }
 
 #[napi]
-pub fn remove_mouse_event_listener(id: u32) -> Result<bool> {
-    let mut state = UNIFIED_STATE.lock().map_err(|_| Error::new(Status::GenericFailure, "Failed to acquire unified monitor state lock"))?;
-    Ok(state.mouse_callbacks.remove(&id).is_some())
+pub fn remove_drop_target_event_listener(env: Env, id: u32) -> Result<bool> {
+    let ctx = monitor_context(&env)?;
+    let mut state = ctx.lock()?;
+    Ok(state.drop_target_callbacks.remove(&id).is_some())
 }
 
+// endregion
 
-// Drag API functions
-#[napi]
-pub fn on_drag_event(callback: JsFunction) -> Result<u32> {
-    let mut state = UNIFIED_STATE.lock().map_err(|_| Error::new(Status::GenericFailure, "Failed to acquire unified monitor state lock"))?;
-    let id = state.next_callback_id + 1;
-    state.next_callback_id = id;
-    let tsfn: ThreadsafeFunction<DragEvent, ErrorStrategy::CalleeHandled> = callback.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
-    state.drag_callbacks.insert(id, tsfn);
-    Ok(id)
+// region: Drag Image (拖拽预览图)
+
+const DEFAULT_DRAG_PREVIEW_SIZE: u32 = 64;
+const MAX_DRAG_PREVIEW_SIZE: u32 = 512;
+
+pub struct DragImageTask {
+    path: String,
+    count: usize,
+    size: u32,
+    badge: bool,
+}
+
+impl Task for DragImageTask {
+    type Output = Vec<u8>;
+    type JsValue = Buffer;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let pixels = platform::file_preview(&self.path, self.size)
+            .ok_or_else(|| Error::new(Status::GenericFailure, format!("Failed to create a preview for {}", self.path)))?;
+        let mut canvas = drag_image::Canvas::new(self.size, pixels);
+        if self.badge && self.count > 1 {
+            canvas.draw_count_badge(self.count);
+        }
+        Ok(canvas.encode_png())
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output.into())
+    }
 }
 
+// 生成拖拽预览图（PNG）：第一个文件的缩略图或图标，多个文件时加数量角标
+// 可写入临时文件后作为 startNativeFileDrag 的 imagePath，也可交给渲染进程绘制自定义拖拽影像
 #[napi]
-pub fn remove_drag_event_listener(id: u32) -> Result<bool> {
-    let mut state = UNIFIED_STATE.lock().map_err(|_| Error::new(Status::GenericFailure, "Failed to acquire unified monitor state lock"))?;
-    Ok(state.drag_callbacks.remove(&id).is_some())
+pub fn create_drag_image(paths: Vec<String>, options: Option<DragImageOptions>) -> Result<AsyncTask<DragImageTask>> {
+    let Some(path) = paths.first() else {
+        return Err(Error::new(Status::InvalidArg, "At least one path is required"));
+    };
+    let size = options.as_ref().and_then(|options| options.size).unwrap_or(DEFAULT_DRAG_PREVIEW_SIZE);
+    if size == 0 || size > MAX_DRAG_PREVIEW_SIZE {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!("Drag image size must be between 1 and {}", MAX_DRAG_PREVIEW_SIZE),
+        ));
+    }
+    Ok(AsyncTask::new(DragImageTask {
+        path: path.clone(),
+        count: paths.len(),
+        size,
+        badge: options.and_then(|options| options.badge).unwrap_or(true),
+    }))
 }
 
-// Unified monitoring functions
-fn start_unified_monitor() -> Result<()> {
-    let mut state = UNIFIED_STATE.lock().map_err(|_| Error::new(Status::GenericFailure, "Failed to acquire unified monitor state lock"))?;
+// endregion
 
-    if state.is_monitoring {
-        return Ok(());
+// region: Screen Color (屏幕取色)
+
+pub struct ScreenColorTask {
+    // 系统全局坐标
+    x: f64,
+    y: f64,
+}
+
+impl Task for ScreenColorTask {
+    type Output = [u8; 4];
+    type JsValue = ScreenColor;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        platform::capture_region(self.x, self.y, 1, 1)
+            .map(|pixels| [pixels[0], pixels[1], pixels[2], pixels[3]])
+            .ok_or_else(|| {
+                Error::new(Status::GenericFailure, format!("Failed to sample screen color at ({}, {})", self.x, self.y))
+            })
     }
 
-    let (shutdown_sender, _shutdown_receiver) = std::sync::mpsc::channel::<()>();
-    state.shutdown_sender = Some(shutdown_sender);
+    fn resolve(&mut self, _env: Env, [r, g, b, a]: Self::Output) -> Result<Self::JsValue> {
+        Ok(ScreenColor {
+            r: r as u32,
+            g: g as u32,
+            b: b as u32,
+            a: a as u32,
+            hex: format!("#{:02x}{:02x}{:02x}", r, g, b),
+        })
+    }
+}
 
-    let handle = thread::spawn(move || {
-        let callback = unified_event_listener();
-        if let Err(error) = listen(callback) {
-            eprintln!("Error listening to input events: {:?}", error);
-        }
-    });
+// 取屏幕上 (x, y) 处的颜色，坐标与事件坐标一致（相对参考显示器），用于取色器等功能
+#[napi]
+pub fn get_screen_color_at(env: Env, x: f64, y: f64) -> Result<AsyncTask<ScreenColorTask>> {
+    let context = monitor_context(&env)?;
+    let state = context.lock()?;
+    let (x, y) = to_global_coordinates(&state, (x, y));
+    Ok(AsyncTask::new(ScreenColorTask { x, y }))
+}
 
-    state.monitor_handle = Some(handle);
-    state.is_monitoring = true;
-    Ok(())
+// endregion
+
+// region: Screen Capture (屏幕截取)
+
+const MAX_CAPTURE_EXTENT: u32 = 16384;
+
+pub struct ScreenCaptureTask {
+    // 系统全局坐标
+    x: f64,
+    y: f64,
+    width: u32,
+    height: u32,
+    format: CaptureFormat,
 }
 
-fn stop_unified_monitor() -> Result<()> {
-    let mut state = UNIFIED_STATE.lock().map_err(|_| Error::new(Status::GenericFailure, "Failed to acquire unified monitor state lock"))?;
+impl Task for ScreenCaptureTask {
+    type Output = Vec<u8>;
+    type JsValue = Buffer;
 
-    if !state.is_monitoring {
-        return Ok(());
+    fn compute(&mut self) -> Result<Self::Output> {
+        let pixels = platform::capture_region(self.x, self.y, self.width, self.height).ok_or_else(|| {
+            Error::new(
+                Status::GenericFailure,
+                format!("Failed to capture screen region {}x{} at ({}, {})", self.width, self.height, self.x, self.y),
+            )
+        })?;
+        Ok(match self.format {
+            CaptureFormat::Rgba => pixels,
+            CaptureFormat::Png => drag_image::encode_png(self.width, self.height, &pixels),
+        })
     }
 
-    if let Some(sender) = state.shutdown_sender.take() {
-        let _ = sender.send(());
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output.into())
     }
+}
 
-    if let Some(handle) = state.monitor_handle.take() {
-        let _ = handle.join();
+// 截取屏幕上的矩形区域（例如放下点周围），在线程池上执行，不阻塞 Node 主线程
+#[napi]
+pub fn capture_screen_region(
+    env: Env,
+    region: ScreenRegion,
+    format: Option<CaptureFormat>,
+) -> Result<AsyncTask<ScreenCaptureTask>> {
+    if region.width == 0 || region.height == 0 || region.width > MAX_CAPTURE_EXTENT || region.height > MAX_CAPTURE_EXTENT {
+        return Err(Error::new(
+            Status::InvalidArg,
+            format!("Capture width and height must be between 1 and {}", MAX_CAPTURE_EXTENT),
+        ));
     }
+    let ctx = monitor_context(&env)?;
+    let state = ctx.lock()?;
+    let (x, y) = match region.display_id {
+        Some(id) => {
+            let (origin_x, origin_y) = platform::display_origin(id)
+                .ok_or_else(|| Error::new(Status::InvalidArg, format!("Unknown display id: {}", id)))?;
+            state.coordinates.to_native((region.x + origin_x, region.y + origin_y))
+        }
+        None => to_global_coordinates(&state, (region.x, region.y)),
+    };
+    Ok(AsyncTask::new(ScreenCaptureTask {
+        x,
+        y,
+        width: region.width,
+        height: region.height,
+        format: format.unwrap_or(CaptureFormat::Png),
+    }))
+}
 
-    state.is_monitoring = false;
+// endregion
+
+// region: Path Helpers (路径工具)
+
+// 返回系统文件管理器中显示的名称（本地化的系统文件夹名，按用户设置隐藏扩展名）
+#[napi]
+pub fn get_display_name(path: String) -> Result<String> {
+    platform::display_name(&path)
+        .ok_or_else(|| Error::new(Status::InvalidArg, format!("Failed to get display name: {}", path)))
+}
+
+// 规范化路径：转为绝对路径，可选解析符号链接，Windows 下统一分隔符并处理长路径前缀
+#[napi]
+pub fn normalize_path(path: String, options: Option<NormalizePathOptions>) -> Result<String> {
+    let (long_path_prefix, resolve_symlinks) = options
+        .map(|options| (options.long_path_prefix.unwrap_or(false), options.resolve_symlinks.unwrap_or(false)))
+        .unwrap_or((false, false));
+    paths::normalize(&path, long_path_prefix, resolve_symlinks)
+        .map_err(|e| Error::new(Status::InvalidArg, format!("Failed to normalize path {}: {}", path, e)))
+}
+
+// endregion
+
+// region: Event Grab (系统级事件拦截，需启用 grab feature)
+
+// 对每种事件类型调用一次 predicate（而不是对每个事件），返回 true 的类型在系统范围内被吞掉，
+// 其他应用和本插件自己的监听都收不到；再次调用会整体替换之前的选择，返回当前被吞掉的事件类型
+#[cfg(feature = "grab")]
+#[napi(
+    ts_args_type = "predicate: (eventType: 'mousedown' | 'mouseup' | 'mousemove' | 'wheel') => boolean",
+    ts_return_type = "Array<'mousedown' | 'mouseup' | 'mousemove' | 'wheel'>"
+)]
+pub fn grab_mouse(env: Env, predicate: JsFunction) -> Result<Vec<String>> {
+    let mut grabbed = Vec::new();
+    for event_type in grab::EVENT_TYPES {
+        let result = predicate.call(None, &[env.create_string(event_type)?])?;
+        if result.coerce_to_bool()?.get_value()? {
+            grabbed.push(event_type);
+        }
+    }
+    grab::set(&grabbed).map_err(|e| Error::new(Status::GenericFailure, e))?;
+    monitor_context(&env)?.lock()?.holds_mouse_grab = !grabbed.is_empty();
+    Ok(grabbed.into_iter().map(String::from).collect())
+}
+
+// 解除拦截，所有事件恢复放行；拦截线程保持运行，下次 grabMouse 时直接生效
+#[cfg(feature = "grab")]
+#[napi]
+pub fn release_mouse_grab(env: Env) -> Result<()> {
+    grab::release();
+    monitor_context(&env)?.lock()?.holds_mouse_grab = false;
     Ok(())
 }
 
+// endregion
+
+// region: Synthetic Load (合成负载，需启用 synthetic-load feature)
+
+// 在后台线程上按真实时间节奏把合成事件送入监听逻辑，已注册的回调会像收到真实输入一样被调用，
+// 用于应用层的压力测试；与基准测试（benches/drag_state.rs）使用同一个负载生成器
+#[cfg(feature = "synthetic-load")]
+pub struct SyntheticLoadTask {
+    ctx: MonitorContext,
+    rate_hz: u32,
+    duration: Duration,
+}
+
+#[cfg(feature = "synthetic-load")]
+impl Task for SyntheticLoadTask {
+    type Output = u32;
+    type JsValue = u32;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        let events = load::generate(self.rate_hz, self.duration);
+        let count = events.len() as u32;
+        let mut listener = unified_event_listener(self.ctx.clone());
+        let start = Instant::now();
+        for (offset, event_type) in events {
+            // 落后于计划时间时不等待，直接追赶
+            if let Some(wait) = offset.checked_sub(start.elapsed()) {
+                thread::sleep(wait);
+            }
+            // 合成负载模拟的是真实输入，不标记为注入事件
+            listener(Event { time: SystemTime::now(), name: None, event_type, synthetic: false, scroll: None, pointer: None }, monotonic_now_ns());
+        }
+        Ok(count)
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(output)
+    }
+}
+
+// 以 rate_hz 的频率注入 duration_ms 毫秒的合成事件，完成后返回注入的事件数
+// 合成事件会驱动真实的拖拽状态，因此监听真实输入时不允许运行
+#[cfg(feature = "synthetic-load")]
 #[napi]
-pub fn is_monitoring() -> bool {
-    UNIFIED_STATE.lock().unwrap().is_monitoring
+pub fn run_synthetic_load(env: Env, rate_hz: u32, duration_ms: u32) -> Result<AsyncTask<SyntheticLoadTask>> {
+    let ctx = monitor_context(&env)?;
+    if ctx.lock()?.is_monitoring {
+        return Err(Error::new(Status::GenericFailure, "Cannot run synthetic load while the mouse monitor is running"));
+    }
+    Ok(AsyncTask::new(SyntheticLoadTask {
+        ctx,
+        rate_hz,
+        duration: Duration::from_millis(duration_ms as u64),
+    }))
 }
 
 // endregion
 
+// region: Test Injection (测试注入，需启用 test-injection feature)
+
+// __injectEvent 接受的事件：MouseEvent/WheelEvent 的形状，或者 DragEvent 的形状（按对应的鼠标输入展开）
+// 坐标为系统坐标（与 setCoordinateSpace 无关），button 使用 native 编号
+#[cfg(feature = "test-injection")]
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct InjectedEvent {
+    event_type: String,
+    x: f64,
+    y: f64,
+    #[serde(default)]
+    start_x: Option<f64>,
+    #[serde(default)]
+    start_y: Option<f64>,
+    #[serde(default = "default_injected_button")]
+    button: i32,
+    #[serde(default)]
+    delta_x: f64,
+    #[serde(default)]
+    delta_y: f64,
+    #[serde(default)]
+    is_synthetic: bool,
+}
+
+#[cfg(feature = "test-injection")]
+fn default_injected_button() -> i32 {
+    1
+}
+
+#[cfg(feature = "test-injection")]
+fn injected_event_types(event: &InjectedEvent) -> std::result::Result<Vec<EventType>, String> {
+    let button = match event.button {
+        1 => Button::Left,
+        2 => Button::Middle,
+        3 => Button::Right,
+        4 => Button::Back,
+        5 => Button::Forward,
+        b => Button::Unknown(u8::try_from(b).map_err(|_| format!("Invalid button: {}", b))?),
+    };
+    let move_to = |x: f64, y: f64| EventType::MouseMove { x, y };
+    let event_types = match event.event_type.as_str() {
+        "mousedown" => vec![EventType::ButtonPress(button)],
+        "mouseup" => vec![EventType::ButtonRelease(button)],
+        "mousemove" | "dragmove" => vec![move_to(event.x, event.y)],
+        // WheelEvent 使用 DOM 约定（正值向下/向右），rdev 中正值表示向上/向右，且只有整格位移
+        "wheel" => vec![EventType::Wheel { delta_x: event.delta_x.round() as i64, delta_y: -event.delta_y.round() as i64 }],
+        // 在起点按下后移动到 (x, y)，移动距离超过阈值时产生 dragstart
+        "dragstart" => {
+            let start = (event.start_x.unwrap_or(event.x), event.start_y.unwrap_or(event.y));
+            vec![move_to(start.0, start.1), EventType::ButtonPress(button), move_to(event.x, event.y)]
+        }
+        "dragend" => vec![move_to(event.x, event.y), EventType::ButtonRelease(button)],
+        other => return Err(format!("Cannot inject event type: {}", other)),
+    };
+    Ok(event_types)
+}
+
+// 把一个伪造的输入事件同步送入监听逻辑，用于在没有真实硬件和系统权限的环境里测试拖拽状态机和阈值
+// 注入的事件会驱动真实的拖拽状态，因此监听真实输入时不允许调用；返回送入的 rdev 事件数
+#[cfg(feature = "test-injection")]
+#[napi(js_name = "__injectEvent")]
+pub fn inject_event(env: Env, event_json: String) -> Result<u32> {
+    let event: InjectedEvent = serde_json::from_str(&event_json)
+        .map_err(|e| Error::new(Status::InvalidArg, format!("Invalid injected event: {}", e)))?;
+    let event_types = injected_event_types(&event).map_err(|e| Error::new(Status::InvalidArg, e))?;
+
+    let ctx = monitor_context(&env)?;
+    if ctx.lock()?.is_monitoring {
+        return Err(Error::new(Status::GenericFailure, "Cannot inject events while the mouse monitor is running"));
+    }
+    let count = event_types.len() as u32;
+    let mut listener = unified_event_listener(ctx);
+    for event_type in event_types {
+        listener(Event { time: SystemTime::now(), name: None, event_type, synthetic: event.is_synthetic, scroll: None, pointer: None }, monotonic_now_ns());
+    }
+    Ok(count)
+}
+
+// endregion
@@ -0,0 +1,72 @@
+// 合成输入负载：按固定频率生成一串鼠标/滚轮事件，用于基准测试和应用层压力测试
+// 事件序列循环重复：按下 -> 拖动超过阈值 -> 松开 -> 悬停移动 -> 滚轮
+
+use rdev::{Button, EventType};
+use std::time::Duration;
+
+// 一个循环包含的事件数
+const CYCLE_LEN: u64 = 64;
+// 拖动阶段每个事件移动的像素数
+const DRAG_STEP: f64 = 2.0;
+
+// 第 index 个合成事件
+pub fn event_at(index: u64) -> EventType {
+    let cycle = index / CYCLE_LEN;
+    let base_x = 100.0 + (cycle % 16) as f64 * 10.0;
+    let base_y = 100.0;
+    match index % CYCLE_LEN {
+        0 => EventType::ButtonPress(Button::Left),
+        step @ 1..=40 => EventType::MouseMove { x: base_x + step as f64 * DRAG_STEP, y: base_y + step as f64 },
+        41 => EventType::ButtonRelease(Button::Left),
+        step @ 42..=55 => EventType::MouseMove { x: base_x + 80.0 - (step - 41) as f64, y: base_y + 40.0 },
+        _ => EventType::Wheel { delta_x: 0, delta_y: -1 },
+    }
+}
+
+// 按 rate_hz 生成 duration 时长内的事件，返回（相对起点的发送时间，事件）
+pub fn generate(rate_hz: u32, duration: Duration) -> Vec<(Duration, EventType)> {
+    if rate_hz == 0 {
+        return Vec::new();
+    }
+    let count = (duration.as_secs_f64() * rate_hz as f64) as u64;
+    let interval = Duration::from_secs_f64(1.0 / rate_hz as f64);
+    (0..count)
+        .map(|i| (interval.mul_f64(i as f64), event_at(i)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycle_layout() {
+        assert_eq!(event_at(0), EventType::ButtonPress(Button::Left));
+        assert_eq!(event_at(1), EventType::MouseMove { x: 102.0, y: 101.0 });
+        assert_eq!(event_at(41), EventType::ButtonRelease(Button::Left));
+        assert_eq!(event_at(42), EventType::MouseMove { x: 179.0, y: 140.0 });
+        assert_eq!(event_at(CYCLE_LEN - 1), EventType::Wheel { delta_x: 0, delta_y: -1 });
+        // 下一个循环整体平移
+        assert_eq!(event_at(CYCLE_LEN), EventType::ButtonPress(Button::Left));
+        assert_eq!(event_at(CYCLE_LEN + 1), EventType::MouseMove { x: 112.0, y: 101.0 });
+    }
+
+    #[test]
+    fn test_every_press_is_released() {
+        let events: Vec<EventType> = (0..CYCLE_LEN * 3).map(event_at).collect();
+        let presses = events.iter().filter(|event| matches!(event, EventType::ButtonPress(_))).count();
+        let releases = events.iter().filter(|event| matches!(event, EventType::ButtonRelease(_))).count();
+        assert_eq!((presses, releases), (3, 3));
+    }
+
+    #[test]
+    fn test_generate() {
+        assert!(generate(0, Duration::from_secs(1)).is_empty());
+
+        let events = generate(100, Duration::from_millis(500));
+        assert_eq!(events.len(), 50);
+        assert_eq!(events[0], (Duration::ZERO, event_at(0)));
+        assert_eq!(events[10].0, Duration::from_millis(100));
+        assert!(events.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+}
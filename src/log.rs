@@ -0,0 +1,150 @@
+// 插件内部日志：按级别过滤后写入日志文件和各个接收端（每个注册了 onLog 的 Node 环境一个）
+// 级别、日志文件和接收端列表都是进程级的：日志不区分来自哪个环境，每条都交给所有接收端；
+// 既没有写入日志文件、也没有任何接收端接收时写到 stderr，与之前直接打印的行为一致
+
+use std::cell::Cell;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+}
+
+impl Level {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Level> {
+        match name {
+            "error" => Some(Level::Error),
+            "warn" => Some(Level::Warn),
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub level: Level,
+    // 产生日志的子系统，例如 "monitor"、"clipboard"
+    pub target: &'static str,
+    pub message: String,
+    pub timestamp: f64,
+}
+
+// 返回记录是否被实际交给了 JS（环境没有 onLog 回调时为 false）
+// 调用时不持有接收端列表的锁；接收端内部再写的日志不会交给接收端，只写入日志文件或 stderr
+pub type Sink = Arc<dyn Fn(&Record) -> bool + Send + Sync>;
+
+// 0 表示关闭
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+static SINKS: Mutex<Vec<(u32, Sink)>> = Mutex::new(Vec::new());
+static NEXT_SINK_ID: AtomicU32 = AtomicU32::new(1);
+static FILE: Mutex<Option<File>> = Mutex::new(None);
+
+thread_local! {
+    // 当前线程是否正在调用接收端，防止接收端写日志时递归
+    static IN_SINK: Cell<bool> = const { Cell::new(false) };
+}
+
+// None 关闭所有日志
+pub fn set_level(level: Option<Level>) {
+    LEVEL.store(level.map_or(0, |level| level as u8), Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+// 以追加方式打开日志文件，None 关闭文件输出
+pub fn set_file(path: Option<&Path>) -> Result<(), String> {
+    let file = match path {
+        Some(path) => Some(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| format!("Failed to open log file {}: {}", path.display(), e))?,
+        ),
+        None => None,
+    };
+    *FILE.lock().map_err(|_| "Log file state poisoned".to_string())? = file;
+    Ok(())
+}
+
+pub fn add_sink(sink: Sink) -> u32 {
+    let id = NEXT_SINK_ID.fetch_add(1, Ordering::Relaxed);
+    if let Ok(mut sinks) = SINKS.lock() {
+        sinks.push((id, sink));
+    }
+    id
+}
+
+pub fn remove_sink(id: u32) {
+    if let Ok(mut sinks) = SINKS.lock() {
+        sinks.retain(|(sink_id, _)| *sink_id != id);
+    }
+}
+
+pub fn log(level: Level, target: &'static str, message: impl Into<String>) {
+    if !enabled(level) {
+        return;
+    }
+    let record = Record {
+        level,
+        target,
+        message: message.into(),
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs_f64(),
+    };
+    let line = format!("[{}] {} {}: {}", record.timestamp, record.level.as_str(), record.target, record.message);
+
+    let mut delivered = false;
+    if let Ok(mut file) = FILE.lock() {
+        if let Some(file) = file.as_mut() {
+            delivered = writeln!(file, "{}", line).is_ok();
+        }
+    }
+    if !IN_SINK.get() {
+        // 先复制列表再释放锁，接收端里获取的其他锁不会与 SINKS 互相等待
+        let sinks: Vec<Sink> = match SINKS.lock() {
+            Ok(sinks) => sinks.iter().map(|(_, sink)| sink.clone()).collect(),
+            Err(_) => Vec::new(),
+        };
+        IN_SINK.set(true);
+        for sink in sinks {
+            delivered |= sink(&record);
+        }
+        IN_SINK.set(false);
+    }
+    if !delivered {
+        eprintln!("{}", line);
+    }
+}
+
+pub fn error(target: &'static str, message: impl Into<String>) {
+    log(Level::Error, target, message);
+}
+
+pub fn info(target: &'static str, message: impl Into<String>) {
+    log(Level::Info, target, message);
+}
+
+pub fn debug(target: &'static str, message: impl Into<String>) {
+    log(Level::Debug, target, message);
+}
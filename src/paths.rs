@@ -0,0 +1,91 @@
+// 路径规范化，与拖拽/拖出接口报告的路径格式保持一致
+// Windows 下处理长路径前缀：\\?\C:\... 与 \\?\UNC\server\share\...
+
+use std::path::{self, Path};
+
+const VERBATIM_PREFIX: &str = r"\\?\";
+const VERBATIM_UNC_PREFIX: &str = r"\\?\UNC\";
+
+// 加上长路径前缀，已有前缀时保持不变
+fn add_long_path_prefix(path: &str) -> String {
+    if path.starts_with(VERBATIM_PREFIX) {
+        path.to_string()
+    } else if let Some(unc) = path.strip_prefix(r"\\") {
+        format!("{}{}", VERBATIM_UNC_PREFIX, unc)
+    } else {
+        format!("{}{}", VERBATIM_PREFIX, path)
+    }
+}
+
+// 去掉长路径前缀，还原为常规形式
+fn strip_long_path_prefix(path: &str) -> String {
+    if let Some(unc) = path.strip_prefix(VERBATIM_UNC_PREFIX) {
+        format!(r"\\{}", unc)
+    } else if let Some(rest) = path.strip_prefix(VERBATIM_PREFIX) {
+        rest.to_string()
+    } else {
+        path.to_string()
+    }
+}
+
+// 转为绝对路径；resolve_symlinks 时解析符号链接（要求路径存在）；
+// Windows 下统一分隔符为 \，并按 long_path_prefix 加上或去掉 \\?\ 前缀，其他平台忽略该选项
+pub fn normalize(path: &str, long_path_prefix: bool, resolve_symlinks: bool) -> std::io::Result<String> {
+    let path = Path::new(path);
+    let absolute = if resolve_symlinks {
+        std::fs::canonicalize(path)?
+    } else {
+        path::absolute(path)?
+    };
+    let normalized = absolute.to_string_lossy().into_owned();
+
+    if !cfg!(target_os = "windows") {
+        return Ok(normalized);
+    }
+    let normalized = strip_long_path_prefix(&normalized.replace('/', r"\"));
+    Ok(if long_path_prefix {
+        add_long_path_prefix(&normalized)
+    } else {
+        normalized
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_long_path_prefix() {
+        assert_eq!(add_long_path_prefix(r"C:\Users\a.txt"), r"\\?\C:\Users\a.txt");
+        assert_eq!(add_long_path_prefix(r"\\server\share\a.txt"), r"\\?\UNC\server\share\a.txt");
+        assert_eq!(add_long_path_prefix(r"\\?\C:\Users\a.txt"), r"\\?\C:\Users\a.txt");
+        assert_eq!(add_long_path_prefix(r"\\?\UNC\server\share\a.txt"), r"\\?\UNC\server\share\a.txt");
+    }
+
+    #[test]
+    fn test_strip_long_path_prefix() {
+        assert_eq!(strip_long_path_prefix(r"\\?\C:\Users\a.txt"), r"C:\Users\a.txt");
+        assert_eq!(strip_long_path_prefix(r"\\?\UNC\server\share\a.txt"), r"\\server\share\a.txt");
+        assert_eq!(strip_long_path_prefix(r"C:\Users\a.txt"), r"C:\Users\a.txt");
+        assert_eq!(strip_long_path_prefix(r"\\server\share\a.txt"), r"\\server\share\a.txt");
+    }
+
+    #[test]
+    fn test_prefix_round_trip() {
+        for path in [r"C:\Users\a.txt", r"\\server\share\a.txt"] {
+            assert_eq!(strip_long_path_prefix(&add_long_path_prefix(path)), path);
+        }
+    }
+
+    #[test]
+    fn test_normalize_makes_absolute() {
+        let normalized = normalize("some/relative.txt", false, false).unwrap();
+        assert!(Path::new(&normalized).is_absolute());
+        assert!(normalized.ends_with("relative.txt"));
+    }
+
+    #[test]
+    fn test_normalize_resolve_symlinks_requires_existing_path() {
+        assert!(normalize("does/not/exist.txt", false, true).is_err());
+    }
+}
@@ -0,0 +1,896 @@
+use super::{
+    ClipboardCallback, ClipboardContents, DisplayInfo, DropEffect, DropTargetCallback, FileDragCallback, FilePromiseProvider,
+    FrontmostApp, GestureCallback, PromisedFile, Rect, Theme, WindowRegion,
+};
+use std::ffi::{CStr, CString};
+use std::os::raw::c_ulong;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+use x11::{xfixes, xlib};
+
+// X11 下没有统一的标题栏命中测试接口（装饰由窗口管理器绘制），暂不分类
+pub fn window_region_at(_x: f64, _y: f64) -> WindowRegion {
+    WindowRegion::Unknown
+}
+
+// 窗口所属进程只能通过客户端自愿设置的 _NET_WM_PID 推断，且窗口管理器会重新设置父窗口，暂不支持
+pub fn window_pid_at(_x: f64, _y: f64) -> Option<u32> {
+    None
+}
+
+// XQueryPointer 的结果：根窗口坐标和按键掩码
+fn query_pointer() -> Option<(f64, f64, u32)> {
+    unsafe {
+        let display = xlib::XOpenDisplay(ptr::null());
+        if display.is_null() {
+            return None;
+        }
+        let root = xlib::XDefaultRootWindow(display);
+        let (mut root_return, mut child_return) = (0, 0);
+        let (mut root_x, mut root_y, mut win_x, mut win_y) = (0, 0, 0, 0);
+        let mut mask = 0;
+        let found = xlib::XQueryPointer(
+            display,
+            root,
+            &mut root_return,
+            &mut child_return,
+            &mut root_x,
+            &mut root_y,
+            &mut win_x,
+            &mut win_y,
+            &mut mask,
+        );
+        xlib::XCloseDisplay(display);
+        if found == 0 {
+            return None;
+        }
+        Some((root_x as f64, root_y as f64, mask))
+    }
+}
+
+// 根窗口或普通窗口上 CARDINAL/WINDOW 类型属性的前 max_items 个值，属性不存在时返回空数组
+fn window_property_values(display: *mut xlib::Display, window: xlib::Window, name: &str, max_items: i64) -> Vec<c_ulong> {
+    let Ok(name) = CString::new(name) else {
+        return Vec::new();
+    };
+    unsafe {
+        let atom = xlib::XInternAtom(display, name.as_ptr(), xlib::True);
+        if atom == 0 {
+            return Vec::new();
+        }
+        let (mut actual_type, mut actual_format, mut item_count, mut bytes_after) = (0, 0, 0, 0);
+        let mut data: *mut u8 = ptr::null_mut();
+        let status = xlib::XGetWindowProperty(
+            display,
+            window,
+            atom,
+            0,
+            max_items,
+            xlib::False,
+            xlib::AnyPropertyType as xlib::Atom,
+            &mut actual_type,
+            &mut actual_format,
+            &mut item_count,
+            &mut bytes_after,
+            &mut data,
+        );
+        if data.is_null() {
+            return Vec::new();
+        }
+        // 32 位格式的属性在客户端以 long 数组返回
+        let values = if status == xlib::Success as i32 && actual_format == 32 {
+            std::slice::from_raw_parts(data as *const c_ulong, item_count as usize).to_vec()
+        } else {
+            Vec::new()
+        };
+        xlib::XFree(data as *mut _);
+        values
+    }
+}
+
+fn window_property_value(display: *mut xlib::Display, window: xlib::Window, name: &str) -> Option<c_ulong> {
+    window_property_values(display, window, name, 1).first().copied()
+}
+
+// 由窗口管理器的 _NET_ACTIVE_WINDOW 和窗口的 _NET_WM_PID 找到前台进程，再读取 /proc 下的可执行文件
+// 窗口管理器不支持 EWMH 或窗口没有设置 PID 时返回 None
+pub fn frontmost_app() -> Option<FrontmostApp> {
+    let pid = unsafe {
+        let display = xlib::XOpenDisplay(ptr::null());
+        if display.is_null() {
+            return None;
+        }
+        let root = xlib::XDefaultRootWindow(display);
+        let pid = window_property_value(display, root, "_NET_ACTIVE_WINDOW")
+            .filter(|window| *window != 0)
+            .and_then(|window| window_property_value(display, window, "_NET_WM_PID"));
+        xlib::XCloseDisplay(display);
+        pid?
+    };
+    let executable = std::fs::read_link(format!("/proc/{}/exe", pid)).ok()?;
+    Some(FrontmostApp::from_executable(pid as u32, executable))
+}
+
+unsafe extern "C" fn ignore_x_error(_display: *mut xlib::Display, _event: *mut xlib::XErrorEvent) -> i32 {
+    0
+}
+
+// 临时替换 Xlib 的错误处理函数，避免查询已销毁的窗口时 BadWindow 错误终止进程
+unsafe fn ignoring_x_errors<T>(display: *mut xlib::Display, f: impl FnOnce() -> T) -> T {
+    let previous = xlib::XSetErrorHandler(Some(ignore_x_error));
+    let result = f();
+    xlib::XSync(display, xlib::False);
+    xlib::XSetErrorHandler(previous);
+    result
+}
+
+// 窗口内容区在根窗口上的位置和大小，不含窗口管理器的边框
+unsafe fn root_geometry(display: *mut xlib::Display, window: xlib::Window) -> Option<Rect> {
+    let mut attributes: xlib::XWindowAttributes = std::mem::zeroed();
+    if xlib::XGetWindowAttributes(display, window, &mut attributes) == 0 {
+        return None;
+    }
+    let (mut x, mut y, mut child) = (0, 0, 0);
+    if xlib::XTranslateCoordinates(display, window, attributes.root, 0, 0, &mut x, &mut y, &mut child) == 0 {
+        return None;
+    }
+    Some(Rect {
+        x: x as f64,
+        y: y as f64,
+        width: attributes.width as f64,
+        height: attributes.height as f64,
+    })
+}
+
+// handle 为 X11 窗口 ID（BrowserWindow.getNativeWindowHandle() 的值）
+pub fn window_bounds(handle: usize) -> Option<Rect> {
+    if handle == 0 {
+        return None;
+    }
+    unsafe {
+        let display = xlib::XOpenDisplay(ptr::null());
+        if display.is_null() {
+            return None;
+        }
+        let bounds = ignoring_x_errors(display, || root_geometry(display, handle as xlib::Window));
+        xlib::XCloseDisplay(display);
+        bounds
+    }
+}
+
+// 窗口管理器的 _NET_CLIENT_LIST_STACKING 按从下到上的顺序列出顶层窗口，取 _NET_WM_PID 匹配的最上层窗口；
+// 窗口管理器不支持 EWMH 或窗口没有设置 PID 时返回 None
+pub fn process_window_bounds(pid: u32) -> Option<Rect> {
+    unsafe {
+        let display = xlib::XOpenDisplay(ptr::null());
+        if display.is_null() {
+            return None;
+        }
+        let root = xlib::XDefaultRootWindow(display);
+        let bounds = ignoring_x_errors(display, || {
+            window_property_values(display, root, "_NET_CLIENT_LIST_STACKING", 4096)
+                .into_iter()
+                .rev()
+                .find(|window| window_property_value(display, *window, "_NET_WM_PID") == Some(c_ulong::from(pid)))
+                .and_then(|window| root_geometry(display, window))
+        });
+        xlib::XCloseDisplay(display);
+        bounds
+    }
+}
+
+pub fn cursor_position() -> Option<(f64, f64)> {
+    query_pointer().map(|(x, y, _)| (x, y))
+}
+
+// X11 根窗口覆盖所有显示器，统一报告为 0
+pub fn display_id_at(_x: f64, _y: f64) -> u32 {
+    0
+}
+
+// 只有根窗口这一个“显示器”，原点即全局原点
+pub fn display_origin(display_id: u32) -> Option<(f64, f64)> {
+    (display_id == 0).then_some((0.0, 0.0))
+}
+
+// X11 默认屏幕的尺寸（像素）
+pub fn primary_display_size() -> Option<(f64, f64)> {
+    rdev::display_size().ok().map(|(width, height)| (width as f64, height as f64))
+}
+
+// 与 display_id_at 一致，整个根窗口作为唯一的显示器报告
+pub fn displays() -> Vec<DisplayInfo> {
+    let Some((width, height)) = primary_display_size() else {
+        return Vec::new();
+    };
+    let bounds = Rect {
+        x: 0.0,
+        y: 0.0,
+        width,
+        height,
+    };
+    vec![DisplayInfo {
+        id: 0,
+        bounds,
+        work_area: bounds,
+        scale_factor: 1.0,
+        primary: true,
+    }]
+}
+
+// 显示器 ID 与系统坐标下的 bounds，供事件的 displayId 使用
+pub fn display_bounds() -> Vec<(u32, Rect)> {
+    displays().into_iter().map(|display| (display.id, display.bounds)).collect()
+}
+
+pub fn primary_button_pressed() -> bool {
+    query_pointer()
+        .map(|(_, _, mask)| mask & xlib::Button1Mask != 0)
+        .unwrap_or(false)
+}
+
+// X11 下监听输入不需要额外授权
+pub fn input_permission_granted() -> Option<bool> {
+    None
+}
+
+// 只有 macOS 提供可轮询的系统拖拽剪贴板
+pub fn drag_pasteboard_change_count() -> Option<i64> {
+    None
+}
+
+pub fn drag_pasteboard_has_files() -> bool {
+    false
+}
+
+// X11 没有统一的主题设置（取决于桌面环境），统一报告为未知
+pub fn system_theme() -> Theme {
+    Theme {
+        dark: None,
+        accent_color: None,
+    }
+}
+
+// 拖出文件：作为 XDND 源（协议版本 5）提供 text/uri-list。
+// 按下发生在应用自己的窗口里，指针已被该窗口隐式抓取，这里无法再抓取指针，
+// 因此用 XQueryPointer 轮询光标位置和按键状态，松开主按键即视为放下。
+
+const XDND_VERSION: i64 = 5;
+const XDND_POLL_INTERVAL: Duration = Duration::from_millis(10);
+const XDND_FINISH_TIMEOUT: Duration = Duration::from_secs(5);
+
+struct XdndAtoms {
+    aware: xlib::Atom,
+    selection: xlib::Atom,
+    enter: xlib::Atom,
+    position: xlib::Atom,
+    status: xlib::Atom,
+    leave: xlib::Atom,
+    drop: xlib::Atom,
+    finished: xlib::Atom,
+    action_copy: xlib::Atom,
+    action_move: xlib::Atom,
+    action_link: xlib::Atom,
+    uri_list: xlib::Atom,
+    targets: xlib::Atom,
+}
+
+impl XdndAtoms {
+    unsafe fn new(display: *mut xlib::Display) -> Self {
+        let atom = |name: &str| {
+            let name = CString::new(name).unwrap();
+            xlib::XInternAtom(display, name.as_ptr(), xlib::False)
+        };
+        Self {
+            aware: atom("XdndAware"),
+            selection: atom("XdndSelection"),
+            enter: atom("XdndEnter"),
+            position: atom("XdndPosition"),
+            status: atom("XdndStatus"),
+            leave: atom("XdndLeave"),
+            drop: atom("XdndDrop"),
+            finished: atom("XdndFinished"),
+            action_copy: atom("XdndActionCopy"),
+            action_move: atom("XdndActionMove"),
+            action_link: atom("XdndActionLink"),
+            uri_list: atom("text/uri-list"),
+            targets: atom("TARGETS"),
+        }
+    }
+
+    fn effect(&self, action: xlib::Atom) -> DropEffect {
+        if action == self.action_move {
+            DropEffect::Move
+        } else if action == self.action_link {
+            DropEffect::Link
+        } else if action == 0 {
+            DropEffect::None
+        } else {
+            DropEffect::Copy
+        }
+    }
+}
+
+// 目标在 XDND 会话中的状态
+struct XdndTarget {
+    window: xlib::Window,
+    version: i64,
+    accepted: bool,
+    action: xlib::Atom,
+    // 已发出 XdndPosition、尚未收到 XdndStatus
+    awaiting_status: bool,
+}
+
+// 按 RFC 8089 生成 file URI，只保留非保留字符和路径分隔符
+fn file_uri(path: &str) -> String {
+    let mut uri = String::from("file://");
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => uri.push(byte as char),
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+// 从根窗口逐层向下，找到光标下第一个声明 XdndAware 的窗口及其协议版本
+unsafe fn xdnd_target_at(display: *mut xlib::Display, atoms: &XdndAtoms, root: xlib::Window, x: i32, y: i32) -> Option<(xlib::Window, i64)> {
+    let mut window = root;
+    loop {
+        let mut actual_type = 0;
+        let mut actual_format = 0;
+        let (mut count, mut remaining) = (0, 0);
+        let mut data: *mut u8 = ptr::null_mut();
+        let status = xlib::XGetWindowProperty(
+            display,
+            window,
+            atoms.aware,
+            0,
+            1,
+            xlib::False,
+            xlib::XA_ATOM,
+            &mut actual_type,
+            &mut actual_format,
+            &mut count,
+            &mut remaining,
+            &mut data,
+        );
+        if !data.is_null() {
+            let version = if status == xlib::Success as i32 && count > 0 {
+                Some(*(data as *const std::os::raw::c_ulong) as i64)
+            } else {
+                None
+            };
+            xlib::XFree(data as *mut _);
+            if let Some(version) = version {
+                return Some((window, version.min(XDND_VERSION)));
+            }
+        }
+
+        let (mut child_x, mut child_y, mut child) = (0, 0, 0);
+        if xlib::XTranslateCoordinates(display, root, window, x, y, &mut child_x, &mut child_y, &mut child) == 0 || child == 0 {
+            return None;
+        }
+        window = child;
+    }
+}
+
+unsafe fn send_xdnd_message(display: *mut xlib::Display, target: xlib::Window, message_type: xlib::Atom, data: [i64; 5]) {
+    let mut event: xlib::XEvent = std::mem::zeroed();
+    event.client_message.type_ = xlib::ClientMessage;
+    event.client_message.display = display;
+    event.client_message.window = target;
+    event.client_message.message_type = message_type;
+    event.client_message.format = 32;
+    for (index, value) in data.iter().enumerate() {
+        event.client_message.data.set_long(index, *value as _);
+    }
+    xlib::XSendEvent(display, target, xlib::False, xlib::NoEventMask, &mut event);
+}
+
+// 响应对 XdndSelection 或 CLIPBOARD 的数据请求，只提供 text/uri-list
+unsafe fn answer_selection_request(display: *mut xlib::Display, atoms: &XdndAtoms, request: &xlib::XSelectionRequestEvent, uri_list: &str) {
+    let property = if request.property == 0 { request.target } else { request.property };
+    let mut notify: xlib::XEvent = std::mem::zeroed();
+    notify.selection.type_ = xlib::SelectionNotify;
+    notify.selection.display = display;
+    notify.selection.requestor = request.requestor;
+    notify.selection.selection = request.selection;
+    notify.selection.target = request.target;
+    notify.selection.time = request.time;
+    notify.selection.property = property;
+
+    if request.target == atoms.uri_list {
+        xlib::XChangeProperty(
+            display,
+            request.requestor,
+            property,
+            atoms.uri_list,
+            8,
+            xlib::PropModeReplace,
+            uri_list.as_ptr(),
+            uri_list.len() as i32,
+        );
+    } else if request.target == atoms.targets {
+        let targets = [atoms.targets, atoms.uri_list];
+        xlib::XChangeProperty(
+            display,
+            request.requestor,
+            property,
+            xlib::XA_ATOM,
+            32,
+            xlib::PropModeReplace,
+            targets.as_ptr() as *const u8,
+            targets.len() as i32,
+        );
+    } else {
+        notify.selection.property = 0;
+    }
+    xlib::XSendEvent(display, request.requestor, xlib::False, xlib::NoEventMask, &mut notify);
+}
+
+// 处理发给源窗口的事件，返回收到的 XdndFinished（目标是否接受，执行的动作）
+unsafe fn pump_xdnd_events(
+    display: *mut xlib::Display,
+    atoms: &XdndAtoms,
+    target: &mut Option<XdndTarget>,
+    uri_list: &str,
+) -> Option<(bool, xlib::Atom)> {
+    let mut finished = None;
+    while xlib::XPending(display) > 0 {
+        let mut event: xlib::XEvent = std::mem::zeroed();
+        xlib::XNextEvent(display, &mut event);
+        match event.get_type() {
+            xlib::SelectionRequest => answer_selection_request(display, atoms, &event.selection_request, uri_list),
+            xlib::ClientMessage => {
+                let message = event.client_message;
+                let sender = message.data.get_long(0) as xlib::Window;
+                let Some(current) = target.as_mut().filter(|t| t.window == sender) else {
+                    continue;
+                };
+                if message.message_type == atoms.status {
+                    current.awaiting_status = false;
+                    current.accepted = message.data.get_long(1) & 1 != 0;
+                    current.action = message.data.get_long(4) as xlib::Atom;
+                } else if message.message_type == atoms.finished {
+                    // 版本 5 起 XdndFinished 携带是否成功和最终动作
+                    finished = if current.version >= 5 {
+                        Some((message.data.get_long(1) & 1 != 0, message.data.get_long(2) as xlib::Atom))
+                    } else {
+                        Some((current.accepted, current.action))
+                    };
+                }
+            }
+            _ => {}
+        }
+    }
+    finished
+}
+
+unsafe fn run_xdnd_source(paths: &[String]) -> Result<DropEffect, String> {
+    let display = xlib::XOpenDisplay(ptr::null());
+    if display.is_null() {
+        return Err("Failed to open X display".to_string());
+    }
+    let result = run_xdnd_session(display, paths);
+    xlib::XCloseDisplay(display);
+    result
+}
+
+unsafe fn run_xdnd_session(display: *mut xlib::Display, paths: &[String]) -> Result<DropEffect, String> {
+    let atoms = XdndAtoms::new(display);
+    let root = xlib::XDefaultRootWindow(display);
+    let uri_list: String = paths.iter().map(|path| file_uri(path) + "\r\n").collect();
+
+    // 不映射的窗口，只用来持有 XdndSelection 和接收目标的回复
+    let source = xlib::XCreateSimpleWindow(display, root, -10, -10, 1, 1, 0, 0, 0);
+    xlib::XSetSelectionOwner(display, atoms.selection, source, xlib::CurrentTime);
+
+    let mut target: Option<XdndTarget> = None;
+    let mut last_position = None;
+    let result = loop {
+        pump_xdnd_events(display, &atoms, &mut target, &uri_list);
+
+        let (mut root_return, mut child_return) = (0, 0);
+        let (mut x, mut y, mut win_x, mut win_y) = (0, 0, 0, 0);
+        let mut mask = 0;
+        if xlib::XQueryPointer(display, root, &mut root_return, &mut child_return, &mut x, &mut y, &mut win_x, &mut win_y, &mut mask) == 0 {
+            break Err("Pointer is not on the default screen".to_string());
+        }
+
+        if mask & xlib::Button1Mask == 0 {
+            // 主按键已松开：目标接受时发出 XdndDrop 并等待 XdndFinished，否则离开目标
+            let Some(current) = target.as_ref().filter(|t| t.accepted) else {
+                if let Some(current) = target.take() {
+                    send_xdnd_message(display, current.window, atoms.leave, [source as i64, 0, 0, 0, 0]);
+                }
+                break Ok(DropEffect::None);
+            };
+            let last_action = current.action;
+            send_xdnd_message(display, current.window, atoms.drop, [source as i64, 0, xlib::CurrentTime as i64, 0, 0]);
+            xlib::XFlush(display);
+
+            let deadline = Instant::now() + XDND_FINISH_TIMEOUT;
+            break Ok(loop {
+                match pump_xdnd_events(display, &atoms, &mut target, &uri_list) {
+                    Some((true, action)) => break atoms.effect(action),
+                    Some((false, _)) => break DropEffect::None,
+                    // 超时视为目标已按最后一次 XdndStatus 的动作处理
+                    None if Instant::now() >= deadline => break atoms.effect(last_action),
+                    None => thread::sleep(XDND_POLL_INTERVAL),
+                }
+            });
+        }
+
+        if last_position != Some((x, y)) {
+            last_position = Some((x, y));
+            let found = xdnd_target_at(display, &atoms, root, x, y).filter(|(window, _)| *window != source);
+            if found.map(|(window, _)| window) != target.as_ref().map(|t| t.window) {
+                if let Some(previous) = target.take() {
+                    send_xdnd_message(display, previous.window, atoms.leave, [source as i64, 0, 0, 0, 0]);
+                }
+                if let Some((window, version)) = found {
+                    send_xdnd_message(display, window, atoms.enter, [source as i64, version << 24, atoms.uri_list as i64, 0, 0]);
+                    target = Some(XdndTarget { window, version, accepted: false, action: 0, awaiting_status: false });
+                }
+            }
+            // 每次只保留一条未回复的 XdndPosition，收到 XdndStatus 后再发下一条
+            if let Some(current) = target.as_mut().filter(|t| !t.awaiting_status) {
+                current.awaiting_status = true;
+                let position = ((x as i64) << 16) | (y as i64 & 0xffff);
+                send_xdnd_message(display, current.window, atoms.position, [source as i64, 0, position, xlib::CurrentTime as i64, atoms.action_copy as i64]);
+            } else if target.is_some() {
+                // 未发出的位置留到下一轮重试
+                last_position = None;
+            }
+        }
+
+        xlib::XFlush(display);
+        thread::sleep(XDND_POLL_INTERVAL);
+    };
+
+    xlib::XDestroyWindow(display, source);
+    result
+}
+
+// image_path/image_size 在 X11 下不生效（XDND 不携带拖拽图像）
+pub fn start_file_drag(paths: &[String], _image_path: Option<&str>, _image_size: f64, on_end: FileDragCallback) {
+    if !primary_button_pressed() {
+        on_end(Err("Native file drag must be started while the primary mouse button is held".to_string()));
+        return;
+    }
+    let paths = paths.to_vec();
+    thread::spawn(move || on_end(unsafe { run_xdnd_source(&paths) }));
+}
+
+// XDND 的虚拟文件需要目标支持 XdndDirectSave，文件管理器支持不一，暂不提供
+#[cfg_attr(not(feature = "async-runtime"), allow(dead_code))]
+pub fn start_file_promise_drag(
+    _files: &[PromisedFile],
+    _image_path: Option<&str>,
+    _image_size: f64,
+    _provider: FilePromiseProvider,
+    on_end: FileDragCallback,
+) {
+    on_end(Err("File promise drag is not supported on Linux".to_string()));
+}
+
+// 从根窗口读取矩形区域，按 TrueColor 视觉的掩码拆分通道，返回不透明的 RGBA
+pub fn capture_region(x: f64, y: f64, width: u32, height: u32) -> Option<Vec<u8>> {
+    unsafe {
+        let display = xlib::XOpenDisplay(ptr::null());
+        if display.is_null() {
+            return None;
+        }
+        let root = xlib::XDefaultRootWindow(display);
+        let image = xlib::XGetImage(display, root, x.floor() as i32, y.floor() as i32, width, height, !0, xlib::ZPixmap);
+        let pixels = (!image.is_null()).then(|| {
+            let masks = [(*image).red_mask, (*image).green_mask, (*image).blue_mask];
+            let channel = |pixel: u64, mask: u64| {
+                if mask == 0 {
+                    return 0;
+                }
+                let shift = mask.trailing_zeros();
+                (((pixel & mask) >> shift) * 255 / (mask >> shift)) as u8
+            };
+            let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+            for row in 0..height as i32 {
+                for column in 0..width as i32 {
+                    let pixel = xlib::XGetPixel(image, column, row);
+                    pixels.extend(masks.iter().map(|mask| channel(pixel, *mask)));
+                    pixels.push(255);
+                }
+            }
+            xlib::XDestroyImage(image);
+            pixels
+        });
+        xlib::XCloseDisplay(display);
+        pixels
+    }
+}
+
+// 没有统一的图标主题与缩略图服务可查询
+pub fn file_preview(_path: &str, _size: u32) -> Option<Vec<u8>> {
+    None
+}
+
+// X11 下没有统一的本地化显示名，使用文件名本身
+pub fn display_name(path: &str) -> Option<String> {
+    std::path::Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+// X11 的手势由 XInput2 多点触控事件交给各个窗口自行识别，没有系统级的手势事件
+pub fn watch_gestures(_running: Arc<AtomicBool>, _on_gesture: GestureCallback) -> Result<(), String> {
+    Err("Gesture events are only supported on macOS".to_string())
+}
+
+// CLIPBOARD 选区换了所有者（每次复制都会重新设置）时 XFixes 发出通知，
+// 随后向新的所有者请求 TARGETS，按提供的目标类型判断内容种类
+
+// XFixes 的事件编号和事件掩码，x11 crate 没有导出
+const XFIXES_SELECTION_NOTIFY: i32 = 0;
+const XFIXES_SET_SELECTION_OWNER_NOTIFY_MASK: std::os::raw::c_ulong = 1;
+const CLIPBOARD_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+fn clipboard_contents_of(targets: &[String]) -> ClipboardContents {
+    let mut contents = ClipboardContents::default();
+    for target in targets {
+        match target.as_str() {
+            "text/uri-list" | "x-special/gnome-copied-files" => contents.files = true,
+            "UTF8_STRING" | "STRING" | "TEXT" => contents.text = true,
+            target if target.starts_with("text/plain") => contents.text = true,
+            target if target.starts_with("image/") => contents.image = true,
+            _ => {}
+        }
+    }
+    contents
+}
+
+// 读取 TARGETS 转换结果（ATOM 数组）并删除属性，返回各个目标的名称
+unsafe fn read_targets(display: *mut xlib::Display, window: xlib::Window, property: xlib::Atom) -> Vec<String> {
+    let (mut actual_type, mut actual_format, mut item_count, mut bytes_after) = (0, 0, 0, 0);
+    let mut data: *mut u8 = ptr::null_mut();
+    let status = xlib::XGetWindowProperty(
+        display,
+        window,
+        property,
+        0,
+        1024,
+        xlib::True,
+        xlib::AnyPropertyType as xlib::Atom,
+        &mut actual_type,
+        &mut actual_format,
+        &mut item_count,
+        &mut bytes_after,
+        &mut data,
+    );
+    if data.is_null() {
+        return Vec::new();
+    }
+    let mut targets = Vec::new();
+    if status == xlib::Success as i32 && actual_format == 32 {
+        let atoms = std::slice::from_raw_parts(data as *const std::os::raw::c_ulong, item_count as usize);
+        for &atom in atoms {
+            let name = xlib::XGetAtomName(display, atom as xlib::Atom);
+            if !name.is_null() {
+                targets.push(CStr::from_ptr(name).to_string_lossy().into_owned());
+                xlib::XFree(name as *mut _);
+            }
+        }
+    }
+    xlib::XFree(data as *mut _);
+    targets
+}
+
+// 在当前线程上监听 CLIPBOARD 选区，直到 running 被置为 false；开始时的内容不报告
+pub fn watch_clipboard(running: Arc<AtomicBool>, on_change: ClipboardCallback) -> Result<(), String> {
+    let mut on_change = on_change;
+    unsafe {
+        let display = xlib::XOpenDisplay(ptr::null());
+        if display.is_null() {
+            return Err("Failed to open X display".to_string());
+        }
+        let (mut event_base, mut error_base) = (0, 0);
+        if xfixes::XFixesQueryExtension(display, &mut event_base, &mut error_base) == 0 {
+            xlib::XCloseDisplay(display);
+            return Err("X server does not support the XFixes extension".to_string());
+        }
+
+        let atom = |name: &str| {
+            let name = CString::new(name).unwrap();
+            xlib::XInternAtom(display, name.as_ptr(), xlib::False)
+        };
+        let clipboard = atom("CLIPBOARD");
+        let targets = atom("TARGETS");
+        let property = atom("ELECTRON_DRAGFILE_CLIPBOARD_TARGETS");
+
+        // 不映射的窗口，用来接收选区通知和 TARGETS 转换结果
+        let root = xlib::XDefaultRootWindow(display);
+        let window = xlib::XCreateSimpleWindow(display, root, -10, -10, 1, 1, 0, 0, 0);
+        xfixes::XFixesSelectSelectionInput(display, window, clipboard, XFIXES_SET_SELECTION_OWNER_NOTIFY_MASK);
+        xlib::XFlush(display);
+
+        while running.load(Ordering::SeqCst) {
+            while xlib::XPending(display) > 0 {
+                let mut event: xlib::XEvent = std::mem::zeroed();
+                xlib::XNextEvent(display, &mut event);
+                let event_type = event.get_type();
+                if event_type == event_base + XFIXES_SELECTION_NOTIFY {
+                    let notify = &*(&event as *const xlib::XEvent as *const xfixes::XFixesSelectionNotifyEvent);
+                    if notify.owner == 0 {
+                        // 所有者退出后剪贴板为空
+                        on_change(ClipboardContents::default());
+                    } else {
+                        xlib::XConvertSelection(display, clipboard, targets, property, window, notify.selection_timestamp);
+                    }
+                } else if event_type == xlib::SelectionNotify {
+                    let selection = event.selection;
+                    if selection.selection == clipboard && selection.requestor == window {
+                        let names = if selection.property == 0 {
+                            Vec::new()
+                        } else {
+                            read_targets(display, window, selection.property)
+                        };
+                        on_change(clipboard_contents_of(&names));
+                    }
+                }
+            }
+            xlib::XFlush(display);
+            thread::sleep(CLIPBOARD_POLL_INTERVAL);
+        }
+
+        xlib::XDestroyWindow(display, window);
+        xlib::XCloseDisplay(display);
+    }
+    Ok(())
+}
+
+const CLIPBOARD_READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+// X11 剪贴板的数据由所有者按需提供：在后台线程上持有 CLIPBOARD 选区并响应请求，
+// 直到其他应用（或下一次写入）接管剪贴板时收到 SelectionClear 退出
+pub fn write_files_to_clipboard(paths: &[String]) -> Result<(), String> {
+    let uri_list: String = paths.iter().map(|path| file_uri(path) + "\r\n").collect();
+    let (sender, receiver) = std::sync::mpsc::channel();
+    thread::spawn(move || unsafe {
+        let display = xlib::XOpenDisplay(ptr::null());
+        if display.is_null() {
+            let _ = sender.send(Err("Failed to open X display".to_string()));
+            return;
+        }
+        let atoms = XdndAtoms::new(display);
+        let clipboard = xlib::XInternAtom(display, CString::new("CLIPBOARD").unwrap().as_ptr(), xlib::False);
+        let root = xlib::XDefaultRootWindow(display);
+        let owner = xlib::XCreateSimpleWindow(display, root, -10, -10, 1, 1, 0, 0, 0);
+        xlib::XSetSelectionOwner(display, clipboard, owner, xlib::CurrentTime);
+
+        if xlib::XGetSelectionOwner(display, clipboard) == owner {
+            let _ = sender.send(Ok(()));
+            loop {
+                let mut event: xlib::XEvent = std::mem::zeroed();
+                xlib::XNextEvent(display, &mut event);
+                match event.get_type() {
+                    xlib::SelectionRequest => answer_selection_request(display, &atoms, &event.selection_request, &uri_list),
+                    xlib::SelectionClear => break,
+                    _ => {}
+                }
+                xlib::XFlush(display);
+            }
+        } else {
+            let _ = sender.send(Err("Failed to take ownership of the clipboard".to_string()));
+        }
+
+        xlib::XDestroyWindow(display, owner);
+        xlib::XCloseDisplay(display);
+    });
+    receiver
+        .recv()
+        .unwrap_or_else(|_| Err("Clipboard thread exited unexpectedly".to_string()))
+}
+
+// text/uri-list 中的 file URI 还原为本地路径，其他主机或协议的 URI 返回 None
+fn path_from_file_uri(uri: &str) -> Option<String> {
+    let rest = uri.strip_prefix("file://")?;
+    let path = if rest.starts_with('/') { rest } else { rest.strip_prefix("localhost")? };
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut input = path.bytes();
+    while let Some(byte) = input.next() {
+        if byte == b'%' {
+            let high = (input.next()? as char).to_digit(16)?;
+            let low = (input.next()? as char).to_digit(16)?;
+            bytes.push((high * 16 + low) as u8);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+// 请求把 CLIPBOARD 转换为 text/uri-list 并等待所有者的回复；没有所有者或所有者不提供文件列表时返回 None
+unsafe fn read_clipboard_uri_list(display: *mut xlib::Display, window: xlib::Window) -> Result<Option<String>, String> {
+    let atoms = XdndAtoms::new(display);
+    let clipboard = xlib::XInternAtom(display, CString::new("CLIPBOARD").unwrap().as_ptr(), xlib::False);
+    let property = xlib::XInternAtom(display, CString::new("ELECTRON_DRAGFILE_CLIPBOARD").unwrap().as_ptr(), xlib::False);
+    xlib::XConvertSelection(display, clipboard, atoms.uri_list, property, window, xlib::CurrentTime);
+    xlib::XFlush(display);
+
+    let deadline = Instant::now() + CLIPBOARD_READ_TIMEOUT;
+    loop {
+        while xlib::XPending(display) > 0 {
+            let mut event: xlib::XEvent = std::mem::zeroed();
+            xlib::XNextEvent(display, &mut event);
+            if event.get_type() != xlib::SelectionNotify || event.selection.requestor != window {
+                continue;
+            }
+            if event.selection.property == 0 {
+                return Ok(None);
+            }
+            let (mut actual_type, mut actual_format, mut item_count, mut bytes_after) = (0, 0, 0, 0);
+            let mut data: *mut u8 = ptr::null_mut();
+            xlib::XGetWindowProperty(
+                display,
+                window,
+                property,
+                0,
+                std::os::raw::c_long::MAX / 4,
+                xlib::True,
+                xlib::AnyPropertyType as xlib::Atom,
+                &mut actual_type,
+                &mut actual_format,
+                &mut item_count,
+                &mut bytes_after,
+                &mut data,
+            );
+            if data.is_null() {
+                return Ok(None);
+            }
+            let uri_list = (actual_format == 8)
+                .then(|| String::from_utf8_lossy(std::slice::from_raw_parts(data, item_count as usize)).into_owned());
+            xlib::XFree(data as *mut _);
+            return Ok(uri_list);
+        }
+        if Instant::now() >= deadline {
+            return Err("Timed out waiting for the clipboard owner".to_string());
+        }
+        thread::sleep(XDND_POLL_INTERVAL);
+    }
+}
+
+pub fn read_files_from_clipboard() -> Result<Vec<String>, String> {
+    unsafe {
+        let display = xlib::XOpenDisplay(ptr::null());
+        if display.is_null() {
+            return Err("Failed to open X display".to_string());
+        }
+        let root = xlib::XDefaultRootWindow(display);
+        let window = xlib::XCreateSimpleWindow(display, root, -10, -10, 1, 1, 0, 0, 0);
+        let result = read_clipboard_uri_list(display, window);
+        xlib::XDestroyWindow(display, window);
+        xlib::XCloseDisplay(display);
+
+        Ok(result?
+            .map(|uri_list| {
+                uri_list
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .filter_map(path_from_file_uri)
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
+
+// X11 的放置目标需要在窗口上实现 XDND 协议并处理窗口的事件循环，而窗口的事件由 Chromium 独占读取
+pub fn attach_drop_target(_handle: usize, _on_event: DropTargetCallback) -> Result<u32, String> {
+    Err("Drop targets are only supported on macOS and Windows".to_string())
+}
+
+pub fn detach_drop_target(_id: u32) -> bool {
+    false
+}
@@ -0,0 +1,1072 @@
+use super::{
+    unpremultiply, ClipboardCallback, ClipboardContents, DisplayInfo, DropEffect, DropTargetCallback, DropTargetEvent, DropTargetPhase,
+    FileDragCallback, FilePromiseProvider, Gesture, GestureCallback, GestureKind, GesturePhase, FrontmostApp, PromisedFile, Rect, Theme,
+    WindowRegion,
+};
+use block2::DynBlock;
+use objc2_core_foundation::{
+    kCFRunLoopDefaultMode, CFMachPort, CFRetained, CFRunLoop, CFString, CFType, CGPoint, CGRect, CGSize,
+};
+use objc2::rc::{autoreleasepool, Retained};
+use objc2::runtime::{AnyObject, NSObject, NSObjectProtocol, ProtocolObject};
+use objc2::{define_class, msg_send, AllocAnyThread, DefinedClass, MainThreadMarker, MainThreadOnly};
+use objc2_app_kit::{
+    NSApplication, NSAutoresizingMaskOptions, NSColor, NSColorSpace, NSDragOperation, NSDraggingContext, NSDraggingDestination,
+    NSDraggingInfo, NSDraggingItem, NSDraggingSession, NSDraggingSource, NSEvent, NSEventModifierFlags, NSEventPhase, NSEventType, NSFilePromiseProvider, NSFilePromiseProviderDelegate, NSImage,
+    NSPasteboard, NSPasteboardNameDrag, NSPasteboardTypeFileURL, NSPasteboardTypePNG, NSPasteboardTypeString,
+    NSPasteboardTypeTIFF, NSPasteboardWriting, NSResponder, NSScreen, NSView, NSWorkspace,
+};
+use objc2_foundation::{
+    ns_string, NSArray, NSDictionary, NSError, NSFileManager, NSLocalizedDescriptionKey, NSOperationQueue, NSPoint, NSProcessInfo,
+    NSRect, NSSize, NSString, NSURL, NSUserDefaults,
+};
+use objc2_core_graphics::{
+    CGBitmapContextCreate, CGColorSpace, CGContext, CGDirectDisplayID, CGDisplayBounds, CGEvent, CGImage, CGImageAlphaInfo,
+    CGMainDisplayID, CGEventSource, CGEventSourceStateID, CGGetActiveDisplayList, CGGetDisplaysWithPoint, CGMouseButton,
+    CGWindowImageOption,
+    CGWindowListOption, kCGNullWindowID, CGEventTapLocation, CGEventTapOptions, CGEventTapPlacement, CGEventTapProxy,
+    CGEventType,
+};
+use dispatch::Queue;
+use std::cell::{Cell, RefCell};
+use std::ffi::c_void;
+use std::ptr::{self, NonNull};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+type AXError = i32;
+const AX_ERROR_SUCCESS: AXError = 0;
+const AX_VALUE_CG_POINT_TYPE: u32 = 1;
+const AX_VALUE_CG_SIZE_TYPE: u32 = 2;
+
+// 距离窗口边缘多少点以内视为缩放区域
+const RESIZE_EDGE: f64 = 5.0;
+
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    fn AXUIElementCreateSystemWide() -> *mut CFType;
+    fn AXUIElementCreateApplication(pid: i32) -> *mut CFType;
+    fn AXIsProcessTrusted() -> u8;
+    fn AXUIElementCopyElementAtPosition(
+        application: *const CFType,
+        x: f32,
+        y: f32,
+        element: *mut *mut CFType,
+    ) -> AXError;
+    fn AXUIElementCopyAttributeValue(
+        element: *const CFType,
+        attribute: *const CFString,
+        value: *mut *mut CFType,
+    ) -> AXError;
+    fn AXValueGetValue(value: *const CFType, value_type: u32, value_ptr: *mut c_void) -> bool;
+    fn AXUIElementGetPid(element: *const CFType, pid: *mut i32) -> AXError;
+}
+
+// 线程约束：AppKit 的拖拽会话、剪贴板、NSScreen 和动态系统颜色只能在主线程上使用，而插件的监听线程、
+// 轮询线程和 libuv 线程池都不是主线程，这些调用统一经过 on_main_thread/on_main_thread_async。
+// 其余调用是线程安全的，可以在任意线程上直接使用：辅助功能（AX*）、CoreGraphics（CG*）、
+// NSUserDefaults、NSFileManager，以及用于生成预览的 NSImage/NSWorkspace 图标查询
+
+// Electron 主进程的主线程运行 NSApplication 的 run loop，会处理主队列；
+// 纯 Node 进程或 utility process 的主线程不处理主队列，等待超时后放弃
+const MAIN_THREAD_TIMEOUT: Duration = Duration::from_secs(1);
+
+// 在主线程上执行 f 并返回结果：已在主线程上时直接执行，否则投递到主队列并等待，超时返回 None
+fn on_main_thread<T, F>(f: F) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce(MainThreadMarker) -> T + Send + 'static,
+{
+    if let Some(mtm) = MainThreadMarker::new() {
+        return Some(autoreleasepool(|_| f(mtm)));
+    }
+    let (sender, receiver) = mpsc::sync_channel(1);
+    Queue::main().exec_async(move || {
+        let mtm = MainThreadMarker::new().expect("the main queue runs on the main thread");
+        let _ = sender.send(autoreleasepool(|_| f(mtm)));
+    });
+    receiver.recv_timeout(MAIN_THREAD_TIMEOUT).ok()
+}
+
+// 在主线程上执行 f，不等待结果：已在主线程上时直接执行，否则投递到主队列
+fn on_main_thread_async<F>(f: F)
+where
+    F: FnOnce(MainThreadMarker) + Send + 'static,
+{
+    match MainThreadMarker::new() {
+        Some(mtm) => autoreleasepool(|_| f(mtm)),
+        None => Queue::main().exec_async(move || {
+            let mtm = MainThreadMarker::new().expect("the main queue runs on the main thread");
+            autoreleasepool(|_| f(mtm));
+        }),
+    }
+}
+
+fn copy_attribute(element: &CFType, attribute: &str) -> Option<CFRetained<CFType>> {
+    let name = CFString::from_str(attribute);
+    let mut value: *mut CFType = ptr::null_mut();
+    unsafe {
+        if AXUIElementCopyAttributeValue(element, &*name, &mut value) != AX_ERROR_SUCCESS {
+            return None;
+        }
+        Some(CFRetained::from_raw(NonNull::new(value)?))
+    }
+}
+
+fn string_attribute(element: &CFType, attribute: &str) -> Option<String> {
+    let value = copy_attribute(element, attribute)?;
+    value.downcast_ref::<CFString>().map(|s| s.to_string())
+}
+
+fn window_frame(window: &CFType) -> Option<(CGPoint, CGSize)> {
+    let position = copy_attribute(window, "AXPosition")?;
+    let size = copy_attribute(window, "AXSize")?;
+    let mut origin = CGPoint::new(0.0, 0.0);
+    let mut extent = CGSize::new(0.0, 0.0);
+    unsafe {
+        if !AXValueGetValue(
+            &*position,
+            AX_VALUE_CG_POINT_TYPE,
+            &mut origin as *mut _ as *mut c_void,
+        ) || !AXValueGetValue(
+            &*size,
+            AX_VALUE_CG_SIZE_TYPE,
+            &mut extent as *mut _ as *mut c_void,
+        ) {
+            return None;
+        }
+    }
+    Some((origin, extent))
+}
+
+fn near_window_edge(window: &CFType, x: f64, y: f64) -> bool {
+    let Some((origin, size)) = window_frame(window) else {
+        return false;
+    };
+    x - origin.x < RESIZE_EDGE
+        || origin.x + size.width - x < RESIZE_EDGE
+        || y - origin.y < RESIZE_EDGE
+        || origin.y + size.height - y < RESIZE_EDGE
+}
+
+// 该点最上层的辅助功能元素，需要辅助功能权限（与全局监听相同）
+fn element_at(x: f64, y: f64) -> Option<CFRetained<CFType>> {
+    unsafe {
+        let system = CFRetained::from_raw(NonNull::new(AXUIElementCreateSystemWide())?);
+        let mut element: *mut CFType = ptr::null_mut();
+        if AXUIElementCopyElementAtPosition(&*system, x as f32, y as f32, &mut element) != AX_ERROR_SUCCESS {
+            return None;
+        }
+        Some(CFRetained::from_raw(NonNull::new(element)?))
+    }
+}
+
+// 通过辅助功能 API 查询按下点的 UI 元素角色，判断是否落在标题栏或窗口边框上，查询失败时返回 Unknown
+pub fn window_region_at(x: f64, y: f64) -> WindowRegion {
+    let Some(element) = element_at(x, y) else {
+        return WindowRegion::Unknown;
+    };
+
+    let role = string_attribute(&element, "AXRole").unwrap_or_default();
+    match role.as_str() {
+        // 命中窗口本身而不是其中的控件，说明点在窗口装饰上
+        "AXWindow" => {
+            if near_window_edge(&element, x, y) {
+                WindowRegion::Resize
+            } else {
+                WindowRegion::Caption
+            }
+        }
+        "AXButton" => {
+            let subrole = string_attribute(&element, "AXSubrole").unwrap_or_default();
+            match subrole.as_str() {
+                "AXCloseButton" | "AXMinimizeButton" | "AXZoomButton" | "AXFullScreenButton" => {
+                    WindowRegion::Caption
+                }
+                _ => WindowRegion::Content,
+            }
+        }
+        // 标题文字和文档代理图标直接挂在窗口下
+        "AXStaticText" | "AXImage" => {
+            let Some(parent) = copy_attribute(&element, "AXParent") else {
+                return WindowRegion::Content;
+            };
+            if string_attribute(&parent, "AXRole").as_deref() != Some("AXWindow") {
+                return WindowRegion::Content;
+            }
+            let title = string_attribute(&parent, "AXTitle");
+            let value = string_attribute(&element, "AXValue");
+            if role == "AXImage" || (title.is_some() && title == value) {
+                WindowRegion::Caption
+            } else {
+                WindowRegion::Content
+            }
+        }
+        "" => WindowRegion::Unknown,
+        _ => WindowRegion::Content,
+    }
+}
+
+// 该点上的窗口所属的进程
+pub fn window_pid_at(x: f64, y: f64) -> Option<u32> {
+    let element = element_at(x, y)?;
+    let mut pid = 0;
+    if unsafe { AXUIElementGetPid(&*element, &mut pid) } != AX_ERROR_SUCCESS {
+        return None;
+    }
+    Some(pid as u32)
+}
+
+// NSView 所在窗口的外框；Cocoa 的窗口坐标以主显示器左下角为原点，转换为与事件一致的左上角原点
+pub fn window_bounds(handle: usize) -> Option<Rect> {
+    if handle == 0 {
+        return None;
+    }
+    let main_height = unsafe { CGDisplayBounds(CGMainDisplayID()) }.size.height;
+    on_main_thread(move |_| {
+        let view = unsafe { &*(handle as *const NSView) };
+        let frame = view.window()?.frame();
+        Some(Rect {
+            x: frame.origin.x,
+            y: main_height - frame.origin.y - frame.size.height,
+            width: frame.size.width,
+            height: frame.size.height,
+        })
+    })
+    .flatten()
+}
+
+// 通过辅助功能接口读取应用的主窗口（没有时取焦点窗口），坐标与事件一致；需要辅助功能权限
+pub fn process_window_bounds(pid: u32) -> Option<Rect> {
+    let application = unsafe { CFRetained::from_raw(NonNull::new(AXUIElementCreateApplication(pid as i32))?) };
+    let window = copy_attribute(&application, "AXMainWindow").or_else(|| copy_attribute(&application, "AXFocusedWindow"))?;
+    let (origin, size) = window_frame(&window)?;
+    Some(Rect {
+        x: origin.x,
+        y: origin.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+pub fn cursor_position() -> Option<(f64, f64)> {
+    let event = CGEvent::new(None)?;
+    let point = CGEvent::location(Some(&event));
+    Some((point.x, point.y))
+}
+
+// 返回包含该点的显示器 CGDirectDisplayID，不在任何显示器上时返回 0
+pub fn display_id_at(x: f64, y: f64) -> u32 {
+    let mut display: CGDirectDisplayID = 0;
+    let mut count: u32 = 0;
+    unsafe {
+        CGGetDisplaysWithPoint(CGPoint::new(x, y), 1, &mut display, &mut count);
+    }
+    if count == 0 {
+        0
+    } else {
+        display
+    }
+}
+
+// 显示器左上角在全局坐标中的位置，显示器不存在时返回 None
+pub fn display_origin(display_id: u32) -> Option<(f64, f64)> {
+    let bounds = CGDisplayBounds(display_id);
+    if bounds.size.width <= 0.0 || bounds.size.height <= 0.0 {
+        return None;
+    }
+    Some((bounds.origin.x, bounds.origin.y))
+}
+
+// 主显示器尺寸，与事件坐标一样以点（point）为单位
+pub fn primary_display_size() -> Option<(f64, f64)> {
+    let bounds = CGDisplayBounds(CGMainDisplayID());
+    Some((bounds.size.width, bounds.size.height))
+}
+
+const MAX_DISPLAYS: usize = 32;
+
+// 只读取 CoreGraphics 的显示器 bounds，可以在任意线程上调用，不需要 NSScreen
+pub fn display_bounds() -> Vec<(u32, Rect)> {
+    let mut ids = [0 as CGDirectDisplayID; MAX_DISPLAYS];
+    let mut count: u32 = 0;
+    unsafe {
+        CGGetActiveDisplayList(MAX_DISPLAYS as u32, ids.as_mut_ptr(), &mut count);
+    }
+    ids[..count as usize]
+        .iter()
+        .map(|&id| {
+            let bounds = CGDisplayBounds(id);
+            let rect = Rect {
+                x: bounds.origin.x,
+                y: bounds.origin.y,
+                width: bounds.size.width,
+                height: bounds.size.height,
+            };
+            (id, rect)
+        })
+        .collect()
+}
+
+// 显示器列表与 bounds 来自 CoreGraphics；可用区域和缩放比例只有 NSScreen 提供，需要在主线程上读取，
+// 按 frame 与 CGDisplayBounds 对应，主线程不可用时可用区域取整个显示器、缩放比例取 1
+pub fn displays() -> Vec<DisplayInfo> {
+    let mut ids = [0 as CGDirectDisplayID; MAX_DISPLAYS];
+    let mut count: u32 = 0;
+    unsafe {
+        CGGetActiveDisplayList(MAX_DISPLAYS as u32, ids.as_mut_ptr(), &mut count);
+    }
+
+    // NSScreen 的坐标以主显示器左下角为原点、y 轴向上，换算为左上角原点
+    let screens = on_main_thread(|mtm| {
+        let screens = NSScreen::screens(mtm);
+        let primary_height = screens.firstObject().map(|screen| screen.frame().size.height).unwrap_or(0.0);
+        let flip = |rect: NSRect| Rect {
+            x: rect.origin.x,
+            y: primary_height - rect.origin.y - rect.size.height,
+            width: rect.size.width,
+            height: rect.size.height,
+        };
+        screens
+            .iter()
+            .map(|screen| (flip(screen.frame()), flip(screen.visibleFrame()), screen.backingScaleFactor()))
+            .collect::<Vec<_>>()
+    })
+    .unwrap_or_default();
+
+    let main = CGMainDisplayID();
+    ids[..count as usize]
+        .iter()
+        .map(|&id| {
+            let cg_bounds = CGDisplayBounds(id);
+            let bounds = Rect {
+                x: cg_bounds.origin.x,
+                y: cg_bounds.origin.y,
+                width: cg_bounds.size.width,
+                height: cg_bounds.size.height,
+            };
+            let (work_area, scale_factor) = screens
+                .iter()
+                .find(|(frame, _, _)| *frame == bounds)
+                .map(|(_, visible, scale)| (*visible, *scale))
+                .unwrap_or((bounds, 1.0));
+            DisplayInfo {
+                id,
+                bounds,
+                work_area,
+                scale_factor,
+                primary: id == main,
+            }
+        })
+        .collect()
+}
+
+pub fn primary_button_pressed() -> bool {
+    CGEventSource::button_state(
+        CGEventSourceStateID::CombinedSessionState,
+        CGMouseButton::Left,
+    )
+}
+
+// 全局鼠标监听需要辅助功能权限，未授权时无法安装事件 tap
+pub fn input_permission_granted() -> Option<bool> {
+    Some(unsafe { AXIsProcessTrusted() } != 0)
+}
+
+// 系统拖拽剪贴板的 changeCount，每次有新的拖拽会话写入数据时递增
+// 由轮询线程调用，主线程不可用时返回 None
+pub fn drag_pasteboard_change_count() -> Option<i64> {
+    on_main_thread(|_| {
+        let pasteboard = NSPasteboard::pasteboardWithName(unsafe { NSPasteboardNameDrag });
+        pasteboard.changeCount() as i64
+    })
+}
+
+pub fn drag_pasteboard_has_files() -> bool {
+    on_main_thread(|_| {
+        let pasteboard = NSPasteboard::pasteboardWithName(unsafe { NSPasteboardNameDrag });
+        pasteboard
+            .types()
+            .map(|types| types.containsObject(unsafe { NSPasteboardTypeFileURL }))
+            .unwrap_or(false)
+    })
+    .unwrap_or(false)
+}
+
+// frontmostApplication 由主线程上的工作区通知更新，因此在主线程上读取，主线程不可用时为 None
+pub fn frontmost_app() -> Option<FrontmostApp> {
+    on_main_thread(|_| {
+        let app = NSWorkspace::sharedWorkspace().frontmostApplication()?;
+        Some(FrontmostApp {
+            pid: app.processIdentifier() as u32,
+            name: app.localizedName().map(|name| name.to_string()),
+            bundle_id: app.bundleIdentifier().map(|id| id.to_string()),
+            executable: app.executableURL().and_then(|url| url.path()).map(|path| path.to_string().into()),
+        })
+    })
+    .flatten()
+}
+
+// 深色模式来自全局偏好 AppleInterfaceStyle（浅色模式下不存在该键），强调色取 controlAccentColor
+// 动态系统颜色按主线程的外观解析，因此强调色在主线程上读取，主线程不可用时为 None
+pub fn system_theme() -> Theme {
+    let dark = autoreleasepool(|_| {
+        let style = NSUserDefaults::standardUserDefaults().stringForKey(ns_string!("AppleInterfaceStyle"));
+        style.is_some_and(|style| style.to_string() == "Dark")
+    });
+    let accent_color = on_main_thread(|_| {
+        NSColor::controlAccentColor()
+            .colorUsingColorSpace(&NSColorSpace::sRGBColorSpace())
+            .map(|color| {
+                let channel = |value: f64| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+                (channel(color.redComponent()), channel(color.greenComponent()), channel(color.blueComponent()))
+            })
+    })
+    .flatten();
+    Theme {
+        dark: Some(dark),
+        accent_color,
+    }
+}
+
+// 拖出文件：在主线程上从当前光标位置发起 NSDraggingSession，从其他线程调用时投递到主队列
+
+struct DragSourceIvars {
+    on_end: Cell<Option<FileDragCallback>>,
+}
+
+define_class!(
+    #[unsafe(super(NSObject))]
+    #[thread_kind = MainThreadOnly]
+    #[name = "ElectronDragfilePluginDragSource"]
+    #[ivars = DragSourceIvars]
+    struct DragSource;
+
+    unsafe impl NSObjectProtocol for DragSource {}
+
+    unsafe impl NSDraggingSource for DragSource {
+        #[unsafe(method(draggingSession:sourceOperationMaskForDraggingContext:))]
+        fn source_operation_mask(&self, _session: &NSDraggingSession, _context: NSDraggingContext) -> NSDragOperation {
+            NSDragOperation::Copy | NSDragOperation::Move | NSDragOperation::Link | NSDragOperation::Generic
+        }
+
+        #[unsafe(method(draggingSession:endedAtPoint:operation:))]
+        fn session_ended(&self, _session: &NSDraggingSession, _point: NSPoint, operation: NSDragOperation) {
+            let effect = if operation.contains(NSDragOperation::Move) {
+                DropEffect::Move
+            } else if operation.contains(NSDragOperation::Copy) || operation.contains(NSDragOperation::Generic) {
+                DropEffect::Copy
+            } else if operation.contains(NSDragOperation::Link) {
+                DropEffect::Link
+            } else {
+                DropEffect::None
+            };
+            if let Some(on_end) = self.ivars().on_end.take() {
+                on_end(Ok(effect));
+            }
+            release_drag_source(self);
+        }
+    }
+);
+
+thread_local! {
+    // NSDraggingSession 不保证持有 source，会话结束前由这里保持引用
+    static ACTIVE_DRAG_SOURCES: RefCell<Vec<Retained<DragSource>>> = const { RefCell::new(Vec::new()) };
+}
+
+impl DragSource {
+    fn new(mtm: MainThreadMarker, on_end: FileDragCallback) -> Retained<Self> {
+        let this = Self::alloc(mtm).set_ivars(DragSourceIvars {
+            on_end: Cell::new(Some(on_end)),
+        });
+        unsafe { msg_send![super(this), init] }
+    }
+}
+
+// 会话结束时从当前方法内部释放自身，交给自动释放池延后释放，避免在方法返回前被销毁
+fn release_drag_source(source: &DragSource) {
+    let retained = ACTIVE_DRAG_SOURCES.with(|sources| {
+        let mut sources = sources.borrow_mut();
+        let index = sources.iter().position(|s| ptr::eq(&**s, source))?;
+        Some(sources.swap_remove(index))
+    });
+    if let Some(retained) = retained {
+        let _ = Retained::autorelease_ptr(retained);
+    }
+}
+
+fn drag_image(paths: &[String], image_path: Option<&str>, image_size: f64) -> Option<Retained<NSImage>> {
+    let image = match image_path {
+        Some(image_path) => NSImage::initWithContentsOfFile(NSImage::alloc(), &NSString::from_str(image_path))?,
+        None => {
+            let paths: Vec<Retained<NSString>> = paths.iter().map(|path| NSString::from_str(path)).collect();
+            NSWorkspace::sharedWorkspace().iconForFiles(&NSArray::from_retained_slice(&paths))?
+        }
+    };
+    image.setSize(NSSize::new(image_size, image_size));
+    Some(image)
+}
+
+pub fn start_file_drag(paths: &[String], image_path: Option<&str>, image_size: f64, on_end: FileDragCallback) {
+    let paths = paths.to_vec();
+    let image_path = image_path.map(str::to_string);
+    on_main_thread_async(move |mtm| {
+        let Some(image) = drag_image(&paths, image_path.as_deref(), image_size) else {
+            on_end(Err("Failed to load the drag image".to_string()));
+            return;
+        };
+        let writers = paths
+            .iter()
+            .map(|path| ProtocolObject::from_retained(NSURL::fileURLWithPath(&NSString::from_str(path))))
+            .collect();
+        begin_dragging(mtm, writers, &image, image_size, on_end);
+    });
+}
+
+// 从当前光标位置发起拖拽会话，每个 writer 对应一个拖拽项
+fn begin_dragging(
+    mtm: MainThreadMarker,
+    writers: Vec<Retained<ProtocolObject<dyn NSPasteboardWriting>>>,
+    image: &NSImage,
+    image_size: f64,
+    on_end: FileDragCallback,
+) {
+    let app = NSApplication::sharedApplication(mtm);
+    let Some((window, view)) = app
+        .keyWindow()
+        .or_else(|| app.mainWindow())
+        .and_then(|window| window.contentView().map(|view| (window, view)))
+    else {
+        on_end(Err("Native file drag requires a focused application window".to_string()));
+        return;
+    };
+
+    // 拖拽会话需要一个鼠标事件作为起点，用当前光标位置合成一个 LeftMouseDragged 事件
+    let window_location = window.mouseLocationOutsideOfEventStream();
+    let Some(event) = NSEvent::mouseEventWithType_location_modifierFlags_timestamp_windowNumber_context_eventNumber_clickCount_pressure(
+        NSEventType::LeftMouseDragged,
+        window_location,
+        NSEventModifierFlags::empty(),
+        NSProcessInfo::processInfo().systemUptime(),
+        window.windowNumber(),
+        None,
+        0,
+        1,
+        1.0,
+    ) else {
+        on_end(Err("Failed to create the drag event".to_string()));
+        return;
+    };
+
+    let location = view.convertPoint_fromView(window_location, None);
+    let frame = NSRect::new(
+        NSPoint::new(location.x - image_size / 2.0, location.y - image_size / 2.0),
+        NSSize::new(image_size, image_size),
+    );
+    let items: Vec<Retained<NSDraggingItem>> = writers
+        .iter()
+        .map(|writer| {
+            let item = NSDraggingItem::initWithPasteboardWriter(NSDraggingItem::alloc(), writer);
+            unsafe { item.setDraggingFrame_contents(frame, Some(image)) };
+            item
+        })
+        .collect();
+
+    let source = DragSource::new(mtm, on_end);
+    ACTIVE_DRAG_SOURCES.with(|sources| sources.borrow_mut().push(source.clone()));
+    let session = view.beginDraggingSessionWithItems_event_source(
+        &NSArray::from_retained_slice(&items),
+        &event,
+        ProtocolObject::from_ref(&*source),
+    );
+    session.setAnimatesToStartingPositionsOnCancelOrFail(true);
+}
+
+// 拖出虚拟文件：NSFilePromiseProvider 在目标接受放下之后才请求内容，
+// 内容由 provider 在专用的操作队列上生成（可能阻塞等待 JS），再复制到目标给出的位置
+
+struct FilePromiseDelegateIvars {
+    index: usize,
+    file_name: String,
+    provider: FilePromiseProvider,
+    queue: Retained<NSOperationQueue>,
+}
+
+define_class!(
+    #[unsafe(super(NSObject))]
+    #[name = "ElectronDragfilePluginFilePromiseDelegate"]
+    #[ivars = FilePromiseDelegateIvars]
+    struct FilePromiseDelegate;
+
+    unsafe impl NSObjectProtocol for FilePromiseDelegate {}
+
+    unsafe impl NSFilePromiseProviderDelegate for FilePromiseDelegate {
+        #[unsafe(method_id(filePromiseProvider:fileNameForType:))]
+        fn file_name(&self, _provider: &NSFilePromiseProvider, _file_type: &NSString) -> Retained<NSString> {
+            NSString::from_str(&self.ivars().file_name)
+        }
+
+        #[unsafe(method(filePromiseProvider:writePromiseToURL:completionHandler:))]
+        fn write_promise(&self, _provider: &NSFilePromiseProvider, url: &NSURL, completion_handler: &DynBlock<dyn Fn(*mut NSError)>) {
+            let ivars = self.ivars();
+            let result = url
+                .path()
+                .ok_or_else(|| "Drop destination is not a file URL".to_string())
+                .and_then(|destination| {
+                    let source = (ivars.provider)(ivars.index)?;
+                    std::fs::copy(&source, destination.to_string())
+                        .map(|_| ())
+                        .map_err(|e| format!("Failed to copy {}: {}", source.display(), e))
+                });
+            match result {
+                Ok(()) => completion_handler.call((ptr::null_mut(),)),
+                Err(message) => {
+                    let message = NSString::from_str(&message);
+                    let user_info = NSDictionary::<NSString, AnyObject>::from_slices(
+                        &[unsafe { NSLocalizedDescriptionKey }],
+                        &[message.as_ref()],
+                    );
+                    let error = unsafe {
+                        NSError::errorWithDomain_code_userInfo(ns_string!("ElectronDragfilePlugin"), 1, Some(&user_info))
+                    };
+                    completion_handler.call((Retained::as_ptr(&error) as *mut NSError,));
+                }
+            }
+        }
+
+        #[unsafe(method_id(operationQueueForFilePromiseProvider:))]
+        fn operation_queue(&self, _provider: &NSFilePromiseProvider) -> Retained<NSOperationQueue> {
+            self.ivars().queue.clone()
+        }
+    }
+);
+
+impl FilePromiseDelegate {
+    fn new(index: usize, file_name: String, provider: FilePromiseProvider, queue: Retained<NSOperationQueue>) -> Retained<Self> {
+        let this = Self::alloc().set_ivars(FilePromiseDelegateIvars {
+            index,
+            file_name,
+            provider,
+            queue,
+        });
+        unsafe { msg_send![super(this), init] }
+    }
+}
+
+#[cfg_attr(not(feature = "async-runtime"), allow(dead_code))]
+pub fn start_file_promise_drag(
+    files: &[PromisedFile],
+    image_path: Option<&str>,
+    image_size: f64,
+    provider: FilePromiseProvider,
+    on_end: FileDragCallback,
+) {
+    let files = files.to_vec();
+    let image_path = image_path.map(str::to_string);
+    on_main_thread_async(move |mtm| {
+        let image = match image_path {
+            Some(image_path) => NSImage::initWithContentsOfFile(NSImage::alloc(), &NSString::from_str(&image_path)),
+            None => {
+                // iconForContentType 需要额外链接 UniformTypeIdentifiers，这里按扩展名取图标
+                let extension = std::path::Path::new(&files[0].file_name)
+                    .extension()
+                    .map(|extension| extension.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                #[allow(deprecated)]
+                Some(NSWorkspace::sharedWorkspace().iconForFileType(&NSString::from_str(&extension)))
+            }
+        };
+        let Some(image) = image else {
+            on_end(Err("Failed to load the drag image".to_string()));
+            return;
+        };
+        image.setSize(NSSize::new(image_size, image_size));
+
+        // 同一次拖拽的文件依次生成，避免同时向 JS 请求多个文件
+        let queue = NSOperationQueue::new();
+        queue.setMaxConcurrentOperationCount(1);
+        let writers = files
+            .iter()
+            .enumerate()
+            .map(|(index, file)| {
+                let delegate = FilePromiseDelegate::new(index, file.file_name.clone(), provider.clone(), queue.clone());
+                let file_type = file.file_type.as_deref().unwrap_or("public.data");
+                let promise = NSFilePromiseProvider::initWithFileType_delegate(
+                    NSFilePromiseProvider::alloc(),
+                    &NSString::from_str(file_type),
+                    ProtocolObject::from_ref(&*delegate),
+                );
+                // provider 只弱引用 delegate，用 userInfo 持有它，生命周期跟随 provider
+                unsafe { promise.setUserInfo(Some(&delegate)) };
+                ProtocolObject::from_retained(promise)
+            })
+            .collect();
+        begin_dragging(mtm, writers, &image, image_size, on_end);
+    });
+}
+
+// Finder 中显示的名称：本地化的系统文件夹名（如 “文稿”），并按用户设置隐藏扩展名
+pub fn display_name(path: &str) -> Option<String> {
+    autoreleasepool(|_| {
+        let name = NSFileManager::defaultManager().displayNameAtPath(&NSString::from_str(path));
+        Some(name.to_string())
+    })
+}
+
+// 拖拽预览：图片文件取缩略图，其他文件取 Finder 图标，等比缩放后居中绘制到 size x size 的画布上
+// 返回非预乘的 RGBA
+pub fn file_preview(path: &str, size: u32) -> Option<Vec<u8>> {
+    autoreleasepool(|_| {
+        let path = NSString::from_str(path);
+        let image = NSImage::initWithContentsOfFile(NSImage::alloc(), &path)
+            .unwrap_or_else(|| NSWorkspace::sharedWorkspace().iconForFile(&path));
+        // 图标包含多种尺寸，按目标尺寸取最合适的一份
+        let mut proposed = NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(size as f64, size as f64));
+        let cg_image = unsafe { image.CGImageForProposedRect_context_hints(&mut proposed, None, None) }?;
+
+        let (width, height) = (CGImage::width(Some(&cg_image)) as f64, CGImage::height(Some(&cg_image)) as f64);
+        if width == 0.0 || height == 0.0 {
+            return None;
+        }
+        let scale = (size as f64 / width).min(size as f64 / height);
+        let (draw_width, draw_height) = (width * scale, height * scale);
+        let rect = CGRect::new(
+            CGPoint::new((size as f64 - draw_width) / 2.0, (size as f64 - draw_height) / 2.0),
+            CGSize::new(draw_width, draw_height),
+        );
+        draw_to_rgba(&cg_image, size as usize, size as usize, rect)
+    })
+}
+
+// 把 image 绘制到 width x height 画布的 rect 处，返回非预乘的 RGBA
+fn draw_to_rgba(image: &CGImage, width: usize, height: usize, rect: CGRect) -> Option<Vec<u8>> {
+    let mut pixels = vec![0u8; width * height * 4];
+    let color_space = CGColorSpace::new_device_rgb()?;
+    let context = unsafe {
+        CGBitmapContextCreate(
+            pixels.as_mut_ptr() as *mut c_void,
+            width,
+            height,
+            8,
+            width * 4,
+            Some(&color_space),
+            CGImageAlphaInfo::PremultipliedLast.0,
+        )
+    }?;
+    CGContext::draw_image(Some(&context), rect, Some(image));
+    drop(context);
+
+    unpremultiply(&mut pixels);
+    Some(pixels)
+}
+
+// 截取屏幕上的矩形区域（全局坐标，单位为点），按 1 点 = 1 像素输出非预乘的 RGBA
+// 未授予“屏幕录制”权限时只能截到桌面和本应用的窗口
+pub fn capture_region(x: f64, y: f64, width: u32, height: u32) -> Option<Vec<u8>> {
+    let bounds = CGRect::new(CGPoint::new(x.floor(), y.floor()), CGSize::new(width as f64, height as f64));
+    // ScreenCaptureKit 只提供异步截取，这里仍使用同步的 CGWindowListCreateImage
+    #[allow(deprecated)]
+    let image = objc2_core_graphics::CGWindowListCreateImage(
+        bounds,
+        CGWindowListOption::OptionOnScreenOnly,
+        kCGNullWindowID,
+        CGWindowImageOption::NominalResolution,
+    )?;
+    let rect = CGRect::new(CGPoint::new(0.0, 0.0), CGSize::new(width as f64, height as f64));
+    draw_to_rgba(&image, width as usize, height as usize, rect)
+}
+
+// 触控板手势对应的 NSEventType 取值，CGEventType 中没有对应的常量
+const GESTURE_EVENT_TYPES: [NSEventType; 3] = [NSEventType::Magnify, NSEventType::Rotate, NSEventType::Swipe];
+
+// 手势线程检查停止标志的间隔
+const GESTURE_POLL_INTERVAL: f64 = 0.2;
+
+unsafe extern "C-unwind" fn gesture_tap_callback(
+    _proxy: CGEventTapProxy,
+    _event_type: CGEventType,
+    cg_event: NonNull<CGEvent>,
+    user_info: *mut c_void,
+) -> *mut CGEvent {
+    // user_info 指向 watch_gestures 栈上的回调，在事件循环退出之前一直有效
+    let on_gesture = unsafe { &mut *(user_info as *mut GestureCallback) };
+    autoreleasepool(|_| {
+        if let Some(event) = unsafe { NSEvent::eventWithCGEvent(cg_event.as_ref()) } {
+            if let Some(gesture) = gesture_of(&event) {
+                on_gesture(gesture);
+            }
+        }
+    });
+    cg_event.as_ptr()
+}
+
+fn gesture_of(event: &NSEvent) -> Option<Gesture> {
+    let phase = || {
+        let phase = unsafe { event.phase() };
+        if phase.contains(NSEventPhase::Began) {
+            Some(GesturePhase::Began)
+        } else if phase.intersects(NSEventPhase::Ended | NSEventPhase::Cancelled) {
+            Some(GesturePhase::Ended)
+        } else if phase.contains(NSEventPhase::Changed) {
+            Some(GesturePhase::Changed)
+        } else {
+            None
+        }
+    };
+    let (kind, phase) = match unsafe { event.r#type() } {
+        NSEventType::Magnify => (GestureKind::Pinch { magnification: unsafe { event.magnification() } }, phase()),
+        NSEventType::Rotate => (GestureKind::Rotate { degrees: unsafe { event.rotation() } as f64 }, phase()),
+        // 轻扫是一次性的离散事件：系统的 deltaX 为 1 表示向左、deltaY 为 1 表示向上，这里换成屏幕坐标方向
+        NSEventType::Swipe => {
+            let (delta_x, delta_y) = unsafe { (event.deltaX(), event.deltaY()) };
+            (GestureKind::Swipe { delta_x: -delta_x, delta_y: -delta_y }, None)
+        }
+        _ => return None,
+    };
+    Some(Gesture { kind, phase })
+}
+
+// 在当前线程上监听触控板手势，直到 running 被置为 false；需要“辅助功能”权限
+// 事件循环分段运行，两段之间检查停止标志
+pub fn watch_gestures(running: Arc<AtomicBool>, on_gesture: GestureCallback) -> Result<(), String> {
+    let mut on_gesture = on_gesture;
+    let mask = GESTURE_EVENT_TYPES.iter().fold(0u64, |mask, event_type| mask | (1 << event_type.0));
+    unsafe {
+        let tap = CGEvent::tap_create(
+            CGEventTapLocation::SessionEventTap,
+            CGEventTapPlacement::TailAppendEventTap,
+            CGEventTapOptions::ListenOnly,
+            mask,
+            Some(gesture_tap_callback),
+            &mut on_gesture as *mut GestureCallback as *mut c_void,
+        )
+        .ok_or_else(|| "Failed to create gesture event tap (accessibility permission required)".to_string())?;
+        let source = CFMachPort::new_run_loop_source(None, Some(&tap), 0)
+            .ok_or_else(|| "Failed to create gesture run loop source".to_string())?;
+        let run_loop = CFRunLoop::current().ok_or_else(|| "No run loop for gesture thread".to_string())?;
+        run_loop.add_source(Some(&source), kCFRunLoopDefaultMode);
+        CGEvent::tap_enable(&tap, true);
+
+        while running.load(Ordering::SeqCst) {
+            CFRunLoop::run_in_mode(kCFRunLoopDefaultMode, GESTURE_POLL_INTERVAL, false);
+        }
+
+        CGEvent::tap_enable(&tap, false);
+        run_loop.remove_source(Some(&source), kCFRunLoopDefaultMode);
+        CFMachPort::invalidate(&tap);
+    }
+    Ok(())
+}
+
+// 通用剪贴板没有变化通知，只能轮询 changeCount
+const CLIPBOARD_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+// NSPasteboard 可以在任意线程上读取，不必像拖拽剪贴板那样切到主线程（纯 Node 进程的主队列不会运行）
+fn general_pasteboard_change_count() -> i64 {
+    autoreleasepool(|_| NSPasteboard::generalPasteboard().changeCount() as i64)
+}
+
+fn general_pasteboard_contents() -> ClipboardContents {
+    autoreleasepool(|_| {
+        let Some(types) = NSPasteboard::generalPasteboard().types() else {
+            return ClipboardContents::default();
+        };
+        let has = |pasteboard_type: &NSString| types.containsObject(pasteboard_type);
+        unsafe {
+            ClipboardContents {
+                files: has(NSPasteboardTypeFileURL),
+                text: has(NSPasteboardTypeString),
+                image: has(NSPasteboardTypePNG) || has(NSPasteboardTypeTIFF),
+            }
+        }
+    })
+}
+
+// 在当前线程上监听通用剪贴板，直到 running 被置为 false；开始时的内容不报告
+pub fn watch_clipboard(running: Arc<AtomicBool>, on_change: ClipboardCallback) -> Result<(), String> {
+    let mut on_change = on_change;
+    let mut change_count = general_pasteboard_change_count();
+    loop {
+        std::thread::sleep(CLIPBOARD_POLL_INTERVAL);
+        if !running.load(Ordering::SeqCst) {
+            break;
+        }
+        let current = general_pasteboard_change_count();
+        if current != change_count {
+            change_count = current;
+            on_change(general_pasteboard_contents());
+        }
+    }
+    Ok(())
+}
+
+// 与拖出文件相同，每个文件写为一个 NSURL，Finder 可以直接粘贴
+pub fn write_files_to_clipboard(paths: &[String]) -> Result<(), String> {
+    autoreleasepool(|_| {
+        let writers: Vec<Retained<ProtocolObject<dyn NSPasteboardWriting>>> = paths
+            .iter()
+            .map(|path| ProtocolObject::from_retained(NSURL::fileURLWithPath(&NSString::from_str(path))))
+            .collect();
+        let pasteboard = NSPasteboard::generalPasteboard();
+        pasteboard.clearContents();
+        if pasteboard.writeObjects(&NSArray::from_retained_slice(&writers)) {
+            Ok(())
+        } else {
+            Err("Failed to write files to the clipboard".to_string())
+        }
+    })
+}
+
+// 每个剪贴板项最多带一个文件 URL；Finder 复制的是文件引用 URL（file:///.file/id=...），转换为普通路径
+fn pasteboard_file_paths(pasteboard: &NSPasteboard) -> Vec<String> {
+    let Some(items) = pasteboard.pasteboardItems() else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| {
+            let uri = item.stringForType(unsafe { NSPasteboardTypeFileURL })?;
+            let url = NSURL::URLWithString(&uri)?;
+            url.filePathURL()?.path().map(|path| path.to_string())
+        })
+        .collect()
+}
+
+pub fn read_files_from_clipboard() -> Result<Vec<String>, String> {
+    Ok(autoreleasepool(|_| pasteboard_file_paths(&NSPasteboard::generalPasteboard())))
+}
+
+// 放置目标：在 Electron 窗口的内容视图上覆盖一个透明子视图，只注册文件 URL 类型，
+// 因此拖入文件时由它接收，文本、链接等其他拖拽仍然交给下面的网页视图
+
+struct DropTargetIvars {
+    id: u32,
+    on_event: RefCell<DropTargetCallback>,
+    // 进入时读取的文件路径，悬停事件沿用，避免每次移动都读取拖拽剪贴板
+    paths: RefCell<Vec<String>>,
+    // 最后一次悬停的位置，draggingExited: 不一定带有拖拽信息
+    last_point: Cell<(f64, f64)>,
+}
+
+define_class!(
+    #[unsafe(super(NSView, NSResponder, NSObject))]
+    #[thread_kind = MainThreadOnly]
+    #[name = "ElectronDragfilePluginDropTargetView"]
+    #[ivars = DropTargetIvars]
+    struct DropTargetView;
+
+    impl DropTargetView {
+        // 不参与鼠标命中测试，点击和滚动仍然落到网页视图上；拖拽目标的查找不经过 hitTest:
+        #[unsafe(method(hitTest:))]
+        fn hit_test(&self, _point: NSPoint) -> *mut NSView {
+            ptr::null_mut()
+        }
+    }
+
+    unsafe impl NSObjectProtocol for DropTargetView {}
+
+    unsafe impl NSDraggingDestination for DropTargetView {
+        #[unsafe(method(draggingEntered:))]
+        fn dragging_entered(&self, sender: &ProtocolObject<dyn NSDraggingInfo>) -> NSDragOperation {
+            *self.ivars().paths.borrow_mut() = pasteboard_file_paths(&sender.draggingPasteboard());
+            self.report(DropTargetPhase::Enter, Some(sender));
+            NSDragOperation::Copy
+        }
+
+        #[unsafe(method(draggingUpdated:))]
+        fn dragging_updated(&self, sender: &ProtocolObject<dyn NSDraggingInfo>) -> NSDragOperation {
+            self.report(DropTargetPhase::Over, Some(sender));
+            NSDragOperation::Copy
+        }
+
+        #[unsafe(method(draggingExited:))]
+        fn dragging_exited(&self, _sender: Option<&ProtocolObject<dyn NSDraggingInfo>>) {
+            self.ivars().paths.borrow_mut().clear();
+            self.report(DropTargetPhase::Leave, None);
+        }
+
+        #[unsafe(method(performDragOperation:))]
+        fn perform_drag_operation(&self, sender: &ProtocolObject<dyn NSDraggingInfo>) -> bool {
+            *self.ivars().paths.borrow_mut() = pasteboard_file_paths(&sender.draggingPasteboard());
+            self.report(DropTargetPhase::Drop, Some(sender));
+            self.ivars().paths.borrow_mut().clear();
+            true
+        }
+    }
+);
+
+thread_local! {
+    // 挂载中的放置目标，只在主线程上访问
+    static DROP_TARGET_VIEWS: RefCell<Vec<Retained<DropTargetView>>> = const { RefCell::new(Vec::new()) };
+}
+
+impl DropTargetView {
+    fn new(mtm: MainThreadMarker, id: u32, on_event: DropTargetCallback, frame: NSRect) -> Retained<Self> {
+        let this = Self::alloc(mtm).set_ivars(DropTargetIvars {
+            id,
+            on_event: RefCell::new(on_event),
+            paths: RefCell::new(Vec::new()),
+            last_point: Cell::new((0.0, 0.0)),
+        });
+        unsafe { msg_send![super(this), initWithFrame: frame] }
+    }
+
+    // 视图不是翻转坐标系，转换为以左上角为原点，与页面坐标一致
+    fn report(&self, phase: DropTargetPhase, sender: Option<&ProtocolObject<dyn NSDraggingInfo>>) {
+        let ivars = self.ivars();
+        if let Some(sender) = sender {
+            let point = self.convertPoint_fromView(sender.draggingLocation(), None);
+            ivars.last_point.set((point.x, self.bounds().size.height - point.y));
+        }
+        let (x, y) = ivars.last_point.get();
+        let event = DropTargetEvent {
+            target_id: ivars.id,
+            phase,
+            x,
+            y,
+            paths: ivars.paths.borrow().clone(),
+        };
+        (ivars.on_event.borrow_mut())(event);
+    }
+}
+
+// handle 为 BrowserWindow.getNativeWindowHandle() 中的 NSView 指针；
+// 只能在主线程（Electron 主进程的 JS 线程）上调用，回调也在主线程上执行
+pub fn attach_drop_target(handle: usize, on_event: DropTargetCallback) -> Result<u32, String> {
+    let mtm = MainThreadMarker::new().ok_or_else(|| "Drop targets can only be attached on the main thread".to_string())?;
+    if handle == 0 {
+        return Err("Invalid window handle".to_string());
+    }
+    let parent = unsafe { &*(handle as *const NSView) };
+    let attached = DROP_TARGET_VIEWS.with(|views| {
+        views.borrow().iter().any(|view| view.superview().is_some_and(|superview| ptr::eq(&*superview, parent)))
+    });
+    if attached {
+        return Err("A drop target is already attached to this window".to_string());
+    }
+    let id = super::next_drop_target_id();
+    let view = DropTargetView::new(mtm, id, on_event, parent.bounds());
+    view.setAutoresizingMask(NSAutoresizingMaskOptions::ViewWidthSizable | NSAutoresizingMaskOptions::ViewHeightSizable);
+    view.registerForDraggedTypes(&NSArray::from_slice(&[unsafe { NSPasteboardTypeFileURL }]));
+    parent.addSubview(&view);
+    DROP_TARGET_VIEWS.with(|views| views.borrow_mut().push(view));
+    Ok(id)
+}
+
+// 在其他线程上调用时找不到挂载记录，返回 false
+pub fn detach_drop_target(id: u32) -> bool {
+    let view = DROP_TARGET_VIEWS.with(|views| {
+        let mut views = views.borrow_mut();
+        let index = views.iter().position(|view| view.ivars().id == id)?;
+        Some(views.swap_remove(index))
+    });
+    match view {
+        Some(view) => {
+            view.unregisterDraggedTypes();
+            view.removeFromSuperview();
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,248 @@
+// 平台相关的系统查询（窗口命中测试、光标位置等），每个平台一个实现文件
+
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "macos")]
+pub use macos::*;
+
+#[cfg(target_os = "windows")]
+mod windows;
+#[cfg(target_os = "windows")]
+pub use windows::*;
+
+#[cfg(all(target_family = "unix", not(target_os = "macos")))]
+mod linux;
+#[cfg(all(target_family = "unix", not(target_os = "macos")))]
+pub use linux::*;
+
+// 系统外观：是否为深色模式、强调色（sRGB），无法获取时为 None
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub dark: Option<bool>,
+    pub accent_color: Option<(u8, u8, u8)>,
+}
+
+// 屏幕上的矩形，单位与事件坐标一致
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+// 一个显示器：标识与 display_id_at 一致，bounds/work_area 为系统全局坐标
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayInfo {
+    pub id: u32,
+    pub bounds: Rect,
+    // 去掉菜单栏、Dock、任务栏之后的可用区域
+    pub work_area: Rect,
+    // 每个坐标单位对应的物理像素数
+    pub scale_factor: f64,
+    pub primary: bool,
+}
+
+// 拖出操作结束时目标接受的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropEffect {
+    None,
+    Copy,
+    Move,
+    Link,
+}
+
+impl DropEffect {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DropEffect::None => "none",
+            DropEffect::Copy => "copy",
+            DropEffect::Move => "move",
+            DropEffect::Link => "link",
+        }
+    }
+}
+
+// 拖出结束（或启动失败）时调用，可能在任意线程上调用
+pub type FileDragCallback = Box<dyn FnOnce(Result<DropEffect, String>) + Send>;
+
+// 拖出的虚拟文件，内容在目标接受放下之后才生成
+#[cfg_attr(not(feature = "async-runtime"), allow(dead_code))]
+#[derive(Debug, Clone)]
+pub struct PromisedFile {
+    // Linux 不支持虚拟文件拖出，不需要文件名
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    pub file_name: String,
+    // macOS 的 UTI，默认为 public.data；其他平台忽略
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    pub file_type: Option<String>,
+}
+
+// 生成第 index 个虚拟文件，返回写好内容的临时文件路径；在后台线程上调用，可以阻塞
+#[cfg_attr(not(feature = "async-runtime"), allow(dead_code))]
+pub type FilePromiseProvider = std::sync::Arc<dyn Fn(usize) -> Result<std::path::PathBuf, String> + Send + Sync>;
+
+// 前台应用；bundle_id 只有 macOS 提供
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontmostApp {
+    pub pid: u32,
+    // 显示名称：macOS 为本地化的应用名，其他平台为不含扩展名的可执行文件名
+    pub name: Option<String>,
+    pub bundle_id: Option<String>,
+    // 可执行文件的完整路径
+    pub executable: Option<std::path::PathBuf>,
+}
+
+impl FrontmostApp {
+    // 可执行文件名（不含路径）
+    pub fn exe_name(&self) -> Option<String> {
+        let executable = self.executable.as_ref()?;
+        executable.file_name().map(|name| name.to_string_lossy().into_owned())
+    }
+
+    #[cfg_attr(target_os = "macos", allow(dead_code))]
+    fn from_executable(pid: u32, executable: std::path::PathBuf) -> Self {
+        Self {
+            pid,
+            name: executable.file_stem().map(|name| name.to_string_lossy().into_owned()),
+            bundle_id: None,
+            executable: Some(executable),
+        }
+    }
+}
+
+// 触控板手势；magnification 为相对上一个事件的缩放增量，degrees 为旋转角度增量（逆时针为正），
+// 轻扫的位移为 -1/0/1，方向与屏幕坐标一致（x 向右、y 向下为正）
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GestureKind {
+    Pinch { magnification: f64 },
+    Rotate { degrees: f64 },
+    Swipe { delta_x: f64, delta_y: f64 },
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GesturePhase {
+    Began,
+    Changed,
+    Ended,
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gesture {
+    pub kind: GestureKind,
+    // 连续手势（缩放、旋转）的阶段，离散手势（轻扫）为 None
+    pub phase: Option<GesturePhase>,
+}
+
+// 收到手势时在手势监听线程上调用
+pub type GestureCallback = Box<dyn FnMut(Gesture) + Send>;
+
+// 剪贴板提供的内容种类，同一次复制可以同时提供多种
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClipboardContents {
+    pub files: bool,
+    pub text: bool,
+    pub image: bool,
+}
+
+// 剪贴板内容变化时在剪贴板监听线程上调用
+pub type ClipboardCallback = Box<dyn FnMut(ClipboardContents) + Send>;
+
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropTargetPhase {
+    Enter,
+    Over,
+    Leave,
+    Drop,
+}
+
+impl DropTargetPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DropTargetPhase::Enter => "drop-target-enter",
+            DropTargetPhase::Over => "drop-target-over",
+            DropTargetPhase::Leave => "drop-target-leave",
+            DropTargetPhase::Drop => "drop-target-drop",
+        }
+    }
+}
+
+// 拖入窗口的文件；x、y 为相对窗口内容区左上角的坐标，单位与页面中的 CSS 像素一致；
+// 离开时 paths 为空
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropTargetEvent {
+    pub target_id: u32,
+    pub phase: DropTargetPhase,
+    pub x: f64,
+    pub y: f64,
+    pub paths: Vec<String>,
+}
+
+// 在窗口所在的 UI 线程上调用
+pub type DropTargetCallback = Box<dyn FnMut(DropTargetEvent) + Send>;
+
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+static NEXT_DROP_TARGET_ID: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(1);
+
+// 放置目标的标识在进程内唯一，多个 Node 环境挂载的目标不会混淆
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+fn next_drop_target_id() -> u32 {
+    NEXT_DROP_TARGET_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+}
+
+// 预乘 alpha 的 RGBA/BGRA 像素还原为非预乘
+#[cfg_attr(not(any(target_os = "macos", target_os = "windows")), allow(dead_code))]
+fn unpremultiply(pixels: &mut [u8]) {
+    for pixel in pixels.chunks_mut(4) {
+        let alpha = pixel[3] as u32;
+        if alpha != 0 && alpha != 255 {
+            for channel in &mut pixel[..3] {
+                *channel = ((*channel as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+            }
+        }
+    }
+}
+
+// 按下点所在的顶层窗口区域；Linux 不做命中测试，只有 Unknown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowRegion {
+    // 窗口内容区
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    Content,
+    // 标题栏及其按钮
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    Caption,
+    // 窗口边框的缩放区域
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    Resize,
+    // 未分类或平台不支持
+    Unknown,
+}
+
+impl WindowRegion {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            #[cfg(any(target_os = "macos", target_os = "windows"))]
+            WindowRegion::Content => "content",
+            #[cfg(any(target_os = "macos", target_os = "windows"))]
+            WindowRegion::Caption => "caption",
+            #[cfg(any(target_os = "macos", target_os = "windows"))]
+            WindowRegion::Resize => "resize",
+            WindowRegion::Unknown => "unknown",
+        }
+    }
+
+    // 标题栏和缩放边框都属于窗口装饰区域，拖拽通常是在移动或缩放窗口
+    pub fn is_window_chrome(&self) -> bool {
+        match self {
+            #[cfg(any(target_os = "macos", target_os = "windows"))]
+            WindowRegion::Caption | WindowRegion::Resize => true,
+            _ => false,
+        }
+    }
+}
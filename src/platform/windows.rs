@@ -0,0 +1,1345 @@
+use super::{
+    unpremultiply, ClipboardCallback, ClipboardContents, DisplayInfo, DropEffect, DropTargetCallback, DropTargetEvent, DropTargetPhase,
+    FileDragCallback, FilePromiseProvider, FrontmostApp, GestureCallback, PromisedFile, Rect, Theme, WindowRegion,
+};
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::ptr;
+use std::thread;
+use winapi::ctypes::c_void;
+use winapi::shared::basetsd::DWORD_PTR;
+use winapi::shared::guiddef::GUID;
+use winapi::shared::guiddef::{IsEqualGUID, REFIID};
+use winapi::shared::minwindef::{BOOL, DWORD, FALSE, FILETIME, HGLOBAL, LPARAM, MAX_PATH, TRUE, UINT, ULONG};
+use winapi::shared::windef::HWND;
+use winapi::shared::winerror::{
+    DRAGDROP_S_DROP, DV_E_DVASPECT, DV_E_FORMATETC, DV_E_LINDEX, DV_E_TYMED, E_FAIL, E_NOINTERFACE, E_NOTIMPL, E_OUTOFMEMORY,
+    ERROR_SUCCESS, FAILED, HRESULT, OLE_E_ADVISENOTSUPPORTED, SUCCEEDED, S_OK,
+};
+use winapi::shared::wtypes::{CLIPFORMAT, DVASPECT_CONTENT};
+use winapi::um::combaseapi::{CoInitializeEx, CoUninitialize};
+use winapi::um::dwmapi::{DwmGetWindowAttribute, DWMWA_CLOAKED, DWMWA_EXTENDED_FRAME_BOUNDS};
+use winapi::um::coml2api::STGM_READ;
+use winapi::um::objidl::{
+    IAdviseSink, IDataObjectVtbl, IEnumFORMATETC, IEnumSTATDATA, FORMATETC, STGMEDIUM, STGMEDIUM_u, DATADIR_GET, TYMED_HGLOBAL,
+    TYMED_ISTREAM,
+};
+use winapi::um::objbase::COINIT_APARTMENTTHREADED;
+use winapi::um::objidlbase::IStream;
+use winapi::um::unknwnbase::{IUnknown, IUnknownVtbl};
+use winapi::um::handleapi::CloseHandle;
+use winapi::um::processthreadsapi::{GetCurrentThreadId, OpenProcess};
+use winapi::um::winbase::{GlobalAlloc, GlobalLock, GlobalUnlock, QueryFullProcessImageNameW, GMEM_MOVEABLE, GMEM_ZEROINIT};
+use winapi::um::wingdi::{
+    BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, DeleteDC, DeleteObject, GetDIBits, SelectObject, CAPTUREBLT, SRCCOPY, GetObjectW, BITMAP, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS};
+use winapi::um::objidl::IDataObject;
+use winapi::um::ole2::OleInitialize;
+use winapi::um::oleidl::{IDropTarget, DROPEFFECT_COPY, DROPEFFECT_LINK, DROPEFFECT_MOVE, DROPEFFECT_NONE};
+use winapi::um::shellapi::{DragQueryFileW, SHGetFileInfoW, HDROP, SHFILEINFOW, SHGFI_DISPLAYNAME};
+use winapi::um::shobjidl_core::{IShellItemArray, SHCreateItemFromParsingName};
+use winapi::um::shtypes::{PCIDLIST_ABSOLUTE_ARRAY, PIDLIST_ABSOLUTE};
+use winapi::um::winnt::{FILE_ATTRIBUTE_NORMAL, LPCWSTR, PROCESS_QUERY_LIMITED_INFORMATION};
+use winapi::Interface;
+use winapi::um::winreg::{RegGetValueW, HKEY_CURRENT_USER, RRF_RT_REG_DWORD};
+use winapi::shared::windef::{HBITMAP, HDC, HMONITOR, LPRECT, POINT, POINTL, RECT, SIZE, SIZEL};
+use winapi::um::shellscalingapi::{GetDpiForMonitor, MDT_EFFECTIVE_DPI};
+use winapi::um::winuser::{
+    EnumDisplayMonitors, GetAncestor, GetAsyncKeyState, GetForegroundWindow, GetCursorPos, GetMonitorInfoW, GetSystemMetrics, MONITORINFO,
+    GetWindowThreadProcessId, MONITORINFOF_PRIMARY, SM_SWAPBUTTON, VK_LBUTTON,
+    VK_RBUTTON, MonitorFromPoint, SendMessageTimeoutW, WindowFromPoint, GA_ROOT,
+    HTCAPTION, HTCLOSE, HTHELP, HTMAXBUTTON, HTMINBUTTON, HTSIZEFIRST, HTSIZELAST, HTSYSMENU,
+    MONITOR_DEFAULTTONULL, SMTO_ABORTIFHUNG, WM_NCHITTEST, RegisterClipboardFormatW, GetDC, ReleaseDC,
+    AddClipboardFormatListener, CreateWindowExW, DestroyWindow, DispatchMessageW, IsClipboardFormatAvailable,
+    MsgWaitForMultipleObjects, PeekMessageW, RemoveClipboardFormatListener, CF_BITMAP, CF_DIB, CF_HDROP, CF_UNICODETEXT,
+    HWND_MESSAGE, MSG, PM_REMOVE, QS_ALLINPUT, WM_CLIPBOARDUPDATE, CloseClipboard, GetClipboardData, OpenClipboard,
+    GetPropW, IsWindow, MonitorFromWindow, ScreenToClient, MONITOR_DEFAULTTONEAREST, EnumWindows, GetWindow, GetWindowRect,
+    IsWindowVisible, GW_OWNER,
+};
+
+// 命中测试超时时间，避免被无响应的窗口阻塞钩子线程
+const HIT_TEST_TIMEOUT_MS: u32 = 20;
+
+// 向按下点所在的顶层窗口发送 WM_NCHITTEST，模拟系统判断该点属于哪个非客户区
+pub fn window_region_at(x: f64, y: f64) -> WindowRegion {
+    unsafe {
+        let point = POINT {
+            x: x as i32,
+            y: y as i32,
+        };
+        let hwnd = WindowFromPoint(point);
+        if hwnd.is_null() {
+            return WindowRegion::Unknown;
+        }
+        let root = GetAncestor(hwnd, GA_ROOT);
+        let target = if root.is_null() { hwnd } else { root };
+
+        let lparam = (((point.y as u16 as u32) << 16) | point.x as u16 as u32) as LPARAM;
+        let mut hit: DWORD_PTR = 0;
+        let sent = SendMessageTimeoutW(
+            target,
+            WM_NCHITTEST,
+            0,
+            lparam,
+            SMTO_ABORTIFHUNG,
+            HIT_TEST_TIMEOUT_MS,
+            &mut hit,
+        );
+        if sent == 0 {
+            return WindowRegion::Unknown;
+        }
+
+        match hit as isize {
+            HTCAPTION | HTSYSMENU | HTMINBUTTON | HTMAXBUTTON | HTCLOSE | HTHELP => {
+                WindowRegion::Caption
+            }
+            code if (HTSIZEFIRST..=HTSIZELAST).contains(&code) => WindowRegion::Resize,
+            _ => WindowRegion::Content,
+        }
+    }
+}
+
+// 该点上的窗口所属的进程
+pub fn window_pid_at(x: f64, y: f64) -> Option<u32> {
+    let point = POINT {
+        x: x as i32,
+        y: y as i32,
+    };
+    let hwnd = unsafe { WindowFromPoint(point) };
+    if hwnd.is_null() {
+        return None;
+    }
+    let mut pid: DWORD = 0;
+    unsafe { GetWindowThreadProcessId(hwnd, &mut pid) };
+    (pid != 0).then_some(pid)
+}
+
+// 窗口可见的外框：DWM 的扩展边框不含四周不可见的缩放边框，与用户看到的窗口一致；
+// 窗口未经 DWM 合成时退回 GetWindowRect
+pub fn window_bounds(handle: usize) -> Option<Rect> {
+    let hwnd = handle as HWND;
+    unsafe {
+        if hwnd.is_null() || IsWindow(hwnd) == FALSE {
+            return None;
+        }
+        let mut rect: RECT = std::mem::zeroed();
+        let hr = DwmGetWindowAttribute(
+            hwnd,
+            DWMWA_EXTENDED_FRAME_BOUNDS,
+            &mut rect as *mut RECT as *mut c_void,
+            std::mem::size_of::<RECT>() as DWORD,
+        );
+        if FAILED(hr) && GetWindowRect(hwnd, &mut rect) == FALSE {
+            return None;
+        }
+        Some(to_rect(&rect))
+    }
+}
+
+// 被 DWM 隐藏（cloaked）的窗口不可见，例如其他虚拟桌面上的窗口和挂起的 UWP 应用
+unsafe fn is_cloaked(hwnd: HWND) -> bool {
+    let mut cloaked: DWORD = 0;
+    let hr = DwmGetWindowAttribute(hwnd, DWMWA_CLOAKED, &mut cloaked as *mut DWORD as *mut c_void, std::mem::size_of::<DWORD>() as DWORD);
+    SUCCEEDED(hr) && cloaked != 0
+}
+
+unsafe extern "system" fn find_process_window(hwnd: HWND, data: LPARAM) -> BOOL {
+    let (target_pid, found) = &mut *(data as *mut (DWORD, HWND));
+    let mut pid: DWORD = 0;
+    GetWindowThreadProcessId(hwnd, &mut pid);
+    if pid != *target_pid || IsWindowVisible(hwnd) == FALSE || !GetWindow(hwnd, GW_OWNER).is_null() || is_cloaked(hwnd) {
+        return TRUE;
+    }
+    *found = hwnd;
+    FALSE
+}
+
+// EnumWindows 按 Z 序从上到下枚举，取该进程最上层的可见顶层窗口，跳过对话框等有所有者的窗口
+pub fn process_window_bounds(pid: u32) -> Option<Rect> {
+    let mut search: (DWORD, HWND) = (pid, ptr::null_mut());
+    unsafe { EnumWindows(Some(find_process_window), &mut search as *mut _ as LPARAM) };
+    if search.1.is_null() {
+        return None;
+    }
+    window_bounds(search.1 as usize)
+}
+
+// 前台窗口所属的进程；提升权限的进程可能无法打开，此时返回 None
+pub fn frontmost_app() -> Option<FrontmostApp> {
+    let hwnd = unsafe { GetForegroundWindow() };
+    if hwnd.is_null() {
+        return None;
+    }
+    let mut pid: DWORD = 0;
+    unsafe { GetWindowThreadProcessId(hwnd, &mut pid) };
+    if pid == 0 {
+        return None;
+    }
+    let mut path = [0u16; MAX_PATH * 4];
+    let mut length = path.len() as DWORD;
+    let found = unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, FALSE, pid);
+        if process.is_null() {
+            return None;
+        }
+        let found = QueryFullProcessImageNameW(process, 0, path.as_mut_ptr(), &mut length);
+        CloseHandle(process);
+        found
+    };
+    if found == 0 {
+        return None;
+    }
+    let path = PathBuf::from(String::from_utf16_lossy(&path[..length as usize]));
+    Some(FrontmostApp::from_executable(pid, path))
+}
+
+pub fn cursor_position() -> Option<(f64, f64)> {
+    let mut point = POINT { x: 0, y: 0 };
+    if unsafe { GetCursorPos(&mut point) } == 0 {
+        return None;
+    }
+    Some((point.x as f64, point.y as f64))
+}
+
+// 以 HMONITOR 句柄作为显示器标识，显示器配置不变时保持稳定；不在任何显示器上时返回 0
+pub fn display_id_at(x: f64, y: f64) -> u32 {
+    let point = POINT {
+        x: x as i32,
+        y: y as i32,
+    };
+    let monitor = unsafe { MonitorFromPoint(point, MONITOR_DEFAULTTONULL) };
+    monitor as usize as u32
+}
+
+fn monitor_info(display_id: u32) -> Option<MONITORINFO> {
+    let mut info: MONITORINFO = unsafe { std::mem::zeroed() };
+    info.cbSize = std::mem::size_of::<MONITORINFO>() as u32;
+    if display_id == 0 || unsafe { GetMonitorInfoW(display_id as usize as HMONITOR, &mut info) } == 0 {
+        return None;
+    }
+    Some(info)
+}
+
+// 显示器左上角在虚拟屏幕坐标中的位置，句柄已失效时返回 None
+pub fn display_origin(display_id: u32) -> Option<(f64, f64)> {
+    monitor_info(display_id).map(|info| (info.rcMonitor.left as f64, info.rcMonitor.top as f64))
+}
+
+fn to_rect(rect: &RECT) -> Rect {
+    Rect {
+        x: rect.left as f64,
+        y: rect.top as f64,
+        width: (rect.right - rect.left) as f64,
+        height: (rect.bottom - rect.top) as f64,
+    }
+}
+
+unsafe extern "system" fn collect_monitor(monitor: HMONITOR, _hdc: HDC, _rect: LPRECT, data: LPARAM) -> BOOL {
+    let monitors = &mut *(data as *mut Vec<HMONITOR>);
+    monitors.push(monitor);
+    TRUE
+}
+
+// 缩放比例取有效 DPI 相对 96 的倍数
+fn monitor_scale(monitor: HMONITOR) -> f64 {
+    let (mut dpi_x, mut dpi_y) = (0, 0);
+    if SUCCEEDED(unsafe { GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y) }) {
+        dpi_x as f64 / 96.0
+    } else {
+        1.0
+    }
+}
+
+pub fn displays() -> Vec<DisplayInfo> {
+    let mut monitors: Vec<HMONITOR> = Vec::new();
+    unsafe {
+        EnumDisplayMonitors(ptr::null_mut(), ptr::null(), Some(collect_monitor), &mut monitors as *mut _ as LPARAM);
+    }
+    monitors
+        .into_iter()
+        .filter_map(|monitor| {
+            let id = monitor as usize as u32;
+            let info = monitor_info(id)?;
+            let scale_factor = monitor_scale(monitor);
+            Some(DisplayInfo {
+                id,
+                bounds: to_rect(&info.rcMonitor),
+                work_area: to_rect(&info.rcWork),
+                scale_factor,
+                primary: info.dwFlags & MONITORINFOF_PRIMARY != 0,
+            })
+        })
+        .collect()
+}
+
+// 显示器 ID 与系统坐标下的 bounds，供事件的 displayId 使用
+pub fn display_bounds() -> Vec<(u32, Rect)> {
+    displays().into_iter().map(|display| (display.id, display.bounds)).collect()
+}
+
+// 主显示器尺寸，与 GetCursorPos 及钩子坐标使用相同的 DPI 虚拟化
+pub fn primary_display_size() -> Option<(f64, f64)> {
+    rdev::display_size().ok().map(|(width, height)| (width as f64, height as f64))
+}
+
+// GetAsyncKeyState 报告的是物理按键，左右键互换时主按键是物理右键
+pub fn primary_button_pressed() -> bool {
+    let key = if unsafe { GetSystemMetrics(SM_SWAPBUTTON) } != 0 {
+        VK_RBUTTON
+    } else {
+        VK_LBUTTON
+    };
+    (unsafe { GetAsyncKeyState(key) } as u16 & 0x8000) != 0
+}
+
+// 低级鼠标钩子不需要额外授权
+pub fn input_permission_granted() -> Option<bool> {
+    None
+}
+
+// 只有 macOS 提供可轮询的系统拖拽剪贴板
+pub fn drag_pasteboard_change_count() -> Option<i64> {
+    None
+}
+
+pub fn drag_pasteboard_has_files() -> bool {
+    false
+}
+
+fn wide(value: &str) -> Vec<u16> {
+    value.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+fn read_user_dword(key: &str, value: &str) -> Option<DWORD> {
+    let key = wide(key);
+    let value = wide(value);
+    let mut data: DWORD = 0;
+    let mut size = std::mem::size_of::<DWORD>() as DWORD;
+    let status = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            key.as_ptr(),
+            value.as_ptr(),
+            RRF_RT_REG_DWORD,
+            ptr::null_mut(),
+            &mut data as *mut DWORD as *mut _,
+            &mut size,
+        )
+    };
+    (status == ERROR_SUCCESS as i32).then_some(data)
+}
+
+// 深色模式取自应用主题设置 AppsUseLightTheme，强调色取自 DWM 的 AccentColor（0xAABBGGRR）
+pub fn system_theme() -> Theme {
+    let dark = read_user_dword(
+        "Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize",
+        "AppsUseLightTheme",
+    )
+    .map(|light| light == 0);
+    let accent_color = read_user_dword("Software\\Microsoft\\Windows\\DWM", "AccentColor")
+        .map(|abgr| ((abgr & 0xff) as u8, ((abgr >> 8) & 0xff) as u8, ((abgr >> 16) & 0xff) as u8));
+    Theme { dark, accent_color }
+}
+
+// 拖出文件：在独立的 STA 线程上通过 Shell 生成 IDataObject（包含 CF_HDROP 和 Shell IDList 等格式），
+// 再用 SHDoDragDrop 运行 OLE 拖拽循环；不传 IDropSource 时 Shell 使用默认实现和默认拖拽图像
+
+// {B8C0BD9F-ED24-455C-83E6-D5390C4FE8C4}
+const BHID_DATA_OBJECT: GUID = GUID {
+    Data1: 0xb8c0bd9f,
+    Data2: 0xed24,
+    Data3: 0x455c,
+    Data4: [0x83, 0xe6, 0xd5, 0x39, 0x0c, 0x4f, 0xe8, 0xc4],
+};
+
+#[link(name = "shell32")]
+extern "system" {
+    fn ILCreateFromPathW(path: LPCWSTR) -> PIDLIST_ABSOLUTE;
+    fn ILFree(pidl: PIDLIST_ABSOLUTE);
+    fn SHCreateShellItemArrayFromIDLists(
+        cidl: UINT,
+        rgpidl: PCIDLIST_ABSOLUTE_ARRAY,
+        items: *mut *mut IShellItemArray,
+    ) -> HRESULT;
+    fn SHDoDragDrop(
+        hwnd: HWND,
+        data: *mut IDataObject,
+        source: *mut c_void, // IDropSource，传 null 使用 Shell 默认实现
+        allowed_effects: DWORD,
+        effect: *mut DWORD,
+    ) -> HRESULT;
+    fn SHCreateStdEnumFmtEtc(count: UINT, formats: *const FORMATETC, enumerator: *mut *mut IEnumFORMATETC) -> HRESULT;
+}
+
+#[link(name = "shlwapi")]
+extern "system" {
+    fn SHCreateStreamOnFileEx(
+        path: LPCWSTR,
+        mode: DWORD,
+        attributes: DWORD,
+        create: BOOL,
+        template: *mut IStream,
+        stream: *mut *mut IStream,
+    ) -> HRESULT;
+}
+
+#[link(name = "ole32")]
+extern "system" {
+    fn OleUninitialize();
+    fn OleSetClipboard(data: *mut IDataObject) -> HRESULT;
+    fn OleFlushClipboard() -> HRESULT;
+    fn RegisterDragDrop(hwnd: HWND, target: *mut DropTargetInterface) -> HRESULT;
+    fn RevokeDragDrop(hwnd: HWND) -> HRESULT;
+    fn ReleaseStgMedium(medium: *mut STGMEDIUM);
+}
+
+unsafe fn create_data_object(paths: &[String]) -> Result<*mut IDataObject, String> {
+    let mut pidls = Vec::with_capacity(paths.len());
+    for path in paths {
+        let pidl = ILCreateFromPathW(wide(path).as_ptr());
+        if pidl.is_null() {
+            pidls.into_iter().for_each(|pidl| ILFree(pidl));
+            return Err(format!("Path does not exist: {}", path));
+        }
+        pidls.push(pidl);
+    }
+
+    let mut items: *mut IShellItemArray = ptr::null_mut();
+    let mut data: *mut IDataObject = ptr::null_mut();
+    let mut hr = SHCreateShellItemArrayFromIDLists(pidls.len() as UINT, pidls.as_ptr() as PCIDLIST_ABSOLUTE_ARRAY, &mut items);
+    if SUCCEEDED(hr) {
+        hr = (*items).BindToHandler(
+            ptr::null_mut(),
+            &BHID_DATA_OBJECT,
+            &IDataObject::uuidof(),
+            &mut data as *mut *mut IDataObject as *mut *mut c_void,
+        );
+        (*items).Release();
+    }
+    pidls.into_iter().for_each(|pidl| ILFree(pidl));
+
+    if SUCCEEDED(hr) && !data.is_null() {
+        Ok(data)
+    } else {
+        Err(format!("Failed to create drag data object (HRESULT 0x{:08x})", hr))
+    }
+}
+
+// 运行 OLE 拖拽循环直到放下或取消，返回目标接受的操作
+unsafe fn do_drag_drop(data: *mut IDataObject, allowed_effects: DWORD) -> DropEffect {
+    let mut effect: DWORD = DROPEFFECT_NONE;
+    let hr = SHDoDragDrop(ptr::null_mut(), data, ptr::null_mut(), allowed_effects, &mut effect);
+    if hr != DRAGDROP_S_DROP {
+        DropEffect::None
+    } else if effect & DROPEFFECT_MOVE != 0 {
+        DropEffect::Move
+    } else if effect & DROPEFFECT_COPY != 0 {
+        DropEffect::Copy
+    } else if effect & DROPEFFECT_LINK != 0 {
+        DropEffect::Link
+    } else {
+        DropEffect::None
+    }
+}
+
+// Windows 使用 Shell 默认的拖拽图像，image_path/image_size 不生效
+pub fn start_file_drag(paths: &[String], _image_path: Option<&str>, _image_size: f64, on_end: FileDragCallback) {
+    let paths = paths.to_vec();
+    thread::spawn(move || unsafe {
+        if FAILED(OleInitialize(ptr::null_mut())) {
+            on_end(Err("Failed to initialize OLE".to_string()));
+            return;
+        }
+        let result = create_data_object(&paths).map(|data| {
+            let effect = do_drag_drop(data, DROPEFFECT_COPY | DROPEFFECT_MOVE | DROPEFFECT_LINK);
+            (*data).Release();
+            effect
+        });
+        OleUninitialize();
+        on_end(result);
+    });
+}
+
+// 资源管理器中显示的名称：本地化的系统文件夹名，并按用户设置隐藏扩展名
+pub fn display_name(path: &str) -> Option<String> {
+    let mut info: SHFILEINFOW = unsafe { std::mem::zeroed() };
+    let found = unsafe {
+        SHGetFileInfoW(
+            wide(path).as_ptr(),
+            0,
+            &mut info,
+            std::mem::size_of::<SHFILEINFOW>() as UINT,
+            SHGFI_DISPLAYNAME,
+        )
+    };
+    if found == 0 {
+        return None;
+    }
+    let length = info.szDisplayName.iter().position(|c| *c == 0).unwrap_or(info.szDisplayName.len());
+    Some(String::from_utf16_lossy(&info.szDisplayName[..length]))
+}
+
+// 拖出虚拟文件：自己实现 IDataObject，只提供 CFSTR_FILEDESCRIPTORW 和 CFSTR_FILECONTENTS 两种格式
+// 目标请求 FileContents 时才调用 provider 生成内容，这发生在拖拽线程的 OLE 消息循环中，阻塞等待不影响 JS 线程
+// 不支持 SetData，因此 Shell 无法附加拖拽图像，拖拽时只显示光标
+
+const CFSTR_FILEDESCRIPTORW: &str = "FileGroupDescriptorW";
+const CFSTR_FILECONTENTS: &str = "FileContents";
+const FD_PROGRESSUI: DWORD = 0x4000;
+const STGM_SHARE_DENY_WRITE: DWORD = 0x20;
+
+// shlobj_core.h 中的 FILEDESCRIPTORW，winapi 未提供
+#[repr(C)]
+struct FileDescriptorW {
+    flags: DWORD,
+    clsid: GUID,
+    size: SIZEL,
+    point: POINTL,
+    file_attributes: DWORD,
+    creation_time: FILETIME,
+    last_access_time: FILETIME,
+    last_write_time: FILETIME,
+    file_size_high: DWORD,
+    file_size_low: DWORD,
+    file_name: [u16; MAX_PATH],
+}
+
+// COM 对象：vtbl 必须是第一个字段，接口指针即指向本结构体
+#[repr(C)]
+struct PromiseDataObject {
+    vtbl: *const IDataObjectVtbl,
+    refs: AtomicU32,
+    files: Vec<PromisedFile>,
+    provider: FilePromiseProvider,
+    // 已生成的内容，目标可能对同一个文件多次请求
+    contents: RefCell<Vec<Option<PathBuf>>>,
+    // 第一次生成失败的原因，拖拽结束后报告给调用方
+    error: RefCell<Option<String>>,
+    descriptor_format: CLIPFORMAT,
+    contents_format: CLIPFORMAT,
+}
+
+static PROMISE_DATA_OBJECT_VTBL: IDataObjectVtbl = IDataObjectVtbl {
+    parent: IUnknownVtbl {
+        QueryInterface: promise_query_interface,
+        AddRef: promise_add_ref,
+        Release: promise_release,
+    },
+    GetData: promise_get_data,
+    GetDataHere: promise_get_data_here,
+    QueryGetData: promise_query_get_data,
+    GetCanonicalFormatEtc: promise_get_canonical_format_etc,
+    SetData: promise_set_data,
+    EnumFormatEtc: promise_enum_format_etc,
+    DAdvise: promise_d_advise,
+    DUnadvise: promise_d_unadvise,
+    EnumDAdvise: promise_enum_d_advise,
+};
+
+impl PromiseDataObject {
+    fn create(files: Vec<PromisedFile>, provider: FilePromiseProvider) -> *mut IDataObject {
+        let register = |name: &str| unsafe { RegisterClipboardFormatW(wide(name).as_ptr()) } as CLIPFORMAT;
+        let contents = RefCell::new(vec![None; files.len()]);
+        let object = Box::new(PromiseDataObject {
+            vtbl: &PROMISE_DATA_OBJECT_VTBL,
+            refs: AtomicU32::new(1),
+            files,
+            provider,
+            contents,
+            error: RefCell::new(None),
+            descriptor_format: register(CFSTR_FILEDESCRIPTORW),
+            contents_format: register(CFSTR_FILECONTENTS),
+        });
+        Box::into_raw(object) as *mut IDataObject
+    }
+
+    unsafe fn from_interface<'a, T>(this: *mut T) -> &'a PromiseDataObject {
+        &*(this as *const PromiseDataObject)
+    }
+
+    fn formats(&self) -> [FORMATETC; 2] {
+        let format = |cf_format, lindex, tymed| FORMATETC {
+            cfFormat: cf_format,
+            ptd: ptr::null(),
+            dwAspect: DVASPECT_CONTENT,
+            lindex,
+            tymed,
+        };
+        [
+            format(self.descriptor_format, -1, TYMED_HGLOBAL),
+            format(self.contents_format, 0, TYMED_ISTREAM | TYMED_HGLOBAL),
+        ]
+    }
+
+    // FILEGROUPDESCRIPTORW：文件数 + 每个文件的描述，大小未知时不填写
+    unsafe fn file_group_descriptor(&self) -> HGLOBAL {
+        let size = std::mem::size_of::<UINT>() + self.files.len() * std::mem::size_of::<FileDescriptorW>();
+        let global = GlobalAlloc(GMEM_MOVEABLE | GMEM_ZEROINIT, size);
+        if global.is_null() {
+            return global;
+        }
+        let base = GlobalLock(global) as *mut u8;
+        *(base as *mut UINT) = self.files.len() as UINT;
+        let descriptors = base.add(std::mem::size_of::<UINT>()) as *mut FileDescriptorW;
+        for (index, file) in self.files.iter().enumerate() {
+            let descriptor = &mut *descriptors.add(index);
+            descriptor.flags = FD_PROGRESSUI;
+            let name: Vec<u16> = file.file_name.encode_utf16().take(MAX_PATH - 1).collect();
+            descriptor.file_name[..name.len()].copy_from_slice(&name);
+        }
+        GlobalUnlock(global);
+        global
+    }
+
+    // 生成第 index 个文件的内容，结果被缓存
+    fn materialize(&self, index: usize) -> Option<PathBuf> {
+        if let Some(path) = &self.contents.borrow()[index] {
+            return Some(path.clone());
+        }
+        match (self.provider)(index) {
+            Ok(path) => {
+                self.contents.borrow_mut()[index] = Some(path.clone());
+                Some(path)
+            }
+            Err(message) => {
+                self.error.borrow_mut().get_or_insert(message);
+                None
+            }
+        }
+    }
+}
+
+fn read_into_hglobal(path: &Path) -> Option<HGLOBAL> {
+    let bytes = std::fs::read(path).ok()?;
+    unsafe {
+        let global = GlobalAlloc(GMEM_MOVEABLE, bytes.len().max(1));
+        if global.is_null() {
+            return None;
+        }
+        ptr::copy_nonoverlapping(bytes.as_ptr(), GlobalLock(global) as *mut u8, bytes.len());
+        GlobalUnlock(global);
+        Some(global)
+    }
+}
+
+unsafe extern "system" fn promise_query_interface(this: *mut IUnknown, riid: REFIID, object: *mut *mut c_void) -> HRESULT {
+    if IsEqualGUID(&*riid, &IUnknown::uuidof()) || IsEqualGUID(&*riid, &IDataObject::uuidof()) {
+        promise_add_ref(this);
+        *object = this as *mut c_void;
+        S_OK
+    } else {
+        *object = ptr::null_mut();
+        E_NOINTERFACE
+    }
+}
+
+unsafe extern "system" fn promise_add_ref(this: *mut IUnknown) -> ULONG {
+    PromiseDataObject::from_interface(this).refs.fetch_add(1, Ordering::AcqRel) + 1
+}
+
+unsafe extern "system" fn promise_release(this: *mut IUnknown) -> ULONG {
+    let refs = PromiseDataObject::from_interface(this).refs.fetch_sub(1, Ordering::AcqRel) - 1;
+    if refs == 0 {
+        drop(Box::from_raw(this as *mut PromiseDataObject));
+    }
+    refs
+}
+
+unsafe extern "system" fn promise_get_data(this: *mut IDataObject, format: *const FORMATETC, medium: *mut STGMEDIUM) -> HRESULT {
+    let object = PromiseDataObject::from_interface(this);
+    let format = &*format;
+    if format.dwAspect != DVASPECT_CONTENT {
+        return DV_E_DVASPECT;
+    }
+
+    let global_medium = |global: HGLOBAL| {
+        (*medium).tymed = TYMED_HGLOBAL;
+        (*medium).u = global as *mut STGMEDIUM_u;
+        (*medium).pUnkForRelease = ptr::null_mut();
+    };
+    if format.cfFormat == object.descriptor_format {
+        if format.tymed & TYMED_HGLOBAL == 0 {
+            return DV_E_TYMED;
+        }
+        let global = object.file_group_descriptor();
+        if global.is_null() {
+            return E_OUTOFMEMORY;
+        }
+        global_medium(global);
+        return S_OK;
+    }
+    if format.cfFormat != object.contents_format {
+        return DV_E_FORMATETC;
+    }
+
+    let Some(index) = usize::try_from(format.lindex).ok().filter(|index| *index < object.files.len()) else {
+        return DV_E_LINDEX;
+    };
+    let Some(path) = object.materialize(index) else {
+        return E_FAIL;
+    };
+    if format.tymed & TYMED_ISTREAM != 0 {
+        let mut stream: *mut IStream = ptr::null_mut();
+        let hr = SHCreateStreamOnFileEx(
+            wide(&path.to_string_lossy()).as_ptr(),
+            STGM_READ | STGM_SHARE_DENY_WRITE,
+            FILE_ATTRIBUTE_NORMAL,
+            FALSE,
+            ptr::null_mut(),
+            &mut stream,
+        );
+        if FAILED(hr) {
+            return hr;
+        }
+        (*medium).tymed = TYMED_ISTREAM;
+        (*medium).u = stream as *mut STGMEDIUM_u;
+        (*medium).pUnkForRelease = ptr::null_mut();
+        S_OK
+    } else if format.tymed & TYMED_HGLOBAL != 0 {
+        match read_into_hglobal(&path) {
+            Some(global) => {
+                global_medium(global);
+                S_OK
+            }
+            None => E_FAIL,
+        }
+    } else {
+        DV_E_TYMED
+    }
+}
+
+unsafe extern "system" fn promise_get_data_here(_this: *mut IDataObject, _format: *const FORMATETC, _medium: *mut STGMEDIUM) -> HRESULT {
+    E_NOTIMPL
+}
+
+unsafe extern "system" fn promise_query_get_data(this: *mut IDataObject, format: *const FORMATETC) -> HRESULT {
+    let object = PromiseDataObject::from_interface(this);
+    let format = &*format;
+    let supported = object
+        .formats()
+        .iter()
+        .any(|f| f.cfFormat == format.cfFormat && f.tymed & format.tymed != 0 && format.dwAspect == DVASPECT_CONTENT);
+    if supported {
+        S_OK
+    } else {
+        DV_E_FORMATETC
+    }
+}
+
+unsafe extern "system" fn promise_get_canonical_format_etc(
+    _this: *mut IDataObject,
+    _format_in: *const FORMATETC,
+    format_out: *mut FORMATETC,
+) -> HRESULT {
+    (*format_out).ptd = ptr::null();
+    E_NOTIMPL
+}
+
+unsafe extern "system" fn promise_set_data(
+    _this: *mut IDataObject,
+    _format: *const FORMATETC,
+    _medium: *const FORMATETC,
+    _release: BOOL,
+) -> HRESULT {
+    E_NOTIMPL
+}
+
+unsafe extern "system" fn promise_enum_format_etc(
+    this: *mut IDataObject,
+    direction: DWORD,
+    enumerator: *mut *mut IEnumFORMATETC,
+) -> HRESULT {
+    if direction != DATADIR_GET {
+        *enumerator = ptr::null_mut();
+        return E_NOTIMPL;
+    }
+    let formats = PromiseDataObject::from_interface(this).formats();
+    SHCreateStdEnumFmtEtc(formats.len() as UINT, formats.as_ptr(), enumerator)
+}
+
+unsafe extern "system" fn promise_d_advise(
+    _this: *mut IDataObject,
+    _format: *const FORMATETC,
+    _advf: DWORD,
+    _sink: *const IAdviseSink,
+    _connection: *mut DWORD,
+) -> HRESULT {
+    OLE_E_ADVISENOTSUPPORTED
+}
+
+unsafe extern "system" fn promise_d_unadvise(_this: *mut IDataObject, _connection: DWORD) -> HRESULT {
+    OLE_E_ADVISENOTSUPPORTED
+}
+
+unsafe extern "system" fn promise_enum_d_advise(_this: *mut IDataObject, _enumerator: *const *const IEnumSTATDATA) -> HRESULT {
+    OLE_E_ADVISENOTSUPPORTED
+}
+
+#[cfg_attr(not(feature = "async-runtime"), allow(dead_code))]
+pub fn start_file_promise_drag(
+    files: &[PromisedFile],
+    _image_path: Option<&str>,
+    _image_size: f64,
+    provider: FilePromiseProvider,
+    on_end: FileDragCallback,
+) {
+    let files = files.to_vec();
+    thread::spawn(move || unsafe {
+        if FAILED(OleInitialize(ptr::null_mut())) {
+            on_end(Err("Failed to initialize OLE".to_string()));
+            return;
+        }
+        let data = PromiseDataObject::create(files, provider);
+        // 虚拟文件只能被复制，目标接受放下时会在拖拽循环内同步请求内容
+        let effect = do_drag_drop(data, DROPEFFECT_COPY);
+        let error = PromiseDataObject::from_interface(data).error.borrow_mut().take();
+        (*data).Release();
+        OleUninitialize();
+        on_end(match error {
+            Some(message) => Err(message),
+            None => Ok(effect),
+        });
+    });
+}
+
+// 拖拽预览：IShellItemImageFactory 对图片返回缩略图，对其他文件返回图标，与资源管理器显示一致
+
+// {BCC18B79-BA16-442F-80C4-8A59C30C463B}
+const IID_ISHELL_ITEM_IMAGE_FACTORY: GUID = GUID {
+    Data1: 0xbcc18b79,
+    Data2: 0xba16,
+    Data3: 0x442f,
+    Data4: [0x80, 0xc4, 0x8a, 0x59, 0xc3, 0x0c, 0x46, 0x3b],
+};
+const SIIGBF_RESIZETOFIT: u32 = 0x0;
+
+// shobjidl_core.h 中的 IShellItemImageFactory，winapi 未提供
+#[repr(C)]
+struct IShellItemImageFactory {
+    vtbl: *const IShellItemImageFactoryVtbl,
+}
+
+#[repr(C)]
+struct IShellItemImageFactoryVtbl {
+    parent: IUnknownVtbl,
+    get_image: unsafe extern "system" fn(this: *mut IShellItemImageFactory, size: SIZE, flags: u32, bitmap: *mut HBITMAP) -> HRESULT,
+}
+
+unsafe fn shell_item_image(path: &str, size: u32) -> Option<HBITMAP> {
+    let mut factory: *mut IShellItemImageFactory = ptr::null_mut();
+    let hr = SHCreateItemFromParsingName(
+        wide(path).as_ptr(),
+        ptr::null_mut(),
+        &IID_ISHELL_ITEM_IMAGE_FACTORY,
+        &mut factory as *mut *mut IShellItemImageFactory as *mut *mut c_void,
+    );
+    if FAILED(hr) || factory.is_null() {
+        return None;
+    }
+    let mut bitmap: HBITMAP = ptr::null_mut();
+    let extent = SIZE {
+        cx: size as i32,
+        cy: size as i32,
+    };
+    let hr = ((*(*factory).vtbl).get_image)(factory, extent, SIIGBF_RESIZETOFIT, &mut bitmap);
+    ((*(*factory).vtbl).parent.Release)(factory as *mut IUnknown);
+    (SUCCEEDED(hr) && !bitmap.is_null()).then_some(bitmap)
+}
+
+// 读出 32 位 DIB 的像素（自上而下的预乘 BGRA）
+unsafe fn bitmap_pixels(bitmap: HBITMAP) -> Option<(usize, usize, Vec<u8>)> {
+    let mut info: BITMAP = std::mem::zeroed();
+    if GetObjectW(bitmap as *mut c_void, std::mem::size_of::<BITMAP>() as i32, &mut info as *mut BITMAP as *mut c_void) == 0 {
+        return None;
+    }
+    let (width, height) = (info.bmWidth.max(0) as usize, info.bmHeight.unsigned_abs() as usize);
+    let mut header: BITMAPINFO = std::mem::zeroed();
+    header.bmiHeader = BITMAPINFOHEADER {
+        biSize: std::mem::size_of::<BITMAPINFOHEADER>() as DWORD,
+        biWidth: width as i32,
+        biHeight: -(height as i32),
+        biPlanes: 1,
+        biBitCount: 32,
+        biCompression: BI_RGB,
+        ..std::mem::zeroed()
+    };
+    let mut pixels = vec![0u8; width * height * 4];
+    let dc = GetDC(ptr::null_mut());
+    let lines = GetDIBits(
+        dc,
+        bitmap,
+        0,
+        height as UINT,
+        pixels.as_mut_ptr() as *mut c_void,
+        &mut header,
+        DIB_RGB_COLORS,
+    );
+    ReleaseDC(ptr::null_mut(), dc);
+    (lines as usize == height).then_some((width, height, pixels))
+}
+
+// 返回 size x size 的非预乘 RGBA，缩略图等比缩放后居中
+pub fn file_preview(path: &str, size: u32) -> Option<Vec<u8>> {
+    unsafe {
+        // AsyncTask 运行在线程池线程上，COM 可能已被其他模式初始化，此时沿用现有的套间
+        let initialized = SUCCEEDED(CoInitializeEx(ptr::null_mut(), COINIT_APARTMENTTHREADED));
+        let result = shell_item_image(path, size).and_then(|bitmap| {
+            let pixels = bitmap_pixels(bitmap);
+            DeleteObject(bitmap as *mut c_void);
+            pixels
+        });
+        if initialized {
+            CoUninitialize();
+        }
+        let (width, height, mut source) = result?;
+
+        // 不带 alpha 的缩略图（如 JPEG）alpha 全为 0，按不透明处理
+        if source.chunks(4).all(|pixel| pixel[3] == 0) {
+            source.chunks_mut(4).for_each(|pixel| pixel[3] = 255);
+        }
+        unpremultiply(&mut source);
+
+        let side = size as usize;
+        let mut pixels = vec![0u8; side * side * 4];
+        let (left, top) = (side.saturating_sub(width) / 2, side.saturating_sub(height) / 2);
+        for y in 0..height.min(side) {
+            for x in 0..width.min(side) {
+                let from = (y * width + x) * 4;
+                let to = ((top + y) * side + left + x) * 4;
+                pixels[to..to + 4].copy_from_slice(&[source[from + 2], source[from + 1], source[from], source[from + 3]]);
+            }
+        }
+        Some(pixels)
+    }
+}
+
+// 截取虚拟屏幕上的矩形区域（物理像素），返回非预乘的 RGBA
+pub fn capture_region(x: f64, y: f64, width: u32, height: u32) -> Option<Vec<u8>> {
+    unsafe {
+        let screen = GetDC(ptr::null_mut());
+        if screen.is_null() {
+            return None;
+        }
+        let memory = CreateCompatibleDC(screen);
+        let bitmap = CreateCompatibleBitmap(screen, width as i32, height as i32);
+        let previous = SelectObject(memory, bitmap as *mut c_void);
+        // CAPTUREBLT 让分层窗口（阴影、半透明窗口）也被截取
+        let copied = BitBlt(memory, 0, 0, width as i32, height as i32, screen, x.floor() as i32, y.floor() as i32, SRCCOPY | CAPTUREBLT);
+        SelectObject(memory, previous);
+        DeleteDC(memory);
+        ReleaseDC(ptr::null_mut(), screen);
+
+        let pixels = if copied != 0 { bitmap_pixels(bitmap) } else { None };
+        DeleteObject(bitmap as *mut c_void);
+        let (_, _, mut pixels) = pixels?;
+        // 屏幕内容不带 alpha，BGRA 转为不透明的 RGBA
+        for pixel in pixels.chunks_mut(4) {
+            pixel.swap(0, 2);
+            pixel[3] = 255;
+        }
+        Some(pixels)
+    }
+}
+
+// WM_GESTURE / WM_TOUCH 只发送给调用过 RegisterTouchWindow 的窗口，低级钩子看不到，
+// 因此无法在不接管应用窗口消息的情况下全局监听
+pub fn watch_gestures(_running: Arc<AtomicBool>, _on_gesture: GestureCallback) -> Result<(), String> {
+    Err("Gesture events are only supported on macOS".to_string())
+}
+
+// 剪贴板线程在两次检查停止标志之间等待消息的最长时间
+const CLIPBOARD_WAIT_MS: DWORD = 200;
+
+// 系统会从 CF_TEXT 合成 CF_UNICODETEXT、从 CF_BITMAP 合成 CF_DIB，浏览器和截图工具另外提供 "PNG"
+fn clipboard_contents() -> ClipboardContents {
+    let png = wide("PNG");
+    unsafe {
+        let available = |format: UINT| format != 0 && IsClipboardFormatAvailable(format) != FALSE;
+        ClipboardContents {
+            files: available(CF_HDROP),
+            text: available(CF_UNICODETEXT),
+            image: available(CF_DIB) || available(CF_BITMAP) || available(RegisterClipboardFormatW(png.as_ptr())),
+        }
+    }
+}
+
+// 在当前线程上用只接收消息的窗口注册剪贴板格式监听，直到 running 被置为 false；开始时的内容不报告
+pub fn watch_clipboard(running: Arc<AtomicBool>, on_change: ClipboardCallback) -> Result<(), String> {
+    let mut on_change = on_change;
+    let class = wide("STATIC");
+    unsafe {
+        let hwnd = CreateWindowExW(
+            0,
+            class.as_ptr(),
+            ptr::null(),
+            0,
+            0,
+            0,
+            0,
+            0,
+            HWND_MESSAGE,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+        );
+        if hwnd.is_null() {
+            return Err("Failed to create clipboard listener window".to_string());
+        }
+        if AddClipboardFormatListener(hwnd) == FALSE {
+            DestroyWindow(hwnd);
+            return Err("AddClipboardFormatListener failed".to_string());
+        }
+
+        let mut msg: MSG = std::mem::zeroed();
+        while running.load(Ordering::SeqCst) {
+            MsgWaitForMultipleObjects(0, ptr::null(), FALSE, CLIPBOARD_WAIT_MS, QS_ALLINPUT);
+            while PeekMessageW(&mut msg, ptr::null_mut(), 0, 0, PM_REMOVE) != FALSE {
+                if msg.hwnd == hwnd && msg.message == WM_CLIPBOARDUPDATE {
+                    if running.load(Ordering::SeqCst) {
+                        on_change(clipboard_contents());
+                    }
+                } else {
+                    DispatchMessageW(&msg);
+                }
+            }
+        }
+
+        RemoveClipboardFormatListener(hwnd);
+        DestroyWindow(hwnd);
+    }
+    Ok(())
+}
+
+// 与拖出文件使用同一个 Shell 数据对象（CF_HDROP、Shell IDList 等格式），资源管理器可以直接粘贴；
+// OleFlushClipboard 把各个格式渲染到剪贴板上，之后数据对象和 OLE 线程都可以释放
+pub fn write_files_to_clipboard(paths: &[String]) -> Result<(), String> {
+    let paths = paths.to_vec();
+    thread::spawn(move || unsafe {
+        if FAILED(OleInitialize(ptr::null_mut())) {
+            return Err("Failed to initialize OLE".to_string());
+        }
+        let result = create_data_object(&paths).and_then(|data| {
+            let mut hr = OleSetClipboard(data);
+            if SUCCEEDED(hr) {
+                hr = OleFlushClipboard();
+            }
+            (*data).Release();
+            if SUCCEEDED(hr) {
+                Ok(())
+            } else {
+                Err(format!("Failed to set the clipboard (HRESULT 0x{:08x})", hr))
+            }
+        });
+        OleUninitialize();
+        result
+    })
+    .join()
+    .map_err(|_| "Clipboard thread panicked".to_string())?
+}
+
+// 剪贴板被其他应用打开时 OpenClipboard 失败，返回错误而不是空列表
+pub fn read_files_from_clipboard() -> Result<Vec<String>, String> {
+    unsafe {
+        if OpenClipboard(ptr::null_mut()) == FALSE {
+            return Err("The clipboard is in use by another application".to_string());
+        }
+        let hdrop = GetClipboardData(CF_HDROP) as HDROP;
+        let paths = if hdrop.is_null() { Vec::new() } else { hdrop_paths(hdrop) };
+        CloseClipboard();
+        Ok(paths)
+    }
+}
+
+unsafe fn hdrop_paths(hdrop: HDROP) -> Vec<String> {
+    let count = DragQueryFileW(hdrop, 0xFFFF_FFFF, ptr::null_mut(), 0);
+    (0..count)
+        .map(|index| {
+            let length = DragQueryFileW(hdrop, index, ptr::null_mut(), 0);
+            let mut buffer = vec![0u16; length as usize + 1];
+            let copied = DragQueryFileW(hdrop, index, buffer.as_mut_ptr(), buffer.len() as UINT);
+            String::from_utf16_lossy(&buffer[..copied as usize])
+        })
+        .collect()
+}
+
+// 放置目标：替换 Chromium 注册在窗口上的 IDropTarget。包含 CF_HDROP 的拖拽由插件处理，
+// 其他拖拽（文本、链接等）原样转发给原来的目标，页面仍然可以接收。
+// winapi 的 IDropTargetVtbl 把按值传递的 POINTL 声明成了指针，与实际的调用约定不符，这里自己定义虚表
+
+#[repr(C)]
+struct DropTargetInterface {
+    vtbl: *const DropTargetVtbl,
+}
+
+#[repr(C)]
+struct DropTargetVtbl {
+    query_interface: unsafe extern "system" fn(*mut DropTargetInterface, REFIID, *mut *mut c_void) -> HRESULT,
+    add_ref: unsafe extern "system" fn(*mut DropTargetInterface) -> ULONG,
+    release: unsafe extern "system" fn(*mut DropTargetInterface) -> ULONG,
+    drag_enter: unsafe extern "system" fn(*mut DropTargetInterface, *mut IDataObject, DWORD, POINTL, *mut DWORD) -> HRESULT,
+    drag_over: unsafe extern "system" fn(*mut DropTargetInterface, DWORD, POINTL, *mut DWORD) -> HRESULT,
+    drag_leave: unsafe extern "system" fn(*mut DropTargetInterface) -> HRESULT,
+    drop: unsafe extern "system" fn(*mut DropTargetInterface, *mut IDataObject, DWORD, POINTL, *mut DWORD) -> HRESULT,
+}
+
+// COM 对象：vtbl 必须是第一个字段，接口指针即指向本结构体
+#[repr(C)]
+struct DropTarget {
+    vtbl: *const DropTargetVtbl,
+    refs: AtomicU32,
+    id: u32,
+    hwnd: HWND,
+    // 注册前窗口上的目标（持有一个引用），没有时为 null
+    previous: *mut DropTargetInterface,
+    on_event: RefCell<DropTargetCallback>,
+    // 当前拖拽包含文件、由插件处理；否则 over/leave/drop 转发给 previous
+    handling: Cell<bool>,
+    // 进入时读取的文件路径，悬停事件沿用
+    paths: RefCell<Vec<String>>,
+    // 最后一次悬停的位置，DragLeave 不带坐标
+    last_point: Cell<(f64, f64)>,
+}
+
+static DROP_TARGET_VTBL: DropTargetVtbl = DropTargetVtbl {
+    query_interface: drop_target_query_interface,
+    add_ref: drop_target_add_ref,
+    release: drop_target_release,
+    drag_enter: drop_target_drag_enter,
+    drag_over: drop_target_drag_over,
+    drag_leave: drop_target_drag_leave,
+    drop: drop_target_drop,
+};
+
+thread_local! {
+    // 挂载中的放置目标，只在窗口所在的 UI 线程上访问
+    static DROP_TARGETS: RefCell<Vec<*mut DropTargetInterface>> = const { RefCell::new(Vec::new()) };
+}
+
+impl DropTarget {
+    unsafe fn from_interface<'a>(this: *mut DropTargetInterface) -> &'a DropTarget {
+        &*(this as *const DropTarget)
+    }
+
+    // 坐标转换为窗口客户区坐标，再按窗口所在显示器的缩放比例换算为页面使用的 DIP
+    fn report(&self, phase: DropTargetPhase, point: Option<POINTL>) {
+        if let Some(point) = point {
+            let mut client = POINT { x: point.x, y: point.y };
+            let scale = unsafe {
+                ScreenToClient(self.hwnd, &mut client);
+                monitor_scale(MonitorFromWindow(self.hwnd, MONITOR_DEFAULTTONEAREST))
+            };
+            self.last_point.set((client.x as f64 / scale, client.y as f64 / scale));
+        }
+        let (x, y) = self.last_point.get();
+        let event = DropTargetEvent {
+            target_id: self.id,
+            phase,
+            x,
+            y,
+            paths: self.paths.borrow().clone(),
+        };
+        (self.on_event.borrow_mut())(event);
+    }
+}
+
+impl Drop for DropTarget {
+    fn drop(&mut self) {
+        if !self.previous.is_null() {
+            unsafe { ((*(*self.previous).vtbl).release)(self.previous) };
+        }
+    }
+}
+
+unsafe fn data_object_paths(data: *mut IDataObject) -> Vec<String> {
+    if data.is_null() {
+        return Vec::new();
+    }
+    let mut format = FORMATETC {
+        cfFormat: CF_HDROP as CLIPFORMAT,
+        ptd: ptr::null(),
+        dwAspect: DVASPECT_CONTENT,
+        lindex: -1,
+        tymed: TYMED_HGLOBAL,
+    };
+    let mut medium: STGMEDIUM = std::mem::zeroed();
+    if FAILED((*data).GetData(&mut format, &mut medium)) {
+        return Vec::new();
+    }
+    let paths = hdrop_paths(medium.u as HDROP);
+    ReleaseStgMedium(&mut medium);
+    paths
+}
+
+// 优先复制，源不允许复制时依次尝试移动、链接
+fn accepted_effect(allowed: DWORD) -> DWORD {
+    [DROPEFFECT_COPY, DROPEFFECT_MOVE, DROPEFFECT_LINK]
+        .into_iter()
+        .find(|effect| allowed & effect != 0)
+        .unwrap_or(DROPEFFECT_NONE)
+}
+
+unsafe extern "system" fn drop_target_query_interface(this: *mut DropTargetInterface, riid: REFIID, object: *mut *mut c_void) -> HRESULT {
+    if IsEqualGUID(&*riid, &IUnknown::uuidof()) || IsEqualGUID(&*riid, &IDropTarget::uuidof()) {
+        drop_target_add_ref(this);
+        *object = this as *mut c_void;
+        S_OK
+    } else {
+        *object = ptr::null_mut();
+        E_NOINTERFACE
+    }
+}
+
+unsafe extern "system" fn drop_target_add_ref(this: *mut DropTargetInterface) -> ULONG {
+    DropTarget::from_interface(this).refs.fetch_add(1, Ordering::AcqRel) + 1
+}
+
+unsafe extern "system" fn drop_target_release(this: *mut DropTargetInterface) -> ULONG {
+    let refs = DropTarget::from_interface(this).refs.fetch_sub(1, Ordering::AcqRel) - 1;
+    if refs == 0 {
+        drop(Box::from_raw(this as *mut DropTarget));
+    }
+    refs
+}
+
+unsafe extern "system" fn drop_target_drag_enter(
+    this: *mut DropTargetInterface,
+    data: *mut IDataObject,
+    key_state: DWORD,
+    point: POINTL,
+    effect: *mut DWORD,
+) -> HRESULT {
+    let target = DropTarget::from_interface(this);
+    let paths = data_object_paths(data);
+    target.handling.set(!paths.is_empty());
+    if paths.is_empty() {
+        if target.previous.is_null() {
+            *effect = DROPEFFECT_NONE;
+            return S_OK;
+        }
+        return ((*(*target.previous).vtbl).drag_enter)(target.previous, data, key_state, point, effect);
+    }
+    *target.paths.borrow_mut() = paths;
+    target.report(DropTargetPhase::Enter, Some(point));
+    *effect = accepted_effect(*effect);
+    S_OK
+}
+
+unsafe extern "system" fn drop_target_drag_over(this: *mut DropTargetInterface, key_state: DWORD, point: POINTL, effect: *mut DWORD) -> HRESULT {
+    let target = DropTarget::from_interface(this);
+    if !target.handling.get() {
+        if target.previous.is_null() {
+            *effect = DROPEFFECT_NONE;
+            return S_OK;
+        }
+        return ((*(*target.previous).vtbl).drag_over)(target.previous, key_state, point, effect);
+    }
+    target.report(DropTargetPhase::Over, Some(point));
+    *effect = accepted_effect(*effect);
+    S_OK
+}
+
+unsafe extern "system" fn drop_target_drag_leave(this: *mut DropTargetInterface) -> HRESULT {
+    let target = DropTarget::from_interface(this);
+    if !target.handling.replace(false) {
+        if target.previous.is_null() {
+            return S_OK;
+        }
+        return ((*(*target.previous).vtbl).drag_leave)(target.previous);
+    }
+    target.paths.borrow_mut().clear();
+    target.report(DropTargetPhase::Leave, None);
+    S_OK
+}
+
+unsafe extern "system" fn drop_target_drop(
+    this: *mut DropTargetInterface,
+    data: *mut IDataObject,
+    key_state: DWORD,
+    point: POINTL,
+    effect: *mut DWORD,
+) -> HRESULT {
+    let target = DropTarget::from_interface(this);
+    if !target.handling.replace(false) {
+        if target.previous.is_null() {
+            *effect = DROPEFFECT_NONE;
+            return S_OK;
+        }
+        return ((*(*target.previous).vtbl).drop)(target.previous, data, key_state, point, effect);
+    }
+    *target.paths.borrow_mut() = data_object_paths(data);
+    target.report(DropTargetPhase::Drop, Some(point));
+    target.paths.borrow_mut().clear();
+    *effect = accepted_effect(*effect);
+    S_OK
+}
+
+// handle 为 BrowserWindow.getNativeWindowHandle() 中的 HWND；必须在创建窗口的 UI 线程
+// （Electron 主进程的 JS 线程）上调用，该线程已初始化 OLE，回调也在这个线程上执行。
+// 原来的目标通过 RegisterDragDrop 保存在窗口属性 "OleDropTargetInterface" 中，卸载时重新注册
+pub fn attach_drop_target(handle: usize, on_event: DropTargetCallback) -> Result<u32, String> {
+    let hwnd = handle as HWND;
+    unsafe {
+        if hwnd.is_null() || IsWindow(hwnd) == FALSE {
+            return Err("Invalid window handle".to_string());
+        }
+        if GetWindowThreadProcessId(hwnd, ptr::null_mut()) != GetCurrentThreadId() {
+            return Err("Drop targets must be attached on the thread that owns the window".to_string());
+        }
+        let attached = DROP_TARGETS.with(|targets| targets.borrow().iter().any(|target| DropTarget::from_interface(*target).hwnd == hwnd));
+        if attached {
+            return Err("A drop target is already attached to this window".to_string());
+        }
+
+        let previous = GetPropW(hwnd, wide("OleDropTargetInterface").as_ptr()) as *mut DropTargetInterface;
+        if !previous.is_null() {
+            ((*(*previous).vtbl).add_ref)(previous);
+            RevokeDragDrop(hwnd);
+        }
+        let id = super::next_drop_target_id();
+        let target = Box::into_raw(Box::new(DropTarget {
+            vtbl: &DROP_TARGET_VTBL,
+            refs: AtomicU32::new(1),
+            id,
+            hwnd,
+            previous,
+            on_event: RefCell::new(on_event),
+            handling: Cell::new(false),
+            paths: RefCell::new(Vec::new()),
+            last_point: Cell::new((0.0, 0.0)),
+        })) as *mut DropTargetInterface;
+        let hr = RegisterDragDrop(hwnd, target);
+        if FAILED(hr) {
+            if !previous.is_null() {
+                RegisterDragDrop(hwnd, previous);
+            }
+            drop_target_release(target);
+            return Err(format!("RegisterDragDrop failed (HRESULT 0x{:08x})", hr));
+        }
+        DROP_TARGETS.with(|targets| targets.borrow_mut().push(target));
+        Ok(id)
+    }
+}
+
+// 在其他线程上调用时找不到挂载记录，返回 false；窗口已销毁时只释放目标
+pub fn detach_drop_target(id: u32) -> bool {
+    let target = DROP_TARGETS.with(|targets| {
+        let mut targets = targets.borrow_mut();
+        let index = targets.iter().position(|target| unsafe { DropTarget::from_interface(*target) }.id == id)?;
+        Some(targets.swap_remove(index))
+    });
+    let Some(target) = target else {
+        return false;
+    };
+    unsafe {
+        let object = DropTarget::from_interface(target);
+        if IsWindow(object.hwnd) != FALSE {
+            RevokeDragDrop(object.hwnd);
+            if !object.previous.is_null() {
+                RegisterDragDrop(object.hwnd, object.previous);
+            }
+        }
+        drop_target_release(target);
+    }
+    true
+}
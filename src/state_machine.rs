@@ -0,0 +1,593 @@
+// 输入处理的热路径：拖拽状态机、拖拽轨迹记录与滚轮节流
+// 这里不依赖 napi，监听线程和基准测试（benches/）共用同一份实现
+
+use std::time::{Duration, Instant};
+
+// 状态机的输入，坐标均为事件发生时的光标位置
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PointerInput {
+    Press { button: i32, x: f64, y: f64 },
+    Move { x: f64, y: f64 },
+    Release { button: i32, x: f64, y: f64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DragPhase {
+    Start,
+    Move,
+    End,
+    Cancel,
+}
+
+// 距离阈值与按住时间阈值的组合方式；按住时间阈值为 0 时只看距离
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThresholdMode {
+    // 任一阈值满足即开始拖拽，按住足够久时即使没有移动也会开始
+    Either,
+    // 两个阈值都满足才开始拖拽
+    Both,
+}
+
+// 状态机输出的一次拖拽状态变化
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragTransition {
+    pub phase: DragPhase,
+    pub x: f64,
+    pub y: f64,
+    pub start_x: f64,
+    pub start_y: f64,
+    pub button: i32,
+    pub session_id: u32,
+}
+
+pub struct DragStateMachine {
+    // Drag state
+    pub is_dragging: bool,
+    pub drag_start_position: Option<(f64, f64)>,
+    pub drag_button: Option<i32>,
+    // Drag session
+    pub drag_session_id: Option<u32>,
+    next_drag_session_id: u32,
+    // Distance threshold detection
+    pub mouse_pressed: bool,
+    pub potential_drag_start: Option<(f64, f64)>,
+    pub drag_threshold: f64,
+    // 本次按下所在显示器的阈值，为 None 时使用 drag_threshold
+    pub press_threshold: Option<f64>,
+    // Time threshold detection：按住的毫秒数阈值，0 表示不使用
+    pub drag_threshold_ms: u32,
+    pub threshold_mode: ThresholdMode,
+    pressed_at: Option<Instant>,
+    // 本次拖拽由按住时间触发，此后的移动不再受距离阈值过滤
+    started_by_hold: bool,
+    // 按下发生在开始监听之前，以第一次观察到的位置作为拖拽起点
+    awaiting_start: bool,
+}
+
+impl DragStateMachine {
+    pub fn new() -> Self {
+        Self {
+            // Drag state
+            is_dragging: false,
+            drag_start_position: None,
+            drag_button: None,
+            // Drag session
+            drag_session_id: None,
+            next_drag_session_id: 0,
+            // Distance threshold detection
+            mouse_pressed: false,
+            potential_drag_start: None,
+            drag_threshold: 5.0, // 5 pixels threshold
+            press_threshold: None,
+            drag_threshold_ms: 0,
+            threshold_mode: ThresholdMode::Either,
+            pressed_at: None,
+            started_by_hold: false,
+            awaiting_start: false,
+        }
+    }
+
+    // 重置拖拽状态，会话计数器保持递增
+    pub fn reset(&mut self) {
+        self.mouse_pressed = false;
+        self.is_dragging = false;
+        self.potential_drag_start = None;
+        self.drag_start_position = None;
+        self.drag_button = None;
+        self.drag_session_id = None;
+        self.press_threshold = None;
+        self.pressed_at = None;
+        self.started_by_hold = false;
+        self.awaiting_start = false;
+    }
+
+    // 放弃当前这次按下的拖拽起点，按下保持有效但不会再产生拖拽
+    pub fn ignore_press(&mut self) {
+        self.potential_drag_start = None;
+    }
+
+    // 开始监听（或恢复）时按键已被按住：补记一次按下，起点取之后第一次移动的位置
+    pub fn seed_press(&mut self, button: i32) {
+        self.reset();
+        self.mouse_pressed = true;
+        self.drag_button = Some(button);
+        self.pressed_at = Some(Instant::now());
+        self.awaiting_start = true;
+    }
+
+    // 在 (x, y) 处取消进行中的拖拽；没有进行中的拖拽时只重置状态
+    // 取消后直到下一次按下之前都不会再产生拖拽事件
+    pub fn cancel(&mut self, x: f64, y: f64) -> Option<DragTransition> {
+        let transition = match (self.is_dragging, self.drag_start_position) {
+            (true, Some(start)) => Some(self.transition(DragPhase::Cancel, x, y, start)),
+            _ => None,
+        };
+        self.reset();
+        transition
+    }
+
+    pub fn handle(&mut self, input: PointerInput) -> Option<DragTransition> {
+        match input {
+            PointerInput::Press { button, x, y } => {
+                // 记录鼠标按下状态，但不立即开始拖拽
+                self.mouse_pressed = true;
+                self.potential_drag_start = Some((x, y));
+                self.drag_button = Some(button);
+                self.press_threshold = None;
+                self.pressed_at = Some(Instant::now());
+                self.awaiting_start = false;
+                // 不触发 dragstart 事件，等待移动距离超过阈值
+                None
+            }
+            PointerInput::Move { x, y } => {
+                if !self.mouse_pressed {
+                    return None;
+                }
+                if self.awaiting_start {
+                    self.awaiting_start = false;
+                    self.potential_drag_start = Some((x, y));
+                    return None;
+                }
+                let (start_x, start_y) = self.potential_drag_start?;
+
+                // 计算移动距离
+                let delta_x = x - start_x;
+                let delta_y = y - start_y;
+                let distance = (delta_x * delta_x + delta_y * delta_y).sqrt();
+                let distance_reached = distance >= self.press_threshold.unwrap_or(self.drag_threshold);
+                let reached = if self.is_dragging {
+                    distance_reached || self.started_by_hold
+                } else {
+                    match self.threshold_mode {
+                        ThresholdMode::Either => distance_reached || self.hold_reached(),
+                        ThresholdMode::Both => distance_reached && self.press_held_long_enough(),
+                    }
+                };
+                if !reached {
+                    // 未超过阈值，不触发事件
+                    return None;
+                }
+                if !self.is_dragging && !distance_reached {
+                    self.started_by_hold = true;
+                }
+
+                let phase = if !self.is_dragging {
+                    // 首次超过阈值，开始拖拽
+                    self.begin_drag((start_x, start_y));
+                    DragPhase::Start
+                } else {
+                    // 已经在拖拽中，触发拖拽移动事件
+                    DragPhase::Move
+                };
+                Some(self.transition(phase, x, y, (start_x, start_y)))
+            }
+            PointerInput::Release { x, y, .. } => {
+                if !self.mouse_pressed {
+                    return None;
+                }
+                // is_dragging 为 true 时，drag_start_position 应该总是有值
+                let transition = match (self.is_dragging, self.drag_start_position) {
+                    (true, Some(start)) => Some(self.transition(DragPhase::End, x, y, start)),
+                    _ => None,
+                };
+                // 无论是否开始拖拽，都重置所有状态
+                self.reset();
+                transition
+            }
+        }
+    }
+
+    // Either 模式下按住时间阈值到期的时刻；没有等待中的按下时为 None
+    pub fn hold_deadline(&self) -> Option<Instant> {
+        if self.threshold_mode != ThresholdMode::Either
+            || self.drag_threshold_ms == 0
+            || self.is_dragging
+            || self.awaiting_start
+            || self.potential_drag_start.is_none()
+        {
+            return None;
+        }
+        self.pressed_at.map(|pressed_at| pressed_at + Duration::from_millis(self.drag_threshold_ms as u64))
+    }
+
+    // 按住时间阈值到期后调用，(x, y) 为当前光标位置；光标没有移动也会开始拖拽
+    pub fn check_hold(&mut self, x: f64, y: f64) -> Option<DragTransition> {
+        let deadline = self.hold_deadline()?;
+        if Instant::now() < deadline {
+            return None;
+        }
+        let start = self.potential_drag_start?;
+        self.started_by_hold = true;
+        self.begin_drag(start);
+        Some(self.transition(DragPhase::Start, x, y, start))
+    }
+
+    fn begin_drag(&mut self, start: (f64, f64)) {
+        self.is_dragging = true;
+        self.drag_start_position = Some(start);
+        self.next_drag_session_id += 1;
+        self.drag_session_id = Some(self.next_drag_session_id);
+    }
+
+    fn hold_reached(&self) -> bool {
+        self.drag_threshold_ms > 0 && self.press_held_long_enough()
+    }
+
+    fn press_held_long_enough(&self) -> bool {
+        match (self.drag_threshold_ms, self.pressed_at) {
+            (0, _) | (_, None) => true,
+            (threshold_ms, Some(pressed_at)) => pressed_at.elapsed().as_millis() >= threshold_ms as u128,
+        }
+    }
+
+    fn transition(&self, phase: DragPhase, x: f64, y: f64, (start_x, start_y): (f64, f64)) -> DragTransition {
+        DragTransition {
+            phase,
+            x,
+            y,
+            start_x,
+            start_y,
+            button: self.drag_button.unwrap_or(0),
+            session_id: self.drag_session_id.unwrap_or(0),
+        }
+    }
+}
+
+// 单次拖拽最多记录的轨迹点数，超出后只更新终点
+const MAX_PATH_POINTS: usize = 4096;
+
+// 拖拽轨迹记录：与上一个记录点的距离小于 min_distance 的移动被丢弃，起点和终点总是保留
+pub struct PathRecorder {
+    pub enabled: bool,
+    pub min_distance: f64,
+    points: Vec<(f64, f64)>,
+}
+
+impl PathRecorder {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            min_distance: 0.0,
+            points: Vec::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+
+    pub fn record(&mut self, transition: &DragTransition) {
+        if !self.enabled {
+            return;
+        }
+        let point = (transition.x, transition.y);
+        match transition.phase {
+            DragPhase::Start => {
+                self.points.clear();
+                self.points.push((transition.start_x, transition.start_y));
+                self.push(point);
+            }
+            DragPhase::Move => self.push(point),
+            DragPhase::End => {
+                // 终点总是保留，与最后一个记录点重合时不重复
+                if self.points.last() != Some(&point) {
+                    if self.points.len() >= MAX_PATH_POINTS {
+                        self.points.pop();
+                    }
+                    self.points.push(point);
+                }
+            }
+            DragPhase::Cancel => self.points.clear(),
+        }
+    }
+
+    fn push(&mut self, (x, y): (f64, f64)) {
+        if let Some(&(last_x, last_y)) = self.points.last() {
+            let (delta_x, delta_y) = (x - last_x, y - last_y);
+            if (delta_x * delta_x + delta_y * delta_y).sqrt() < self.min_distance {
+                return;
+            }
+        }
+        if self.points.len() < MAX_PATH_POINTS {
+            self.points.push((x, y));
+        }
+    }
+
+    pub fn points(&self) -> &[(f64, f64)] {
+        &self.points
+    }
+}
+
+// 滚轮节流：节流窗口内的位移被累积，下一次允许分发时一并发出
+pub struct WheelThrottle {
+    pub throttle_ms: u32, // 0 表示不节流
+    last_emit: Option<Instant>,
+    pending_delta: (f64, f64),
+    // 精确（像素）位移，与 pending_delta 一起累积、一起取走
+    pending_pixel_delta: (f64, f64),
+}
+
+impl WheelThrottle {
+    pub fn new() -> Self {
+        Self {
+            throttle_ms: 0,
+            last_emit: None,
+            pending_delta: (0.0, 0.0),
+            pending_pixel_delta: (0.0, 0.0),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.pending_delta = (0.0, 0.0);
+        self.pending_pixel_delta = (0.0, 0.0);
+    }
+
+    // 累积一次像素位移，在下一次 push/flush 返回合并位移之后用 take_pixels 取走
+    pub fn push_pixels(&mut self, delta_x: f64, delta_y: f64) {
+        self.pending_pixel_delta.0 += delta_x;
+        self.pending_pixel_delta.1 += delta_y;
+    }
+
+    pub fn take_pixels(&mut self) -> (f64, f64) {
+        std::mem::take(&mut self.pending_pixel_delta)
+    }
+
+    // 累积一次位移并立即返回合并位移，不受节流窗口限制；用于手势开始/结束这类不能被合并掉的事件
+    pub fn flush(&mut self, delta_x: f64, delta_y: f64, now: Instant) -> (f64, f64) {
+        self.pending_delta.0 += delta_x;
+        self.pending_delta.1 += delta_y;
+        self.last_emit = Some(now);
+        std::mem::take(&mut self.pending_delta)
+    }
+
+    // 累积一次位移，返回此刻应当分发的合并位移；仍在节流窗口内时返回 None
+    pub fn push(&mut self, delta_x: f64, delta_y: f64, now: Instant) -> Option<(f64, f64)> {
+        self.pending_delta.0 += delta_x;
+        self.pending_delta.1 += delta_y;
+
+        if let Some(last) = self.last_emit {
+            if now.duration_since(last).as_millis() < self.throttle_ms as u128 {
+                return None;
+            }
+        }
+        self.last_emit = Some(now);
+        Some(std::mem::take(&mut self.pending_delta))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    fn press(machine: &mut DragStateMachine, x: f64, y: f64) -> Option<DragTransition> {
+        machine.handle(PointerInput::Press { button: 1, x, y })
+    }
+
+    fn move_to(machine: &mut DragStateMachine, x: f64, y: f64) -> Option<DragTransition> {
+        machine.handle(PointerInput::Move { x, y })
+    }
+
+    fn release(machine: &mut DragStateMachine, x: f64, y: f64) -> Option<DragTransition> {
+        machine.handle(PointerInput::Release { button: 1, x, y })
+    }
+
+    fn phase(transition: Option<DragTransition>) -> Option<DragPhase> {
+        transition.map(|transition| transition.phase)
+    }
+
+    #[test]
+    fn test_distance_threshold() {
+        let mut machine = DragStateMachine::new();
+        assert_eq!(press(&mut machine, 10.0, 10.0), None);
+        assert_eq!(move_to(&mut machine, 13.0, 13.0), None);
+
+        let start = move_to(&mut machine, 14.0, 13.0).unwrap();
+        assert_eq!(start.phase, DragPhase::Start);
+        assert_eq!((start.start_x, start.start_y), (10.0, 10.0));
+        assert_eq!((start.x, start.y), (14.0, 13.0));
+        assert_eq!(start.button, 1);
+        assert!(machine.is_dragging);
+
+        // 拖拽中回到阈值以内的移动不产生 dragmove，松开仍然结束拖拽
+        assert_eq!(phase(move_to(&mut machine, 20.0, 10.0)), Some(DragPhase::Move));
+        assert_eq!(move_to(&mut machine, 11.0, 10.0), None);
+        let end = release(&mut machine, 11.0, 10.0).unwrap();
+        assert_eq!(end.phase, DragPhase::End);
+        assert_eq!(end.session_id, start.session_id);
+        assert!(!machine.is_dragging && !machine.mouse_pressed);
+    }
+
+    #[test]
+    fn test_press_threshold_overrides_default() {
+        let mut machine = DragStateMachine::new();
+        press(&mut machine, 0.0, 0.0);
+        machine.press_threshold = Some(20.0);
+        assert_eq!(move_to(&mut machine, 10.0, 0.0), None);
+        assert_eq!(phase(move_to(&mut machine, 20.0, 0.0)), Some(DragPhase::Start));
+        release(&mut machine, 20.0, 0.0);
+        assert_eq!(machine.press_threshold, None);
+    }
+
+    #[test]
+    fn test_click_without_drag() {
+        let mut machine = DragStateMachine::new();
+        assert_eq!(move_to(&mut machine, 50.0, 50.0), None);
+        press(&mut machine, 0.0, 0.0);
+        move_to(&mut machine, 1.0, 1.0);
+        assert_eq!(release(&mut machine, 1.0, 1.0), None);
+        assert_eq!(release(&mut machine, 1.0, 1.0), None);
+        assert_eq!(machine.drag_session_id, None);
+    }
+
+    #[test]
+    fn test_session_ids_increase() {
+        let mut machine = DragStateMachine::new();
+        let mut sessions = Vec::new();
+        for _ in 0..3 {
+            press(&mut machine, 0.0, 0.0);
+            sessions.push(move_to(&mut machine, 10.0, 0.0).unwrap().session_id);
+            release(&mut machine, 10.0, 0.0);
+        }
+        assert_eq!(sessions, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut machine = DragStateMachine::new();
+        assert_eq!(machine.cancel(0.0, 0.0), None);
+
+        press(&mut machine, 0.0, 0.0);
+        move_to(&mut machine, 10.0, 0.0);
+        let cancel = machine.cancel(12.0, 3.0).unwrap();
+        assert_eq!(cancel.phase, DragPhase::Cancel);
+        assert_eq!((cancel.x, cancel.y), (12.0, 3.0));
+        // 取消后直到下一次按下都不再产生拖拽
+        assert_eq!(move_to(&mut machine, 30.0, 0.0), None);
+        assert_eq!(release(&mut machine, 30.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_ignore_press() {
+        let mut machine = DragStateMachine::new();
+        press(&mut machine, 0.0, 0.0);
+        machine.ignore_press();
+        assert_eq!(move_to(&mut machine, 50.0, 0.0), None);
+        assert!(machine.mouse_pressed);
+        assert_eq!(release(&mut machine, 50.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_seed_press_starts_from_first_move() {
+        let mut machine = DragStateMachine::new();
+        machine.seed_press(1);
+        assert_eq!(move_to(&mut machine, 100.0, 100.0), None);
+        let start = move_to(&mut machine, 110.0, 100.0).unwrap();
+        assert_eq!(start.phase, DragPhase::Start);
+        assert_eq!((start.start_x, start.start_y), (100.0, 100.0));
+    }
+
+    #[test]
+    fn test_hold_timer_starts_drag_without_movement() {
+        let mut machine = DragStateMachine::new();
+        machine.drag_threshold_ms = 10;
+        assert_eq!(machine.hold_deadline(), None);
+        press(&mut machine, 5.0, 5.0);
+        assert!(machine.hold_deadline().is_some());
+        assert_eq!(machine.check_hold(5.0, 5.0), None);
+
+        thread::sleep(Duration::from_millis(20));
+        let start = machine.check_hold(6.0, 5.0).unwrap();
+        assert_eq!(start.phase, DragPhase::Start);
+        assert_eq!((start.start_x, start.start_y), (5.0, 5.0));
+        assert_eq!(machine.hold_deadline(), None);
+        // 按住触发的拖拽不再受距离阈值限制
+        assert_eq!(phase(move_to(&mut machine, 6.0, 6.0)), Some(DragPhase::Move));
+        assert_eq!(phase(release(&mut machine, 6.0, 6.0)), Some(DragPhase::End));
+    }
+
+    #[test]
+    fn test_hold_timer_reached_on_move() {
+        let mut machine = DragStateMachine::new();
+        machine.drag_threshold_ms = 10;
+        press(&mut machine, 0.0, 0.0);
+        assert_eq!(move_to(&mut machine, 1.0, 0.0), None);
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(phase(move_to(&mut machine, 1.0, 0.0)), Some(DragPhase::Start));
+    }
+
+    #[test]
+    fn test_both_mode_requires_distance_and_time() {
+        let mut machine = DragStateMachine::new();
+        machine.drag_threshold_ms = 10;
+        machine.threshold_mode = ThresholdMode::Both;
+        press(&mut machine, 0.0, 0.0);
+        assert_eq!(machine.hold_deadline(), None);
+        assert_eq!(move_to(&mut machine, 10.0, 0.0), None);
+
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(machine.check_hold(0.0, 0.0), None);
+        assert_eq!(move_to(&mut machine, 1.0, 0.0), None);
+        assert_eq!(phase(move_to(&mut machine, 10.0, 0.0)), Some(DragPhase::Start));
+    }
+
+    fn transition(phase: DragPhase, x: f64, y: f64) -> DragTransition {
+        DragTransition {
+            phase,
+            x,
+            y,
+            start_x: 0.0,
+            start_y: 0.0,
+            button: 1,
+            session_id: 1,
+        }
+    }
+
+    #[test]
+    fn test_path_recorder() {
+        let mut recorder = PathRecorder::new();
+        recorder.record(&transition(DragPhase::Start, 5.0, 0.0));
+        assert!(recorder.points().is_empty());
+
+        recorder.enabled = true;
+        recorder.min_distance = 3.0;
+        recorder.record(&transition(DragPhase::Start, 5.0, 0.0));
+        recorder.record(&transition(DragPhase::Move, 6.0, 0.0));
+        recorder.record(&transition(DragPhase::Move, 9.0, 0.0));
+        recorder.record(&transition(DragPhase::End, 10.0, 0.0));
+        assert_eq!(recorder.points(), &[(0.0, 0.0), (5.0, 0.0), (9.0, 0.0), (10.0, 0.0)]);
+
+        // 终点与最后一个记录点重合时不重复
+        recorder.record(&transition(DragPhase::Start, 5.0, 0.0));
+        recorder.record(&transition(DragPhase::End, 5.0, 0.0));
+        assert_eq!(recorder.points(), &[(0.0, 0.0), (5.0, 0.0)]);
+
+        recorder.record(&transition(DragPhase::Cancel, 5.0, 0.0));
+        assert!(recorder.points().is_empty());
+    }
+
+    #[test]
+    fn test_path_recorder_limit_keeps_end() {
+        let mut recorder = PathRecorder::new();
+        recorder.enabled = true;
+        recorder.record(&transition(DragPhase::Start, 1.0, 0.0));
+        for i in 2..MAX_PATH_POINTS * 2 {
+            recorder.record(&transition(DragPhase::Move, i as f64, 0.0));
+        }
+        recorder.record(&transition(DragPhase::End, -1.0, 0.0));
+        assert_eq!(recorder.points().len(), MAX_PATH_POINTS);
+        assert_eq!(recorder.points().last(), Some(&(-1.0, 0.0)));
+    }
+
+    #[test]
+    fn test_wheel_throttle_accumulates() {
+        let mut throttle = WheelThrottle::new();
+        throttle.throttle_ms = 50;
+        let now = Instant::now();
+        assert_eq!(throttle.push(1.0, 0.0, now), Some((1.0, 0.0)));
+        assert_eq!(throttle.push(1.0, 2.0, now + Duration::from_millis(10)), None);
+        assert_eq!(throttle.push(1.0, 2.0, now + Duration::from_millis(20)), None);
+        assert_eq!(throttle.push(0.0, 1.0, now + Duration::from_millis(60)), Some((2.0, 5.0)));
+        assert_eq!(throttle.flush(3.0, 0.0, now + Duration::from_millis(61)), (3.0, 0.0));
+    }
+}
@@ -0,0 +1,75 @@
+#!/usr/bin/env node
+
+// Drives drag detection end to end through __injectEvent, without real input or OS permissions.
+// Needs an addon built with the test-injection feature: npm run build:test
+
+const assert = require('assert');
+const plugin = require('..');
+
+if (typeof plugin.__injectEvent !== 'function') {
+    console.error('❌ __injectEvent is not available, build the addon with: npm run build:test');
+    process.exit(1);
+}
+
+function inject(event) {
+    plugin.__injectEvent(JSON.stringify(event));
+}
+
+// Callbacks are queued to the event loop through threadsafe functions, let them run before checking
+function flush() {
+    return new Promise((resolve) => setTimeout(resolve, 50));
+}
+
+async function collectDragEvents(inputs) {
+    const events = [];
+    const id = await plugin.onDragEvent((err, event) => {
+        if (!err) {
+            events.push(event);
+        }
+    });
+    try {
+        inputs.forEach(inject);
+        await flush();
+    } finally {
+        await plugin.removeDragEventListener(id);
+    }
+    return events;
+}
+
+async function testPressMoveRelease() {
+    const events = await collectDragEvents([
+        { eventType: 'mousemove', x: 100, y: 100 },
+        { eventType: 'mousedown', x: 100, y: 100 },
+        { eventType: 'mousemove', x: 120, y: 100 },
+        { eventType: 'mouseup', x: 120, y: 100 },
+    ]);
+
+    assert.deepStrictEqual(events.map((event) => event.eventType), ['dragstart', 'dragend']);
+    const [start, end] = events;
+    assert.strictEqual(start.button, 1);
+    assert.strictEqual(end.sessionId, start.sessionId);
+    assert.strictEqual(end.reason, 'release');
+}
+
+async function testClickWithoutDrag() {
+    const events = await collectDragEvents([
+        { eventType: 'mousemove', x: 100, y: 100 },
+        { eventType: 'mousedown', x: 100, y: 100 },
+        { eventType: 'mousemove', x: 102, y: 100 },
+        { eventType: 'mouseup', x: 102, y: 100 },
+    ]);
+
+    assert.deepStrictEqual(events, []);
+}
+
+async function main() {
+    for (const test of [testPressMoveRelease, testClickWithoutDrag]) {
+        await test();
+        console.log(`✅ ${test.name}`);
+    }
+}
+
+main().catch((error) => {
+    console.error('❌', error);
+    process.exit(1);
+});